@@ -0,0 +1,186 @@
+// จัดเรียงตัวแปรภายนอก (exogenous) ให้ตรงกับแท่งของ series หลักก่อนใส่ ARMAX
+// ทุกคอลัมน์เป็น Vec<Option<f64>> ยาวเท่ากับ timestamp หลัก (None = ไม่มีข้อมูล)
+
+use chrono::{DateTime, Datelike, Utc, Weekday};
+
+use crate::module::data::read_csv::Bar;
+
+/// รองรับทั้งวินาทีและมิลลิวินาที (เหมือน plot_graph)
+fn to_datetime(ts: i64) -> Option<DateTime<Utc>> {
+    if ts.abs() >= 1_000_000_000_000 {
+        DateTime::from_timestamp_millis(ts)
+    } else {
+        DateTime::from_timestamp(ts, 0)
+    }
+}
+
+/// ค่าล่าสุดของ other ที่ timestamp <= base_ts[i] (as-of join, other ต้องเรียงตามเวลา)
+pub fn align_asof(base_ts: &[i64], other: &[(i64, f64)]) -> Vec<Option<f64>> {
+    let mut j = 0;
+    let mut last = None;
+    base_ts
+        .iter()
+        .map(|&ts| {
+            while j < other.len() && other[j].0 <= ts {
+                last = Some(other[j].1).filter(|v| v.is_finite());
+                j += 1;
+            }
+            last
+        })
+        .collect()
+}
+
+/// เลื่อนไปข้างหลัง k แท่ง: out[t] = xs[t - k]
+pub fn lag(xs: &[Option<f64>], k: usize) -> Vec<Option<f64>> {
+    (0..xs.len())
+        .map(|t| if t >= k { xs[t - k] } else { None })
+        .collect()
+}
+
+/// ln(x_t / x_{t-1}) ยาวเท่าเดิม (ตัวแรก None)
+pub fn log_returns(xs: &[Option<f64>]) -> Vec<Option<f64>> {
+    (0..xs.len())
+        .map(|t| match (t.checked_sub(1).and_then(|i| xs[i]), xs[t]) {
+            (Some(a), Some(b)) if a > 0.0 && b > 0.0 => Some((b / a).ln()),
+            _ => None,
+        })
+        .collect()
+}
+
+pub fn log_volume(bars: &[Bar]) -> Vec<Option<f64>> {
+    bars.iter()
+        .map(|b| b.volume.filter(|v| *v > 0).map(|v| (v as f64).ln()))
+        .collect()
+}
+
+/// dummy จันทร์–พฤหัส (ศุกร์เป็น baseline กัน collinear กับค่าคงที่)
+pub fn day_of_week_dummies(timestamps: &[i64]) -> Vec<(String, Vec<Option<f64>>)> {
+    [Weekday::Mon, Weekday::Tue, Weekday::Wed, Weekday::Thu]
+        .into_iter()
+        .map(|day| {
+            let col = timestamps
+                .iter()
+                .map(|&ts| to_datetime(ts).map(|d| f64::from(u8::from(d.weekday() == day))))
+                .collect();
+            (format!("dow_{day}"), col)
+        })
+        .collect()
+}
+
+/// ตัวแปรภายนอกแบบคอลัมน์ ทุกคอลัมน์ยาว n เท่ากัน
+#[derive(Debug, Clone, Default)]
+pub struct DesignMatrix {
+    pub names: Vec<String>,
+    pub columns: Vec<Vec<Option<f64>>>,
+}
+
+impl DesignMatrix {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.columns.first().map_or(0, |c| c.len())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn n_cols(&self) -> usize {
+        self.columns.len()
+    }
+
+    pub fn push(&mut self, name: impl Into<String>, column: Vec<Option<f64>>) {
+        assert!(
+            self.columns.is_empty() || column.len() == self.len(),
+            "column length mismatch"
+        );
+        self.names.push(name.into());
+        self.columns.push(column);
+    }
+
+    pub fn with(mut self, name: impl Into<String>, column: Vec<Option<f64>>) -> Self {
+        self.push(name, column);
+        self
+    }
+
+    /// เติมช่องว่างตรงกลางด้วยค่าก่อนหน้า (ช่วงต้นที่ยังไม่มีค่ายังเป็น None)
+    pub fn forward_fill(mut self) -> Self {
+        for col in self.columns.iter_mut() {
+            let mut last = None;
+            for v in col.iter_mut() {
+                match v {
+                    Some(x) => last = Some(*x),
+                    None => *v = last,
+                }
+            }
+        }
+        self
+    }
+
+    /// index แรกที่ทุกแถวหลังจากนั้นครบทุกคอลัมน์ (None ถ้าไม่มี)
+    pub fn complete_from(&self) -> Option<usize> {
+        let n = self.len();
+        let mut start = n;
+        for t in (0..n).rev() {
+            if self.columns.iter().all(|c| c[t].is_some()) {
+                start = t;
+            } else {
+                break;
+            }
+        }
+        (start < n).then_some(start)
+    }
+
+    /// แถว [start, end) เป็น Vec ของแถว (NaN แทนช่องที่หายไป)
+    pub fn rows(&self, start: usize, end: usize) -> Vec<Vec<f64>> {
+        (start..end)
+            .map(|t| {
+                self.columns
+                    .iter()
+                    .map(|c| c[t].unwrap_or(f64::NAN))
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_align_asof_and_lag() {
+        let base = [10, 20, 30, 40];
+        let other = [(5, 1.0), (20, 2.0), (35, 3.0)];
+        assert_eq!(
+            align_asof(&base, &other),
+            vec![Some(1.0), Some(2.0), Some(2.0), Some(3.0)]
+        );
+        assert_eq!(
+            lag(&[Some(1.0), Some(2.0), Some(3.0)], 1),
+            vec![None, Some(1.0), Some(2.0)]
+        );
+    }
+
+    #[test]
+    fn test_day_of_week_dummies() {
+        // 2024-01-01 เป็นวันจันทร์, 2024-01-05 เป็นวันศุกร์
+        let ts = [1_704_110_400, 1_704_456_000];
+        let cols = day_of_week_dummies(&ts);
+        assert_eq!(cols[0].0, "dow_Mon");
+        assert_eq!(cols[0].1, vec![Some(1.0), Some(0.0)]);
+        assert!(cols.iter().all(|(_, c)| c[1] == Some(0.0)));
+    }
+
+    #[test]
+    fn test_design_matrix_complete_from() {
+        let x = DesignMatrix::new()
+            .with("a", vec![None, Some(1.0), None, Some(2.0)])
+            .with("b", vec![None, None, Some(1.0), Some(1.0)])
+            .forward_fill();
+        assert_eq!(x.complete_from(), Some(2));
+        assert_eq!(x.rows(2, 4), vec![vec![1.0, 1.0], vec![2.0, 1.0]]);
+    }
+}
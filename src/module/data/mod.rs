@@ -1,3 +1,4 @@
+pub mod align;
 pub mod fetch_data;
 pub mod read_csv;
 pub mod save_data;
@@ -9,12 +9,28 @@ struct CsvRow3 {
     close: f64,
 }
 
+/// แท่งข้อมูลหนึ่งแท่งตามที่เก็บใน CSV (timestamp, volume, close)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Bar {
+    pub timestamp: i64,
+    pub volume: Option<u64>,
+    pub close: f64,
+}
+
 pub fn read_close_series(csv_path: &PathBuf) -> Result<Vec<(i64, f64)>, Box<dyn Error>> {
+    Ok(read_bars(csv_path)?
+        .into_iter()
+        .map(|b| (b.timestamp, b.close))
+        .collect())
+}
+
+/// อ่านทุกคอลัมน์ (รวม volume) เรียงตาม timestamp
+pub fn read_bars(csv_path: &PathBuf) -> Result<Vec<Bar>, Box<dyn Error>> {
     // Try headered 3-column file first: timestamp,volume,close
     let mut rdr = ReaderBuilder::new()
         .trim(csv::Trim::All)
         .from_path(csv_path)?;
-    let mut rows: Vec<(i64, f64)> = Vec::new();
+    let mut rows: Vec<Bar> = Vec::new();
     let mut tried_headered = false;
 
     if let Ok(h) = rdr.headers() {
@@ -24,7 +40,11 @@ pub fn read_close_series(csv_path: &PathBuf) -> Result<Vec<(i64, f64)>, Box<dyn
             tried_headered = true;
             for (i, r) in rdr.deserialize::<CsvRow3>().enumerate() {
                 match r {
-                    Ok(row) => rows.push((row.timestamp, row.close)),
+                    Ok(row) => rows.push(Bar {
+                        timestamp: row.timestamp,
+                        volume: row.volume,
+                        close: row.close,
+                    }),
                     Err(e) => {
                         if i < 5 {
                             eprintln!("CSV warn: skip row {}: {}", i + 1, e);
@@ -73,11 +93,16 @@ pub fn read_close_series(csv_path: &PathBuf) -> Result<Vec<(i64, f64)>, Box<dyn
                 Some(v) => v,
                 None => continue,
             };
-            rows.push((ts, close));
+            let volume: Option<u64> = rec.get(1).and_then(|s| s.parse().ok());
+            rows.push(Bar {
+                timestamp: ts,
+                volume,
+                close,
+            });
         }
     }
 
     // Sort by timestamp just in case
-    rows.sort_by_key(|(ts, _)| *ts);
+    rows.sort_by_key(|b| b.timestamp);
     Ok(rows)
 }
@@ -0,0 +1,218 @@
+// confusion matrix แบบ 3 คลาส (ลง / ทรงตัว / ขึ้น)
+
+use crate::module::eval::Outcome;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Direction {
+    Down,
+    Flat,
+    Up,
+}
+
+impl Direction {
+    pub const ALL: [Direction; 3] = [Direction::Down, Direction::Flat, Direction::Up];
+
+    pub fn from_sign(s: i8) -> Self {
+        match s.signum() {
+            1 => Direction::Up,
+            -1 => Direction::Down,
+            _ => Direction::Flat,
+        }
+    }
+
+    pub fn index(self) -> usize {
+        match self {
+            Direction::Down => 0,
+            Direction::Flat => 1,
+            Direction::Up => 2,
+        }
+    }
+}
+
+/// metric แบบหลายคลาสจาก confusion matrix
+#[derive(Clone, Copy, Debug)]
+pub struct MultiClassMetrics {
+    pub accuracy: f64,
+    pub macro_precision: f64,
+    pub macro_recall: f64,
+    pub macro_f1: f64,
+    /// micro average รวม tp / predicted / actual ทุกคลาสก่อนหาร
+    /// (single-label ทุกจุดอยู่ในคลาสเดียว -> ทั้งสามค่าเท่ากับ accuracy)
+    pub micro_precision: f64,
+    pub micro_recall: f64,
+    pub micro_f1: f64,
+    pub kappa: f64,
+    pub mcc: f64,
+}
+
+/// counts[pred][actual] เรียงตาม Direction::index
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ConfusionMatrix {
+    pub counts: [[usize; 3]; 3],
+}
+
+impl ConfusionMatrix {
+    pub fn from_outcomes(outcomes: &[Option<Outcome>]) -> Self {
+        let mut m = Self::default();
+        for o in outcomes.iter().flatten() {
+            m.add(Direction::from_sign(o.pred), Direction::from_sign(o.actual));
+        }
+        m
+    }
+
+    pub fn add(&mut self, pred: Direction, actual: Direction) {
+        self.counts[pred.index()][actual.index()] += 1;
+    }
+
+    pub fn get(&self, pred: Direction, actual: Direction) -> usize {
+        self.counts[pred.index()][actual.index()]
+    }
+
+    pub fn total(&self) -> usize {
+        self.counts.iter().flatten().sum()
+    }
+
+    /// จำนวนที่ทายตรงคลาส (เส้นทแยง)
+    pub fn correct(&self) -> usize {
+        (0..3).map(|i| self.counts[i][i]).sum()
+    }
+
+    pub fn predicted(&self, d: Direction) -> usize {
+        self.counts[d.index()].iter().sum()
+    }
+
+    pub fn actual(&self, d: Direction) -> usize {
+        self.counts.iter().map(|row| row[d.index()]).sum()
+    }
+
+    /// macro average คิดเฉพาะคลาสที่ปรากฏ (มีทั้ง actual หรือ predicted อย่างน้อยหนึ่งครั้ง)
+    /// precision/recall ที่หารด้วยศูนย์นับเป็น 0
+    pub fn metrics(&self) -> MultiClassMetrics {
+        let total = self.total();
+        if total == 0 {
+            return MultiClassMetrics {
+                accuracy: f64::NAN,
+                macro_precision: f64::NAN,
+                macro_recall: f64::NAN,
+                macro_f1: f64::NAN,
+                micro_precision: f64::NAN,
+                micro_recall: f64::NAN,
+                micro_f1: f64::NAN,
+                kappa: f64::NAN,
+                mcc: f64::NAN,
+            };
+        }
+        let n = total as f64;
+        let correct = self.correct() as f64;
+        let accuracy = correct / n;
+
+        let ratio = |a: usize, b: usize| if b == 0 { 0.0 } else { a as f64 / b as f64 };
+        let (mut sum_p, mut sum_r, mut sum_f, mut classes) = (0.0, 0.0, 0.0, 0usize);
+        let (mut pe, mut sum_pt, mut sum_p2, mut sum_t2) = (0.0, 0.0, 0.0, 0.0);
+        let (mut all_tp, mut all_pred, mut all_act) = (0usize, 0usize, 0usize);
+        for d in Direction::ALL {
+            let tp = self.get(d, d);
+            let pred = self.predicted(d);
+            let act = self.actual(d);
+            all_tp += tp;
+            all_pred += pred;
+            all_act += act;
+            let (pk, tk) = (pred as f64, act as f64);
+            pe += pk * tk / (n * n);
+            sum_pt += pk * tk;
+            sum_p2 += pk * pk;
+            sum_t2 += tk * tk;
+            if pred == 0 && act == 0 {
+                continue;
+            }
+            let precision = ratio(tp, pred);
+            let recall = ratio(tp, act);
+            let f1 = if precision + recall > 0.0 {
+                2.0 * precision * recall / (precision + recall)
+            } else {
+                0.0
+            };
+            sum_p += precision;
+            sum_r += recall;
+            sum_f += f1;
+            classes += 1;
+        }
+        let c = classes as f64;
+        let micro_precision = ratio(all_tp, all_pred);
+        let micro_recall = ratio(all_tp, all_act);
+        let micro_f1 = if micro_precision + micro_recall > 0.0 {
+            2.0 * micro_precision * micro_recall / (micro_precision + micro_recall)
+        } else {
+            0.0
+        };
+
+        let kappa = if (1.0 - pe).abs() < f64::EPSILON {
+            f64::NAN
+        } else {
+            (accuracy - pe) / (1.0 - pe)
+        };
+        // Gorodkin (2004) multi-class MCC
+        let denom = ((n * n - sum_p2) * (n * n - sum_t2)).sqrt();
+        let mcc = if denom > 0.0 {
+            (correct * n - sum_pt) / denom
+        } else {
+            f64::NAN
+        };
+
+        MultiClassMetrics {
+            accuracy,
+            macro_precision: sum_p / c,
+            macro_recall: sum_r / c,
+            macro_f1: sum_f / c,
+            micro_precision,
+            micro_recall,
+            micro_f1,
+            kappa,
+            mcc,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_binary_mcc_and_kappa_reference() {
+        // TP=20 FP=5 FN=10 TN=65 (ขึ้น/ลง)
+        let mut m = ConfusionMatrix::default();
+        m.counts[Direction::Up.index()][Direction::Up.index()] = 20;
+        m.counts[Direction::Up.index()][Direction::Down.index()] = 5;
+        m.counts[Direction::Down.index()][Direction::Up.index()] = 10;
+        m.counts[Direction::Down.index()][Direction::Down.index()] = 65;
+        let r = m.metrics();
+        let mcc = (20.0 * 65.0 - 5.0 * 10.0) / (25.0f64 * 30.0 * 70.0 * 75.0).sqrt();
+        assert!((r.mcc - mcc).abs() < 1e-12);
+        // po = 0.85, pe = 0.25*0.30 + 0.75*0.70 = 0.60
+        assert!((r.kappa - 0.625).abs() < 1e-12);
+        assert!((r.micro_f1 - 0.85).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_three_class_macro_average() {
+        let mut m = ConfusionMatrix::default();
+        for (p, a, k) in [
+            (Direction::Up, Direction::Up, 3),
+            (Direction::Up, Direction::Flat, 1),
+            (Direction::Flat, Direction::Flat, 2),
+            (Direction::Down, Direction::Down, 4),
+        ] {
+            for _ in 0..k {
+                m.add(p, a);
+            }
+        }
+        let r = m.metrics();
+        // precision: up 3/4, flat 1, down 1 ; recall: up 1, flat 2/3, down 1
+        assert!((r.macro_precision - (0.75 + 1.0 + 1.0) / 3.0).abs() < 1e-12);
+        assert!((r.macro_recall - (1.0 + 2.0 / 3.0 + 1.0) / 3.0).abs() < 1e-12);
+        assert!((r.accuracy - 0.9).abs() < 1e-12);
+        assert!((r.micro_precision - 0.9).abs() < 1e-12);
+        assert!((r.micro_recall - 0.9).abs() < 1e-12);
+        assert!((r.micro_f1 - 0.9).abs() < 1e-12);
+    }
+}
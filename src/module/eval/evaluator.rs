@@ -0,0 +1,331 @@
+// จุดเข้าใช้งานเดียวของการประเมินทิศทาง ใช้ได้ทั้งค่าพยากรณ์ตัวเลขและสัญญาณ buy/sell
+// ทุกเส้นทางแปลงเป็น Outcome ก่อน แล้วนับด้วยกติกาเดียวกัน ตัวเลขจึงตรงกันทุกที่
+
+use crate::module::eval::{
+    ClassificationMetrics, DeadBand, EvalReport, MetricIntervals, Outcome, TargetKind, ZeroRule,
+    calculate, confusion::ConfusionMatrix, sgn_band,
+};
+use crate::module::util::math::bootstrap::{
+    BootstrapConfig, Interval, bootstrap_draws, percentile_interval,
+};
+
+#[derive(Clone, Copy, Debug)]
+pub struct Evaluator {
+    pub target: TargetKind,
+    pub zero_rule: ZeroRule,
+    /// เกณฑ์ flat ของการเปลี่ยนแปลงจริง
+    pub dead_band: DeadBand,
+    /// เกณฑ์ flat ของค่าพยากรณ์ (ใช้กับ forecast ตัวเลขเท่านั้น)
+    pub pred_dead_band: DeadBand,
+}
+
+#[derive(Clone, Debug)]
+pub struct Evaluation {
+    pub report: EvalReport,
+    pub metrics: ClassificationMetrics,
+    pub confusion: ConfusionMatrix,
+    /// None = abstain (ไม่มีสัญญาณ หรือข้อมูลไม่ finite)
+    pub outcomes: Vec<Option<Outcome>>,
+}
+
+/// ค่าเริ่มต้นตรงกับ eval_with_signals เดิม: แท่งที่ close ไม่เปลี่ยนนับเป็นลง
+impl Default for Evaluator {
+    fn default() -> Self {
+        Self::new(TargetKind::Diff, ZeroRule::CountAsDown)
+    }
+}
+
+impl Evaluator {
+    pub fn new(target: TargetKind, zero_rule: ZeroRule) -> Self {
+        Self {
+            target,
+            zero_rule,
+            dead_band: DeadBand::None,
+            pred_dead_band: DeadBand::None,
+        }
+    }
+
+    /// evaluator ของสัญญาณ buy/sell: ไม่มี band -> กติกาเดิม (flat = ลง),
+    /// มี band -> แท่ง flat ไม่ถูกนับ (ไม่งั้น band จะถูกกลืนกลับเป็น "ลง")
+    pub fn for_signals(dead_band: DeadBand) -> Self {
+        match dead_band {
+            DeadBand::None => Self::default(),
+            band => {
+                Self::new(TargetKind::Diff, ZeroRule::Ignore).with_dead_band(band, DeadBand::None)
+            }
+        }
+    }
+
+    pub fn with_dead_band(mut self, actual: DeadBand, pred: DeadBand) -> Self {
+        self.dead_band = actual;
+        self.pred_dead_band = pred;
+        self
+    }
+
+    /// outcome จากค่าพยากรณ์ pred_next[i] ของช่วง actual_levels[i] -> actual_levels[i + 1]
+    pub fn forecast_outcomes(
+        &self,
+        actual_levels: &[f64],
+        pred_next: &[f64],
+    ) -> Vec<Option<Outcome>> {
+        let n = actual_levels.len().saturating_sub(1);
+        assert!(pred_next.len() >= n, "pred_next too short");
+        let changes: Vec<f64> = actual_levels.windows(2).map(|w| w[1] - w[0]).collect();
+        let pred_changes: Vec<f64> = (0..n)
+            .map(|i| match self.target {
+                TargetKind::Level => pred_next[i] - actual_levels[i],
+                TargetKind::Diff => pred_next[i],
+            })
+            .collect();
+        let band = self.dead_band.thresholds(&changes, actual_levels);
+        let pred_band = self.pred_dead_band.thresholds(&pred_changes, actual_levels);
+        (0..n)
+            .map(|i| {
+                if !changes[i].is_finite()
+                    || !pred_changes[i].is_finite()
+                    || band[i].is_nan()
+                    || pred_band[i].is_nan()
+                {
+                    return None;
+                }
+                Some(Outcome {
+                    pred: sgn_band(pred_changes[i], pred_band[i]),
+                    actual: sgn_band(changes[i], band[i]),
+                })
+            })
+            .collect()
+    }
+
+    /// outcome จากสัญญาณ: Some(true) = ทายขึ้น, Some(false) = ทายลง, None = abstain
+    pub fn signal_outcomes(&self, close: &[f64], signal: &[Option<bool>]) -> Vec<Option<Outcome>> {
+        let changes: Vec<f64> = close.windows(2).map(|w| w[1] - w[0]).collect();
+        let band = self.dead_band.thresholds(&changes, close);
+        (0..changes.len())
+            .map(|t| {
+                let pred_up = signal.get(t).copied().flatten()?;
+                if !changes[t].is_finite() || band[t].is_nan() {
+                    return None;
+                }
+                Some(Outcome {
+                    pred: if pred_up { 1 } else { -1 },
+                    actual: sgn_band(changes[t], band[t]),
+                })
+            })
+            .collect()
+    }
+
+    /// นับ outcome เป็น EvalReport ตาม zero_rule
+    pub fn tally(&self, outcomes: &[Option<Outcome>]) -> EvalReport {
+        let mut r = EvalReport::default();
+        for o in outcomes {
+            let Some(Outcome {
+                mut pred,
+                mut actual,
+            }) = *o
+            else {
+                r.abstained += 1;
+                r.skipped += 1;
+                continue;
+            };
+            if actual == 0 || pred == 0 {
+                match self.zero_rule {
+                    ZeroRule::CountAsMiss => {
+                        r.total += 1;
+                        continue;
+                    }
+                    ZeroRule::Ignore => {
+                        r.skipped += 1;
+                        continue;
+                    }
+                    ZeroRule::CountAsDown => {
+                        pred = if pred == 0 { -1 } else { pred };
+                        actual = if actual == 0 { -1 } else { actual };
+                    }
+                }
+            }
+            r.total += 1;
+            match (pred > 0, actual > 0) {
+                (true, true) => {
+                    r.hits += 1;
+                    r.up_up += 1;
+                }
+                (false, false) => {
+                    r.hits += 1;
+                    r.down_down += 1;
+                }
+                (true, false) => r.up_down += 1,
+                (false, true) => r.down_up += 1,
+            }
+        }
+        r
+    }
+
+    pub fn evaluate(&self, outcomes: Vec<Option<Outcome>>) -> Evaluation {
+        let report = self.tally(&outcomes);
+        Evaluation {
+            metrics: calculate(&report),
+            confusion: ConfusionMatrix::from_outcomes(&outcomes),
+            report,
+            outcomes,
+        }
+    }
+
+    pub fn evaluate_forecasts(&self, actual_levels: &[f64], pred_next: &[f64]) -> Evaluation {
+        self.evaluate(self.forecast_outcomes(actual_levels, pred_next))
+    }
+
+    pub fn evaluate_signals(&self, close: &[f64], signal: &[Option<bool>]) -> Evaluation {
+        self.evaluate(self.signal_outcomes(close, signal))
+    }
+
+    /// block/stationary bootstrap ของทุก metric โดยสุ่ม outcome เป็นช่วง ๆ เพื่อเก็บ autocorrelation
+    pub fn bootstrap_intervals(
+        &self,
+        outcomes: &[Option<Outcome>],
+        config: &BootstrapConfig,
+    ) -> MetricIntervals {
+        let draws = bootstrap_draws(outcomes, config, |sample| {
+            let m = calculate(&self.tally(sample));
+            vec![m.accuracy, m.precision, m.recall, m.f1]
+        });
+        let ci = |i: usize| {
+            draws
+                .get(i)
+                .map(|d| percentile_interval(d, config.level))
+                .unwrap_or_else(Interval::nan)
+        };
+        MetricIntervals {
+            accuracy: ci(0),
+            precision: ci(1),
+            recall: ci(2),
+            f1: ci(3),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_signals_and_forecasts_agree() {
+        let close = [10.0, 11.0, 10.5, 10.5, 12.0, 11.0];
+        let signal = [Some(true), Some(true), None, Some(true), Some(false)];
+        // ค่าพยากรณ์ Δ ที่มีทิศเดียวกับสัญญาณ, จุดที่ abstain ใส่ NaN
+        let pred = [0.3, 0.2, f64::NAN, 0.1, -0.4];
+        for zero_rule in [ZeroRule::CountAsMiss, ZeroRule::Ignore] {
+            let ev = Evaluator::new(TargetKind::Diff, zero_rule);
+            let a = ev.evaluate_signals(&close, &signal);
+            let b = ev.evaluate_forecasts(&close, &pred);
+            assert_eq!(a.outcomes, b.outcomes);
+            assert_eq!(a.report.hits, b.report.hits);
+            assert_eq!(a.report.total, b.report.total);
+            assert_eq!(a.report.abstained, 1);
+        }
+    }
+
+    #[test]
+    fn test_zero_rule_and_confusion() {
+        let close = [1.0, 2.0, 2.0, 1.0];
+        let signal = [Some(true), Some(true), Some(true)];
+        let miss = Evaluator::new(TargetKind::Diff, ZeroRule::CountAsMiss)
+            .evaluate_signals(&close, &signal);
+        assert_eq!((miss.report.hits, miss.report.total), (1, 3));
+        let ignore =
+            Evaluator::new(TargetKind::Diff, ZeroRule::Ignore).evaluate_signals(&close, &signal);
+        assert_eq!((ignore.report.hits, ignore.report.total), (1, 2));
+        assert!((ignore.metrics.accuracy - 0.5).abs() < 1e-12);
+
+        use crate::module::eval::confusion::Direction;
+        assert_eq!(miss.confusion.get(Direction::Up, Direction::Flat), 1);
+        assert_eq!(miss.confusion.total(), 3);
+    }
+
+    #[test]
+    fn test_dead_band_turns_small_moves_flat() {
+        let close = [100.0, 100.05, 101.0, 100.98, 99.0, 99.0];
+        let pred = [0.01, 0.5, -0.01, -0.9, 0.2];
+        let ev = Evaluator::new(TargetKind::Diff, ZeroRule::Ignore)
+            .with_dead_band(DeadBand::Absolute(0.1), DeadBand::Absolute(0.05));
+        let res = ev.evaluate_forecasts(&close, &pred);
+        let actual: Vec<i8> = res.outcomes.iter().map(|o| o.unwrap().actual).collect();
+        let predicted: Vec<i8> = res.outcomes.iter().map(|o| o.unwrap().pred).collect();
+        assert_eq!(actual, vec![0, 1, 0, -1, 0]);
+        assert_eq!(predicted, vec![0, 1, 0, -1, 1]);
+        // เหลือแค่ 2 จุดที่ขยับชัดเจนทั้งคู่ และทายถูกทั้งคู่
+        assert_eq!((res.report.hits, res.report.total), (2, 2));
+        assert!((res.confusion.metrics().accuracy - 0.8).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_default_matches_legacy_signal_eval() {
+        // eval_with_signals เดิม: actual_up = close[t+1] > close[t] -> แท่ง flat นับเป็นลง
+        let close = [1.0, 2.0, 2.0, 2.0, 1.0];
+        let signal = [Some(true), Some(false), Some(true), Some(false)];
+        let ev = Evaluator::default().evaluate_signals(&close, &signal);
+        assert_eq!((ev.report.hits, ev.report.total), (3, 4));
+        assert_eq!(
+            (
+                ev.report.up_up,
+                ev.report.up_down,
+                ev.report.down_up,
+                ev.report.down_down
+            ),
+            (1, 1, 0, 2)
+        );
+        assert!((ev.metrics.accuracy - 0.75).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_sigma_band_uses_trailing_changes_only() {
+        let mut close = vec![100.0];
+        for t in 0..20 {
+            let step = if t % 2 == 0 { 1.0 } else { -1.0 };
+            close.push(close[t] + step);
+        }
+        // แท่งสุดท้ายกระโดดแรง: ถ้า σ รวมอนาคต threshold ของแท่งก่อน ๆ จะเปลี่ยน
+        let mut shocked = close.clone();
+        shocked.push(close[20] + 50.0);
+        close.push(close[20] + 0.5);
+
+        let ev = Evaluator::for_signals(DeadBand::Sigma(1.0));
+        let signal = vec![Some(true); 21];
+        let a = ev.signal_outcomes(&close, &signal);
+        let b = ev.signal_outcomes(&shocked, &signal);
+        assert_eq!(a[..20], b[..20]);
+        // 2 แท่งแรกยังไม่มีประวัติพอ -> abstain
+        assert!(a[0].is_none() && a[1].is_none());
+        assert_eq!(a[2].unwrap().actual, 0);
+        // 0.5 < σ(±1) ≈ 1.03 -> flat, 50 -> ขึ้น
+        assert_eq!(a[20].unwrap().actual, 0);
+        assert_eq!(b[20].unwrap().actual, 1);
+    }
+
+    #[test]
+    fn test_sigma_band_is_relative_to_price_level() {
+        // ผลตอบแทนชุดเดียวกันที่ระดับราคาต่างกัน 40 เท่า ต้องได้ flat / ขึ้น / ลง ตรงกัน
+        let returns = [0.01, -0.012, 0.0005, 0.008, -0.0002, -0.009, 0.011, 0.0001];
+        let path = |start: f64| {
+            let mut close = vec![start];
+            for r in returns.iter().cycle().take(40) {
+                close.push(close.last().unwrap() * (1.0 + r));
+            }
+            close
+        };
+        let (low, high) = (path(100.0), path(4000.0));
+        let ev = Evaluator::for_signals(DeadBand::Sigma(0.2));
+        let signal = vec![Some(true); 40];
+        let a = ev.signal_outcomes(&low, &signal);
+        assert_eq!(a, ev.signal_outcomes(&high, &signal));
+        let actual: Vec<i8> = a[8..16].iter().map(|o| o.unwrap().actual).collect();
+        assert_eq!(actual, vec![1, -1, 0, 1, 0, -1, 1, 0]);
+    }
+
+    #[test]
+    fn test_empty_metrics_are_nan() {
+        // เปลี่ยนจาก 0.0 ของ eval_with_signals เดิมโดยตั้งใจ
+        let ev = Evaluator::default().evaluate_signals(&[1.0, 2.0], &[None]);
+        assert!(ev.metrics.accuracy.is_nan());
+        assert!(ev.metrics.f1.is_nan());
+    }
+}
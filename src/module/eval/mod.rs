@@ -0,0 +1,185 @@
+pub mod confusion;
+pub mod evaluator;
+pub mod probabilistic;
+pub mod skill;
+
+use crate::module::util::math::bootstrap::{Interval, wilson_interval};
+
+#[derive(Clone, Copy, Debug)]
+pub enum TargetKind {
+    Level, // Predict next level directly
+    Diff,  // Predict next change/return
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum ZeroRule {
+    CountAsMiss, // Δ==0 counts as miss
+    Ignore,      // Δ==0 is skipped
+    CountAsDown, // Δ==0 counts as down (close[t+1] > close[t] เป็น up เท่านั้น)
+}
+
+/// เกณฑ์ "ทรงตัว": การเปลี่ยนแปลงที่ |Δ| < threshold ถือเป็น flat (sign = 0)
+#[derive(Clone, Copy, Debug, Default)]
+pub enum DeadBand {
+    /// ใช้เฉพาะ Δ == 0 พอดี
+    #[default]
+    None,
+    /// |Δ| < ค่าคงที่ (หน่วยเดียวกับข้อมูล)
+    Absolute(f64),
+    /// |Δ / level[t]| < k·σ โดย σ คือส่วนเบี่ยงเบนมาตรฐานของผลตอบแทน Δ / level ย้อนหลัง
+    /// SIGMA_WINDOW แท่ง (ไม่รวมแท่งปัจจุบัน) -> band ไม่ขึ้นกับระดับราคา
+    Sigma(f64),
+}
+
+/// ความยาวหน้าต่างย้อนหลังของ DeadBand::Sigma (~1 ปีเทรด)
+pub const SIGMA_WINDOW: usize = 252;
+
+/// dead band ที่ใช้รายงาน strategy / forecast: 0.1σ ของผลตอบแทนย้อนหลัง
+pub const DEFAULT_DEAD_BAND: DeadBand = DeadBand::Sigma(0.1);
+
+impl DeadBand {
+    /// threshold ของ |changes[t]| ในหน่วยเดียวกับ changes โดย base[t] คือ level ก่อนเปลี่ยน
+    /// ใช้เฉพาะข้อมูลก่อนจุดนั้น (ค่าที่ไม่ finite ถูกข้าม)
+    /// Sigma ที่ประวัติยังไม่ถึง 2 ค่าหรือ base ไม่ใช้การได้คืน NaN -> จุดนั้นเป็น abstain
+    pub fn thresholds(&self, changes: &[f64], base: &[f64]) -> Vec<f64> {
+        match *self {
+            DeadBand::None => vec![0.0; changes.len()],
+            DeadBand::Absolute(x) => vec![x.abs(); changes.len()],
+            DeadBand::Sigma(k) => {
+                let returns: Vec<f64> =
+                    changes.iter().zip(base).map(|(d, b)| d / b.abs()).collect();
+                (0..changes.len())
+                    .map(|t| {
+                        let xs: Vec<f64> = returns[t.saturating_sub(SIGMA_WINDOW)..t]
+                            .iter()
+                            .copied()
+                            .filter(|x| x.is_finite())
+                            .collect();
+                        let b = base.get(t).map_or(f64::NAN, |b| b.abs());
+                        if xs.len() < 2 || !b.is_finite() || b == 0.0 {
+                            return f64::NAN;
+                        }
+                        let n = xs.len() as f64;
+                        let mean = xs.iter().sum::<f64>() / n;
+                        let var = xs.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.0);
+                        k.abs() * var.sqrt() * b
+                    })
+                    .collect()
+            }
+        }
+    }
+}
+
+#[derive(Default, Clone, Debug)]
+pub struct EvalReport {
+    pub total: usize,
+    pub hits: usize,
+    /// จุดที่ไม่ถูกนับ (abstain + Δ==0 ภายใต้ ZeroRule::Ignore)
+    pub skipped: usize,
+    /// จุดที่ไม่มีการทำนาย (สัญญาณเป็น None หรือค่าไม่ finite)
+    pub abstained: usize,
+    pub up_up: usize,
+    pub up_down: usize,
+    pub down_up: usize,
+    pub down_down: usize,
+}
+
+impl EvalReport {
+    pub fn accuracy(&self) -> f64 {
+        if self.total == 0 {
+            f64::NAN
+        } else {
+            self.hits as f64 / self.total as f64
+        }
+    }
+
+    pub fn precision(&self) -> f64 {
+        let predicted_positive = self.up_up + self.up_down;
+        if predicted_positive == 0 {
+            f64::NAN
+        } else {
+            self.up_up as f64 / predicted_positive as f64
+        }
+    }
+
+    pub fn recall(&self) -> f64 {
+        let actual_positive = self.up_up + self.down_up;
+        if actual_positive == 0 {
+            f64::NAN
+        } else {
+            self.up_up as f64 / actual_positive as f64
+        }
+    }
+}
+
+/// ผลทำนายรายจุด: sign ของทิศที่ทำนายและทิศจริง (+1 / 0 / -1)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Outcome {
+    pub pred: i8,
+    pub actual: i8,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct MetricIntervals {
+    pub accuracy: Interval,
+    pub precision: Interval,
+    pub recall: Interval,
+    pub f1: Interval,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ClassificationMetrics {
+    pub accuracy: f64,
+    pub precision: f64,
+    pub recall: f64,
+    pub f1: f64,
+}
+
+/// คำนวณหาค่าทางสถิติเบื้องต้น Accuracy Precision Recall F1Score
+/// NaN เมื่อไม่มีข้อมูล (eval_with_signals เดิมคืน 0.0) -> 0 ข้อมูลไม่ถูกนับเป็น 0% ตอนจัดอันดับ
+pub fn calculate(report: &EvalReport) -> ClassificationMetrics {
+    let accuracy = report.accuracy();
+    let precision = report.precision();
+    let recall = report.recall();
+    let f1 = if !precision.is_finite()
+        || !recall.is_finite()
+        || (precision + recall).abs() < f64::EPSILON
+    {
+        f64::NAN
+    } else {
+        2.0 * precision * recall / (precision + recall)
+    };
+    ClassificationMetrics {
+        accuracy,
+        precision,
+        recall,
+        f1,
+    }
+}
+
+#[inline]
+pub(crate) fn sgn(x: f64) -> i8 {
+    if x > 0.0 {
+        1
+    } else if x < 0.0 {
+        -1
+    } else {
+        0
+    }
+}
+
+/// sign ที่ถือว่า |x| < threshold เป็น 0
+#[inline]
+pub(crate) fn sgn_band(x: f64, threshold: f64) -> i8 {
+    if x.abs() < threshold { 0 } else { sgn(x) }
+}
+
+/// Wilson score interval ของ accuracy / precision / recall (F1 ไม่มีรูปปิด -> NaN)
+pub fn wilson_intervals(report: &EvalReport, level: f64) -> MetricIntervals {
+    MetricIntervals {
+        accuracy: wilson_interval(report.hits, report.total, level),
+        precision: wilson_interval(report.up_up, report.up_up + report.up_down, level),
+        recall: wilson_interval(report.up_up, report.up_up + report.down_up, level),
+        f1: Interval::nan(),
+    }
+}
@@ -0,0 +1,183 @@
+// ประเมินการพยากรณ์แบบความน่าจะเป็น แทนการยุบเหลือแค่ sign
+// - Brier score / log loss ของ P(ขึ้น)
+// - reliability diagram (calibration)
+// - CRPS ของการแจกแจงพยากรณ์แบบ Gaussian
+
+use std::f64::consts::PI;
+
+use crate::module::eval::TargetKind;
+use crate::module::util::math::distribution::{normal_cdf, normal_pdf};
+
+/// การแจกแจงพยากรณ์ N(mean, sd²) ของค่าถัดไป (Δ หรือ level ตาม TargetKind)
+#[derive(Clone, Copy, Debug)]
+pub struct GaussianForecast {
+    pub mean: f64,
+    pub sd: f64,
+}
+
+impl GaussianForecast {
+    /// P(ค่าจริง > threshold)
+    pub fn prob_above(&self, threshold: f64) -> f64 {
+        if self.sd <= 0.0 {
+            return if self.mean > threshold { 1.0 } else { 0.0 };
+        }
+        1.0 - normal_cdf((threshold - self.mean) / self.sd)
+    }
+
+    /// ช่วงพยากรณ์สองด้านที่ระดับ z
+    pub fn interval(&self, z: f64) -> (f64, f64) {
+        (self.mean - z * self.sd, self.mean + z * self.sd)
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct ReliabilityBin {
+    pub lo: f64,
+    pub hi: f64,
+    pub mean_pred: f64,
+    pub observed: f64,
+    pub count: usize,
+}
+
+#[derive(Clone, Debug)]
+pub struct ProbabilisticReport {
+    pub n: usize,
+    pub brier: f64,
+    pub log_loss: f64,
+    pub crps: f64,
+    pub reliability: Vec<ReliabilityBin>,
+}
+
+const LOG_LOSS_EPS: f64 = 1e-15;
+
+fn mean_of(xs: impl Iterator<Item = f64>) -> f64 {
+    let (sum, n) = xs.fold((0.0, 0usize), |(s, n), x| (s + x, n + 1));
+    if n == 0 { f64::NAN } else { sum / n as f64 }
+}
+
+/// Brier score = mean (p - y)²
+pub fn brier_score(prob_up: &[f64], up: &[bool]) -> f64 {
+    mean_of(
+        prob_up
+            .iter()
+            .zip(up)
+            .map(|(&p, &y)| (p - if y { 1.0 } else { 0.0 }).powi(2)),
+    )
+}
+
+/// log loss (cross-entropy) โดย clip p ไว้ที่ [ε, 1-ε]
+pub fn log_loss(prob_up: &[f64], up: &[bool]) -> f64 {
+    mean_of(prob_up.iter().zip(up).map(|(&p, &y)| {
+        let p = p.clamp(LOG_LOSS_EPS, 1.0 - LOG_LOSS_EPS);
+        if y { -p.ln() } else { -(1.0 - p).ln() }
+    }))
+}
+
+/// แบ่ง [0, 1] เป็น n_bins ช่วงเท่ากัน แล้วเทียบ p เฉลี่ยกับความถี่ที่ขึ้นจริง (ช่วงว่างถูกตัดทิ้ง)
+pub fn reliability_bins(prob_up: &[f64], up: &[bool], n_bins: usize) -> Vec<ReliabilityBin> {
+    let n_bins = n_bins.max(1);
+    let mut sum_p = vec![0.0; n_bins];
+    let mut sum_y = vec![0.0; n_bins];
+    let mut count = vec![0usize; n_bins];
+    for (&p, &y) in prob_up.iter().zip(up) {
+        if !p.is_finite() {
+            continue;
+        }
+        let b = ((p.clamp(0.0, 1.0) * n_bins as f64) as usize).min(n_bins - 1);
+        sum_p[b] += p;
+        sum_y[b] += if y { 1.0 } else { 0.0 };
+        count[b] += 1;
+    }
+    (0..n_bins)
+        .filter(|&b| count[b] > 0)
+        .map(|b| ReliabilityBin {
+            lo: b as f64 / n_bins as f64,
+            hi: (b + 1) as f64 / n_bins as f64,
+            mean_pred: sum_p[b] / count[b] as f64,
+            observed: sum_y[b] / count[b] as f64,
+            count: count[b],
+        })
+        .collect()
+}
+
+/// CRPS แบบปิดของ N(μ, σ²) ที่ค่าจริง x (Gneiting & Raftery 2007)
+pub fn crps_gaussian(f: &GaussianForecast, x: f64) -> f64 {
+    if f.sd <= 0.0 {
+        return (x - f.mean).abs();
+    }
+    let z = (x - f.mean) / f.sd;
+    f.sd * (z * (2.0 * normal_cdf(z) - 1.0) + 2.0 * normal_pdf(z) - 1.0 / PI.sqrt())
+}
+
+/// ประเมิน forecasts[i] (ค่าพยากรณ์ของช่วง actual_levels[i] -> actual_levels[i + 1])
+/// จุดที่ forecast เป็น None หรือค่าไม่ finite ถูกข้าม
+pub fn evaluate_probabilistic(
+    actual_levels: &[f64],
+    forecasts: &[Option<GaussianForecast>],
+    target: TargetKind,
+    n_bins: usize,
+) -> ProbabilisticReport {
+    let mut probs = Vec::new();
+    let mut ups = Vec::new();
+    let mut crps = Vec::new();
+    for (i, f) in forecasts
+        .iter()
+        .enumerate()
+        .take(actual_levels.len().saturating_sub(1))
+    {
+        let Some(f) = f else { continue };
+        let (a0, a1) = (actual_levels[i], actual_levels[i + 1]);
+        if !a0.is_finite() || !a1.is_finite() || !f.mean.is_finite() || !f.sd.is_finite() {
+            continue;
+        }
+        // เปลี่ยนทุกอย่างให้อยู่ในสเกลเดียวกับ forecast
+        let (threshold, realized) = match target {
+            TargetKind::Level => (a0, a1),
+            TargetKind::Diff => (0.0, a1 - a0),
+        };
+        probs.push(f.prob_above(threshold));
+        ups.push(a1 > a0);
+        crps.push(crps_gaussian(f, realized));
+    }
+    ProbabilisticReport {
+        n: probs.len(),
+        brier: brier_score(&probs, &ups),
+        log_loss: log_loss(&probs, &ups),
+        crps: mean_of(crps.into_iter()),
+        reliability: reliability_bins(&probs, &ups, n_bins),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scores_reference_values() {
+        let p = [0.9, 0.2, 0.5];
+        let y = [true, false, true];
+        assert!((brier_score(&p, &y) - (0.01 + 0.04 + 0.25) / 3.0).abs() < 1e-12);
+        let ll = -(0.9f64.ln() + 0.8f64.ln() + 0.5f64.ln()) / 3.0;
+        assert!((log_loss(&p, &y) - ll).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_crps_gaussian_at_mean() {
+        // CRPS(N(0,1), 0) = 2φ(0) - 1/√π ≈ 0.233695
+        let f = GaussianForecast { mean: 0.0, sd: 1.0 };
+        assert!((crps_gaussian(&f, 0.0) - 0.233695).abs() < 1e-5);
+        // sd -> 0 เหลือ absolute error
+        let point = GaussianForecast { mean: 1.0, sd: 0.0 };
+        assert_eq!(crps_gaussian(&point, 3.0), 2.0);
+    }
+
+    #[test]
+    fn test_reliability_bins_counts() {
+        let p = [0.05, 0.15, 0.12, 0.95];
+        let y = [false, true, false, true];
+        let bins = reliability_bins(&p, &y, 10);
+        assert_eq!(bins.len(), 3);
+        assert_eq!(bins[1].count, 2);
+        assert!((bins[1].observed - 0.5).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,213 @@
+// ทดสอบทางสถิติว่า forecaster ทำนายทิศทางได้จริงหรือไม่
+// - Pesaran–Timmermann: ทิศที่ทำนายกับทิศจริงเป็นอิสระต่อกันหรือไม่
+// - Binomial: hit rate สูงกว่า base rate (ทายคลาสที่เจอบ่อยที่สุดทุกครั้ง) หรือไม่
+// - Diebold–Mariano: loss ของ forecaster สองตัวต่างกันหรือไม่
+
+use crate::module::eval::Outcome;
+use crate::module::util::math::distribution::{binomial_sf, normal_cdf, student_t_cdf};
+
+#[derive(Debug, Clone, Copy)]
+pub struct TestResult {
+    pub statistic: f64,
+    pub p_value: f64,
+    pub n: usize,
+}
+
+impl TestResult {
+    fn nan(n: usize) -> Self {
+        Self {
+            statistic: f64::NAN,
+            p_value: f64::NAN,
+            n,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum LossKind {
+    Squared,
+    Absolute,
+    /// 1 เมื่อทายทิศผิด, 0 เมื่อถูก
+    Direction,
+}
+
+/// เก็บเฉพาะ outcome ที่ทั้งสองฝั่งไม่เป็นศูนย์ (ขึ้น/ลงชัดเจน)
+fn decisive(outcomes: &[Option<Outcome>]) -> Vec<Outcome> {
+    outcomes
+        .iter()
+        .flatten()
+        .copied()
+        .filter(|o| o.pred != 0 && o.actual != 0)
+        .collect()
+}
+
+/// Pesaran–Timmermann (1992) test, H0: ทิศที่ทำนายไม่มีข้อมูลเกี่ยวกับทิศจริง
+/// คืนค่า PT statistic (~N(0,1)) และ p-value ด้านเดียว (ขวา)
+pub fn pesaran_timmermann(outcomes: &[Option<Outcome>]) -> TestResult {
+    let obs = decisive(outcomes);
+    let n = obs.len();
+    if n < 2 {
+        return TestResult::nan(n);
+    }
+    let nf = n as f64;
+    let hits = obs.iter().filter(|o| o.pred == o.actual).count() as f64;
+    let p_hat = hits / nf;
+    let py = obs.iter().filter(|o| o.actual > 0).count() as f64 / nf;
+    let px = obs.iter().filter(|o| o.pred > 0).count() as f64 / nf;
+
+    let p_star = py * px + (1.0 - py) * (1.0 - px);
+    let v_p = p_star * (1.0 - p_star) / nf;
+    let v_star = (2.0 * py - 1.0).powi(2) * px * (1.0 - px) / nf
+        + (2.0 * px - 1.0).powi(2) * py * (1.0 - py) / nf
+        + 4.0 * py * px * (1.0 - py) * (1.0 - px) / (nf * nf);
+    let var = v_p - v_star;
+    if var <= 0.0 {
+        // forecaster ทายทิศเดียวตลอด -> ไม่มีข้อมูลให้ทดสอบ
+        return TestResult::nan(n);
+    }
+    let statistic = (p_hat - p_star) / var.sqrt();
+    TestResult {
+        statistic,
+        p_value: 1.0 - normal_cdf(statistic),
+        n,
+    }
+}
+
+/// Exact binomial test, H0: hit rate <= base rate ของคลาสที่เกิดบ่อยที่สุด
+/// statistic = hit rate ที่สังเกตได้, คืนค่า base rate มาด้วย
+pub fn binomial_vs_base_rate(outcomes: &[Option<Outcome>]) -> (TestResult, f64) {
+    let obs = decisive(outcomes);
+    let n = obs.len();
+    if n == 0 {
+        return (TestResult::nan(n), f64::NAN);
+    }
+    let hits = obs.iter().filter(|o| o.pred == o.actual).count();
+    let ups = obs.iter().filter(|o| o.actual > 0).count() as f64 / n as f64;
+    let base_rate = ups.max(1.0 - ups);
+    let result = TestResult {
+        statistic: hits as f64 / n as f64,
+        p_value: binomial_sf(hits, n, base_rate),
+        n,
+    };
+    (result, base_rate)
+}
+
+pub fn loss(actual: f64, forecast: f64, kind: LossKind) -> f64 {
+    let e = actual - forecast;
+    match kind {
+        LossKind::Squared => e * e,
+        LossKind::Absolute => e.abs(),
+        LossKind::Direction => {
+            if actual.signum() == forecast.signum() {
+                0.0
+            } else {
+                1.0
+            }
+        }
+    }
+}
+
+/// loss ทิศทางรายจุด (None ถ้าจุดนั้นไม่ได้ทำนาย)
+pub fn direction_losses(outcomes: &[Option<Outcome>]) -> Vec<Option<f64>> {
+    outcomes
+        .iter()
+        .map(|o| o.map(|o| if o.pred == o.actual { 0.0 } else { 1.0 }))
+        .collect()
+}
+
+/// Diebold–Mariano test พร้อม small-sample correction ของ Harvey–Leybourne–Newbold
+/// H0: E[loss_a - loss_b] = 0, horizon = จำนวนก้าวที่พยากรณ์ (ใช้ autocov ถึง lag h-1)
+/// statistic < 0 แปลว่า a มี loss ต่ำกว่า b, p-value สองด้านจาก t_{n-1}
+pub fn diebold_mariano(
+    loss_a: &[Option<f64>],
+    loss_b: &[Option<f64>],
+    horizon: usize,
+) -> TestResult {
+    let d: Vec<f64> = loss_a
+        .iter()
+        .zip(loss_b.iter())
+        .filter_map(|(a, b)| match (a, b) {
+            (Some(a), Some(b)) if a.is_finite() && b.is_finite() => Some(a - b),
+            _ => None,
+        })
+        .collect();
+    let n = d.len();
+    let h = horizon.max(1);
+    if n < 2 * h + 1 {
+        return TestResult::nan(n);
+    }
+    let nf = n as f64;
+    let mean = d.iter().sum::<f64>() / nf;
+    let autocov = |k: usize| -> f64 {
+        (k..n)
+            .map(|t| (d[t] - mean) * (d[t - k] - mean))
+            .sum::<f64>()
+            / nf
+    };
+    let mut lrv = autocov(0);
+    for k in 1..h {
+        lrv += 2.0 * autocov(k);
+    }
+    if lrv <= 0.0 {
+        return TestResult::nan(n);
+    }
+    let dm = mean / (lrv / nf).sqrt();
+    let hf = h as f64;
+    let hln = ((nf + 1.0 - 2.0 * hf + hf * (hf - 1.0) / nf) / nf).sqrt();
+    let statistic = dm * hln;
+    let p_value = 2.0 * (1.0 - student_t_cdf(statistic.abs(), nf - 1.0));
+    TestResult {
+        statistic,
+        p_value,
+        n,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn outcomes(pairs: &[(i8, i8)]) -> Vec<Option<Outcome>> {
+        pairs
+            .iter()
+            .map(|&(pred, actual)| Some(Outcome { pred, actual }))
+            .collect()
+    }
+
+    #[test]
+    fn test_pesaran_timmermann_perfect_vs_random() {
+        let actual: Vec<i8> = (0..200)
+            .map(|i| if (i * 7) % 5 < 2 { 1 } else { -1 })
+            .collect();
+        let perfect = outcomes(&actual.iter().map(|&a| (a, a)).collect::<Vec<_>>());
+        let pt = pesaran_timmermann(&perfect);
+        assert!(pt.statistic > 5.0);
+        assert!(pt.p_value < 1e-6);
+
+        let always_up = outcomes(&actual.iter().map(|&a| (1, a)).collect::<Vec<_>>());
+        assert!(pesaran_timmermann(&always_up).statistic.is_nan());
+    }
+
+    #[test]
+    fn test_binomial_vs_base_rate() {
+        // 60% ขึ้น ทายถูก 60% -> ไม่ดีกว่า base rate
+        let mut pairs = vec![(1, 1); 60];
+        pairs.extend(vec![(1, -1); 40]);
+        let (res, base) = binomial_vs_base_rate(&outcomes(&pairs));
+        assert!((base - 0.6).abs() < 1e-12);
+        assert!(res.p_value > 0.4);
+    }
+
+    #[test]
+    fn test_diebold_mariano_sign_and_degenerate() {
+        let a: Vec<Option<f64>> = (0..100).map(|i| Some(((i % 3) as f64) * 0.1)).collect();
+        let mixed: Vec<Option<f64>> = (0..100)
+            .map(|i| Some(((i % 3) as f64) * 0.1 + if i % 2 == 0 { 0.4 } else { 0.6 }))
+            .collect();
+        let res = diebold_mariano(&a, &mixed, 1);
+        assert!(res.statistic < 0.0);
+        assert!(res.p_value < 0.01);
+        // loss เท่ากันทุกจุด -> variance = 0 ทดสอบไม่ได้
+        assert!(diebold_mariano(&a, &a, 1).statistic.is_nan());
+    }
+}
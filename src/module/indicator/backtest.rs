@@ -0,0 +1,98 @@
+// ผลตอบแทนจากการเทรดตามสัญญาณ (long เมื่อทำนายขึ้น / short เมื่อทำนายลง)
+use crate::module::util::math::bootstrap::{
+    BootstrapConfig, Interval, bootstrap_draws, percentile_interval,
+};
+
+/// จำนวนวันเทรดต่อปี ใช้ annualize Sharpe
+const TRADING_DAYS: f64 = 252.0;
+
+#[derive(Debug, Clone, Copy)]
+pub struct ReturnMetrics {
+    pub n: usize,
+    /// ผลตอบแทนเฉลี่ยต่อแท่ง (simple return)
+    pub mean: f64,
+    /// Sharpe ratio แบบ annualized (risk-free = 0)
+    pub sharpe: f64,
+    /// ผลตอบแทนสะสมแบบทบต้น
+    pub total: f64,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ReturnIntervals {
+    pub mean: Interval,
+    pub sharpe: Interval,
+    pub total: Interval,
+}
+
+/// ผลตอบแทนรายแท่ง: signal[t] ตัดสินสถานะที่ถือจาก t ไป t+1 (None = ไม่ถือ)
+pub fn strategy_returns(close: &[f64], signal: &[Option<bool>]) -> Vec<f64> {
+    strategy_returns_scaled(close, signal, &[])
+}
+
+/// เหมือน strategy_returns แต่คูณขนาดสถานะด้วย scale[t] (เช่น vol_target_scale จาก GARCH)
+/// scale[t] ต้องรู้ได้ ณ ปิดแท่ง t (ไม่งั้นมี look-ahead) ; index ที่ไม่มีใน scale ถือขนาด 1
+pub fn strategy_returns_scaled(close: &[f64], signal: &[Option<bool>], scale: &[f64]) -> Vec<f64> {
+    let n = close.len();
+    let mut out = Vec::with_capacity(n.saturating_sub(1));
+    for t in 0..n.saturating_sub(1) {
+        if let Some(up) = signal.get(t).copied().flatten() {
+            if close[t].abs() < 1e-12 || !close[t].is_finite() || !close[t + 1].is_finite() {
+                continue;
+            }
+            let r = close[t + 1] / close[t] - 1.0;
+            let w = scale.get(t).copied().unwrap_or(1.0);
+            out.push(if up { w * r } else { -w * r });
+        }
+    }
+    out
+}
+
+pub fn return_metrics(returns: &[f64]) -> ReturnMetrics {
+    let n = returns.len();
+    if n == 0 {
+        return ReturnMetrics {
+            n,
+            mean: f64::NAN,
+            sharpe: f64::NAN,
+            total: f64::NAN,
+        };
+    }
+    let mean = returns.iter().sum::<f64>() / n as f64;
+    let var = if n > 1 {
+        returns.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / (n - 1) as f64
+    } else {
+        0.0
+    };
+    let sd = var.sqrt();
+    let sharpe = if sd > 0.0 {
+        mean / sd * TRADING_DAYS.sqrt()
+    } else {
+        f64::NAN
+    };
+    let total = returns.iter().fold(1.0, |acc, r| acc * (1.0 + r)) - 1.0;
+    ReturnMetrics {
+        n,
+        mean,
+        sharpe,
+        total,
+    }
+}
+
+/// bootstrap interval ของ mean / Sharpe / total return
+pub fn return_intervals(returns: &[f64], config: &BootstrapConfig) -> ReturnIntervals {
+    let draws = bootstrap_draws(returns, config, |sample| {
+        let m = return_metrics(sample);
+        vec![m.mean, m.sharpe, m.total]
+    });
+    let ci = |i: usize| {
+        draws
+            .get(i)
+            .map(|d| percentile_interval(d, config.level))
+            .unwrap_or_else(Interval::nan)
+    };
+    ReturnIntervals {
+        mean: ci(0),
+        sharpe: ci(1),
+        total: ci(2),
+    }
+}
@@ -2,7 +2,15 @@
 // ai gen ครับ
 
 // รวมฟังก์ชันที่ใช้สร้างสัญญาณซื้อ/ขายจากอินดิเคเตอร์ต่าง ๆ
-use crate::module::model::{ema::ema_series, sma::sma_series};
+use crate::module::eval::probabilistic::GaussianForecast;
+use crate::module::model::{
+    cycle::{adaptive_ema_series, cycle_switch_signal, dominant_cycle_series},
+    ema::ema_series,
+    filter::spec::{FilterSpec, filter_signal},
+    sma::sma_series,
+    state_space::kalman_trend,
+    technical::oscillator::{macd_series, rsi_series},
+};
 
 #[derive(Clone, Copy, Debug)]
 pub enum Strategy {
@@ -12,9 +20,25 @@ pub enum Strategy {
     EmaFastGtEmaSlow { fast: usize, slow: usize },
     /// Buy เมื่อคาดการณ์ Δ_{t+1} > 0 (ต้องใช้ forecaster ภายนอก)
     ArimaDeltaPos { window: usize },
+    /// Buy เมื่อ slope ของ local linear trend (Kalman filter) > 0
+    KalmanTrend { level_snr: f64, slope_snr: f64 },
+    /// Buy เมื่อ MACD line > signal line
+    MacdGtSignal {
+        fast: usize,
+        slow: usize,
+        signal: usize,
+    },
+    /// Buy เมื่อ RSI > level
+    RsiGt { period: usize, level: f64 },
+    /// Buy เมื่อ filter ชันขึ้น (low-pass) หรือ > 0 (high/band-pass)
+    Filter { spec: FilterSpec },
+    /// Buy เมื่อ close > EMA ที่ period = fraction · dominant cycle (homodyne)
+    AdaptiveEmaTrend { fraction: f64 },
+    /// trend mode: ตามแนวโน้ม (price > trendline) ; cycle mode: lead sine > sine
+    CycleModeSwitch,
 }
 
-/// สร้างสัญญาณสำหรับ strategy ที่ใช้ EMA/SMA (ไม่รวม ARIMA)
+/// สร้างสัญญาณสำหรับ strategy ที่คำนวณจาก close อย่างเดียว (ไม่รวม ARIMA)
 pub fn signal_series_basic(data: &[f64], strat: Strategy) -> Vec<Option<bool>> {
     match strat {
         // สำหรับ ema ตัดขึ้นเหนือ sma จะ return true
@@ -43,6 +67,36 @@ pub fn signal_series_basic(data: &[f64], strat: Strategy) -> Vec<Option<bool>> {
                 .collect()
         }
         Strategy::ArimaDeltaPos { .. } => vec![None; data.len()],
+        // สองแท่งแรกยังเป็นช่วง diffuse ของ slope
+        Strategy::KalmanTrend {
+            level_snr,
+            slope_snr,
+        } => kalman_trend(data, level_snr, slope_snr)
+            .iter()
+            .enumerate()
+            .map(|(t, (_, slope))| (t >= 2).then_some(*slope > 0.0))
+            .collect(),
+        Strategy::MacdGtSignal { fast, slow, signal } => {
+            let macd = macd_series(data, fast, slow, signal);
+            macd.line
+                .iter()
+                .zip(macd.signal.iter())
+                .map(|(l, s)| Some((*l)? > (*s)?))
+                .collect()
+        }
+        Strategy::RsiGt { period, level } => rsi_series(data, period)
+            .iter()
+            .map(|r| r.map(|r| r > level))
+            .collect(),
+        Strategy::Filter { spec } => filter_signal(data, spec),
+        Strategy::AdaptiveEmaTrend { fraction } => {
+            let ema = adaptive_ema_series(data, &dominant_cycle_series(data), fraction);
+            data.iter()
+                .zip(&ema)
+                .map(|(x, e)| e.map(|e| *x > e))
+                .collect()
+        }
+        Strategy::CycleModeSwitch => cycle_switch_signal(data),
     }
 }
 
@@ -78,6 +132,55 @@ where
     out
 }
 
+/// เหมือน signal_series_arima แต่เก็บการแจกแจงพยากรณ์ของ Δ_{t+1} ไว้ทั้งก้อน
+/// ใช้ประเมินแบบความน่าจะเป็น (Brier / log loss / CRPS) บนสเกล log-close
+pub fn forecast_series_arima<F>(
+    close: &[f64],
+    window: usize,
+    mut forecaster: F,
+) -> Vec<Option<GaussianForecast>>
+where
+    F: FnMut(&[f64]) -> GaussianForecast,
+{
+    let n = close.len();
+    let mut out = vec![None; n];
+    if window == 0 || n <= window {
+        return out;
+    }
+    let logc: Vec<f64> = close.iter().map(|&x| x.max(1e-12).ln()).collect();
+    for t in (window - 1)..(n - 1) {
+        let seg = &logc[t + 1 - window..=t];
+        if seg.len() < 2 {
+            continue;
+        }
+        let diff: Vec<f64> = seg.windows(2).map(|w| w[1] - w[0]).collect();
+        out[t] = Some(forecaster(&diff));
+    }
+    out
+}
+
+/// AR(1) แบบมีการแจกแจง: mean เหมือน forecaster_ar1, sd จาก residual ของ AR(1)
+pub fn forecaster_ar1_dist(diff: &[f64]) -> GaussianForecast {
+    let mean = forecaster_ar1(diff);
+    if diff.len() < 3 {
+        return GaussianForecast { mean, sd: f64::NAN };
+    }
+    let x = &diff[1..];
+    let y = &diff[..diff.len() - 1];
+    let num: f64 = x.iter().zip(y.iter()).map(|(a, b)| a * b).sum();
+    let den: f64 = y.iter().map(|b| b * b).sum::<f64>().max(1e-12);
+    let phi = num / den;
+    let sse: f64 = x
+        .iter()
+        .zip(y.iter())
+        .map(|(a, b)| (a - phi * b).powi(2))
+        .sum();
+    GaussianForecast {
+        mean,
+        sd: (sse / (x.len() - 1) as f64).sqrt(),
+    }
+}
+
 /// forecaster AR(1) อย่างง่ายเอาไว้ fallback เมื่อยังไม่มีโมเดล ARIMA เต็ม
 pub fn forecaster_ar1(diff: &[f64]) -> f64 {
     if diff.is_empty() {
@@ -1,23 +1,48 @@
 use crate::module::data::read_csv::read_close_series;
-use crate::module::eval::EvalReport;
+use crate::module::eval::{
+    ClassificationMetrics, DEFAULT_DEAD_BAND, DeadBand, EvalReport, MetricIntervals, Outcome,
+    TargetKind,
+    confusion::ConfusionMatrix,
+    evaluator::{Evaluation, Evaluator},
+    probabilistic::{ProbabilisticReport, evaluate_probabilistic},
+    skill::{
+        TestResult, binomial_vs_base_rate, diebold_mariano, direction_losses, pesaran_timmermann,
+    },
+    wilson_intervals,
+};
+use crate::module::indicator::backtest::{
+    ReturnIntervals, ReturnMetrics, return_intervals, return_metrics, strategy_returns,
+    strategy_returns_scaled,
+};
 use crate::module::indicator::decide::{
-    Strategy, forecaster_ar1, signal_series_arima, signal_series_basic,
+    Strategy, forecast_series_arima, signal_series_arima, signal_series_basic,
 };
+use crate::module::model::arma::rolling::RollingArmaForecaster;
+use crate::module::model::garch::{GarchKind, rolling_garch_sd, vol_target_scale};
+use crate::module::util::math::bootstrap::{BootstrapConfig, Interval};
 use crate::module::util::math::percent::cal_percent_f64;
 use std::path::PathBuf;
 
-#[derive(Debug, Clone, Default)]
-pub struct EvalMetrics {
-    pub accuracy: f64,
-    pub precision_up: f64,
-    pub recall_up: f64,
-    pub f1_up: f64,
+/// ช่วงความเชื่อมั่นของ metric และผลตอบแทน (คำนวณเฉพาะตอนรายงานผล เพราะ bootstrap ช้า)
+#[derive(Debug, Clone, Copy)]
+pub struct StrategyIntervals {
+    /// ระดับความเชื่อมั่นที่ใช้คำนวณ interval ทั้งหมดในนี้
+    pub level: f64,
+    pub wilson: MetricIntervals,
+    pub bootstrap: MetricIntervals,
+    pub returns: ReturnMetrics,
+    pub returns_ci: ReturnIntervals,
+    /// ผลตอบแทนเมื่อปรับขนาดสถานะด้วย vol targeting จาก GARCH σ̂_{t+1|t}
+    pub vol_targeted: ReturnMetrics,
 }
 
 #[derive(Debug, Clone)]
 pub struct EvaluatedStrategy {
     pub report: EvalReport,
-    pub metrics: EvalMetrics,
+    pub metrics: ClassificationMetrics,
+    pub confusion: ConfusionMatrix,
+    pub outcomes: Vec<Option<Outcome>>,
+    pub intervals: Option<StrategyIntervals>,
 }
 
 #[derive(Debug, Clone)]
@@ -25,13 +50,30 @@ pub struct ThreeEval {
     pub ema_gt_sma: EvaluatedStrategy,
     pub ema_fast_gt_slow: EvaluatedStrategy,
     pub arima_delta_pos: EvaluatedStrategy,
+    pub kalman_trend: EvaluatedStrategy,
+    /// Brier / log loss / CRPS ของ rolling ARMA บน Δlog-close
+    /// None เมื่อผู้เรียกส่ง forecaster เอง (forecaster นั้นให้แค่ค่ากลาง ไม่มีการแจกแจง)
+    pub arima_probabilistic: Option<ProbabilisticReport>,
 }
 
+/// forecaster ของ Δlog-close แท่งถัดไปจากหน้าต่าง Δ ย้อนหลัง (ให้แค่ค่ากลาง)
+pub type DeltaForecaster = Box<dyn FnMut(&[f64]) -> f64>;
+
 #[derive(Debug, Clone, Copy)]
 pub struct ThreeEvalConfig {
     pub ema_vs_sma: (usize, usize),
     pub ema_fast_vs_slow: (usize, usize),
     pub arima_window: usize,
+    /// (p, q) ของ RollingArmaForecaster
+    pub arima_order: (usize, usize),
+    /// ฟิตใหม่ทุกกี่แท่ง
+    pub arima_refit_every: usize,
+    /// (level_snr, slope_snr) ของ Kalman trend
+    pub kalman_snr: (f64, f64),
+    /// |Δclose / close| ที่เล็กกว่านี้ถือว่าทรงตัวและไม่ถูกนับเป็นถูก/ผิด
+    pub dead_band: DeadBand,
+    /// (σ เป้าหมายต่อแท่ง, leverage สูงสุด) ของ backtest แบบ vol targeting
+    pub vol_target: (f64, f64),
 }
 
 pub fn eval_percent_ema_sma(file_path: PathBuf, ema_period: usize, sma_period: usize) -> f64 {
@@ -74,119 +116,93 @@ pub fn build_eval_report(path: PathBuf, ema_period: usize, sma_period: usize) ->
     report
 }
 
-/// คำนวณหาค่าทางสถิติเบื้องต้น Accuracy Precision Recall F1Score
-pub fn compute_metrics(r: &EvalReport) -> EvalMetrics {
-    let total = r.total as f64;
-    let tp = r.up_up as f64;
-    let fp = r.up_down as f64;
-    let fn_ = r.down_up as f64;
-    let _tn = r.down_down as f64;
-
-    // accuracy ความถูกต้อง คือการเอา (TP + FP) / (TOTAL)
-    let accuracy = if total > 0.0 {
-        (r.hits as f64) / total
-    } else {
-        0.0
-    };
-
-    // precision up ความแม่นยำว่าขึ้นไหมคือการเอา (tp) / ความถูกต้อง
-    let precision_up = if (tp + fp) > 0.0 { tp / (tp + fp) } else { 0.0 };
-
-    // recall up ความเร็วว่าขึ้นไหมหมายถึงการดูว่าจะขึ้นแล้วดูว่าขึ้นจริงๆเท่าไร tp/ (tp + fn )
-    let recall_up = if (tp + fn_) > 0.0 {
-        tp / (tp + fn_)
-    } else {
-        0.0
-    };
-
-    // f1 score up คือการเอาทิศทางขึ้นแล้วถ่วงเฉลี่ยกับความน่าจะเป็น
-    let f1_up = if (precision_up + recall_up) > 0.0 {
-        2.0 * precision_up * recall_up / (precision_up + recall_up)
-    } else {
-        0.0
-    };
-
-    EvalMetrics {
-        accuracy,
-        precision_up,
-        recall_up,
-        f1_up,
-    }
-}
-
-pub fn eval_with_signals(close: &[f64], signal: &[Option<bool>]) -> EvalReport {
-    let n = close.len();
-    let mut r = EvalReport::default();
-    if n < 2 {
-        return r;
-    }
-
-    let last = n - 1;
-    for t in 0..last {
-        if let Some(pred_up) = signal.get(t).copied().flatten() {
-            let actual_up = close[t + 1] > close[t];
-            r.total += 1;
-            match (pred_up, actual_up) {
-                (true, true) => {
-                    r.hits += 1;
-                    r.up_up += 1;
-                }
-                (true, false) => {
-                    r.up_down += 1;
-                }
-                (false, true) => {
-                    r.down_up += 1;
-                }
-                (false, false) => {
-                    r.hits += 1;
-                    r.down_down += 1;
-                }
-            }
-        } else {
-            r.skipped += 1;
-        }
-    }
-    r
-}
-
 pub fn run_three_eval(
     close: &[f64],
     config: &ThreeEvalConfig,
-    mut forecaster_opt: Option<Box<dyn FnMut(&[f64]) -> f64>>,
+    mut forecaster_opt: Option<DeltaForecaster>,
 ) -> ThreeEval {
-    let ema_gt_sma = evaluate_basic(
+    let evaluator = Evaluator::for_signals(config.dead_band);
+    let scale = vol_target_scale(
+        &walk_forward_garch_sd(close, config.arima_window, config.arima_refit_every),
+        config.vol_target.0,
+        config.vol_target.1,
+    );
+    let ema_gt_sma = finalize_with_intervals(
+        &evaluator,
+        &scale,
         close,
-        Strategy::EmaGtSma {
-            ema: config.ema_vs_sma.0,
-            sma: config.ema_vs_sma.1,
-        },
+        signal_series_basic(
+            close,
+            Strategy::EmaGtSma {
+                ema: config.ema_vs_sma.0,
+                sma: config.ema_vs_sma.1,
+            },
+        ),
     );
-    let ema_fast_gt_slow = evaluate_basic(
+    let ema_fast_gt_slow = finalize_with_intervals(
+        &evaluator,
+        &scale,
         close,
-        Strategy::EmaFastGtEmaSlow {
-            fast: config.ema_fast_vs_slow.0,
-            slow: config.ema_fast_vs_slow.1,
-        },
+        signal_series_basic(
+            close,
+            Strategy::EmaFastGtEmaSlow {
+                fast: config.ema_fast_vs_slow.0,
+                slow: config.ema_fast_vs_slow.1,
+            },
+        ),
     );
 
-    let arima_delta_pos = if let Some(f) = forecaster_opt.as_mut() {
-        evaluate_arima(close, config.arima_window, f.as_mut())
-    } else {
-        let mut fallback = |diff: &[f64]| forecaster_ar1(diff);
-        evaluate_arima(close, config.arima_window, &mut fallback)
+    let kalman_trend = finalize_with_intervals(
+        &evaluator,
+        &scale,
+        close,
+        signal_series_basic(
+            close,
+            Strategy::KalmanTrend {
+                level_snr: config.kalman_snr.0,
+                slope_snr: config.kalman_snr.1,
+            },
+        ),
+    );
+
+    // ARMA walk-forward: ฟิตใหม่ทุก k แท่งบนหน้าต่างย้อนหลังเท่านั้น
+    // ถ้าผู้เรียกส่ง forecaster มา ทั้งสัญญาณและตัวชี้วัดต้องมาจาก model นั้นตัวเดียว -> ไม่รัน rolling ARMA
+    let (arima_signal, arima_probabilistic) = match forecaster_opt.as_mut() {
+        Some(f) => (
+            signal_series_arima(close, config.arima_window, |diff| f(diff)),
+            None,
+        ),
+        None => {
+            let mut rolling = RollingArmaForecaster::new(
+                config.arima_order.0,
+                config.arima_order.1,
+                config.arima_refit_every,
+            );
+            let dist =
+                forecast_series_arima(close, config.arima_window, |diff| rolling.forecast(diff));
+            let log_close: Vec<f64> = close.iter().map(|&x| x.max(1e-12).ln()).collect();
+            let prob = evaluate_probabilistic(&log_close, &dist, TargetKind::Diff, 10);
+            (
+                dist.iter().map(|g| g.map(|g| g.mean > 0.0)).collect(),
+                Some(prob),
+            )
+        }
     };
+    let arima_delta_pos = finalize_with_intervals(&evaluator, &scale, close, arima_signal);
 
     ThreeEval {
         ema_gt_sma,
         ema_fast_gt_slow,
         arima_delta_pos,
+        kalman_trend,
+        arima_probabilistic,
     }
 }
 
 pub fn run_three_eval_from_path(
     file_path: PathBuf,
     config: ThreeEvalConfig,
-    forecaster_opt: Option<Box<dyn FnMut(&[f64]) -> f64>>,
+    forecaster_opt: Option<DeltaForecaster>,
 ) -> ThreeEval {
     let close = load_close_series(&file_path);
     run_three_eval(&close, &config, forecaster_opt)
@@ -195,7 +211,7 @@ pub fn run_three_eval_from_path(
 pub fn calculate_three(
     file_path: PathBuf,
     config: ThreeEvalConfig,
-    forecaster_opt: Option<Box<dyn FnMut(&[f64]) -> f64>>,
+    forecaster_opt: Option<DeltaForecaster>,
 ) -> ThreeEval {
     let result = run_three_eval_from_path(file_path, config.clone(), forecaster_opt);
     print_three_eval(&result, &config);
@@ -222,52 +238,207 @@ pub fn print_three_eval(result: &ThreeEval, config: &ThreeEvalConfig) {
         ),
         (
             "ARIMA Δ>0",
-            format!("window={}", config.arima_window),
+            format!(
+                "window={} order=({},{}) refit_every={}",
+                config.arima_window,
+                config.arima_order.0,
+                config.arima_order.1,
+                config.arima_refit_every
+            ),
             &result.arima_delta_pos,
         ),
+        (
+            "KalmanTrend slope>0",
+            format!(
+                "level_snr={} slope_snr={}",
+                config.kalman_snr.0, config.kalman_snr.1
+            ),
+            &result.kalman_trend,
+        ),
     ];
     for (name, detail, eval) in specs {
         let report = &eval.report;
         let metrics = &eval.metrics;
         println!("=== {name} ({detail}) ===");
         println!(
-            "total={} hits={} skipped={}",
-            report.total, report.hits, report.skipped
+            "total={} hits={} skipped={} (abstained={})",
+            report.total, report.hits, report.skipped, report.abstained
         );
         println!(
             "up_up={} up_down={} down_up={} down_down={}",
             report.up_up, report.up_down, report.down_up, report.down_down
         );
         println!(
-            "acc={:.2}% prec_up={:.2}% recall_up={:.2}% f1_up={:.2}% \n",
+            "acc={:.2}% prec_up={:.2}% recall_up={:.2}% f1_up={:.2}%",
             metrics.accuracy * 100.0,
-            metrics.precision_up * 100.0,
-            metrics.recall_up * 100.0,
-            metrics.f1_up * 100.0,
+            metrics.precision * 100.0,
+            metrics.recall * 100.0,
+            metrics.f1 * 100.0,
+        );
+        let mc = eval.confusion.metrics();
+        println!(
+            "  kappa={:.4} mcc={:.4} macro_f1={:.2}% micro p/r/f1={:.2}%/{:.2}%/{:.2}%",
+            mc.kappa,
+            mc.mcc,
+            mc.macro_f1 * 100.0,
+            mc.micro_precision * 100.0,
+            mc.micro_recall * 100.0,
+            mc.micro_f1 * 100.0
+        );
+        if let Some(ci) = &eval.intervals {
+            print_intervals(ci);
+        }
+        let pt = pesaran_timmermann(&eval.outcomes);
+        let (binom, base_rate) = binomial_vs_base_rate(&eval.outcomes);
+        println!(
+            "  skill: PT={:.3} (p={:.4}) binomial acc={:.2}% vs base={:.2}% (p={:.4})\n",
+            pt.statistic,
+            pt.p_value,
+            binom.statistic * 100.0,
+            base_rate * 100.0,
+            binom.p_value,
+        );
+    }
+
+    // เทียบ ARIMA กับ EMA ด้วย Diebold–Mariano บน loss ทิศทาง (0/1)
+    let arima_loss = direction_losses(&result.arima_delta_pos.outcomes);
+    for (name, other) in [
+        ("EMA>SMA", &result.ema_gt_sma),
+        ("EMAfast>EMAslow", &result.ema_fast_gt_slow),
+        ("KalmanTrend", &result.kalman_trend),
+    ] {
+        let dm = diebold_mariano(&arima_loss, &direction_losses(&other.outcomes), 1);
+        println!("DM ARIMA vs {name}: {}", format_test(&dm));
+    }
+
+    let Some(prob) = &result.arima_probabilistic else {
+        return;
+    };
+    println!(
+        "ARIMA P(up): brier={:.5} log_loss={:.5} crps={:.6} n={}",
+        prob.brier, prob.log_loss, prob.crps, prob.n
+    );
+    for b in &prob.reliability {
+        println!(
+            "  p∈[{:.1},{:.1}) mean_p={:.3} observed={:.3} count={}",
+            b.lo, b.hi, b.mean_pred, b.observed, b.count
         );
     }
 }
 
+fn format_test(t: &TestResult) -> String {
+    format!("stat={:.3} p={:.4} n={}", t.statistic, t.p_value, t.n)
+}
+
+fn print_intervals(ci: &StrategyIntervals) {
+    let level = ci.level * 100.0;
+    println!(
+        "  {level:.0}% bootstrap: acc={} prec_up={} recall_up={} f1_up={}",
+        ci.bootstrap.accuracy.to_percent_string(),
+        ci.bootstrap.precision.to_percent_string(),
+        ci.bootstrap.recall.to_percent_string(),
+        ci.bootstrap.f1.to_percent_string(),
+    );
+    println!(
+        "  {level:.0}% wilson:    acc={} prec_up={} recall_up={}",
+        ci.wilson.accuracy.to_percent_string(),
+        ci.wilson.precision.to_percent_string(),
+        ci.wilson.recall.to_percent_string(),
+    );
+    let r = &ci.returns;
+    let rc = &ci.returns_ci;
+    println!(
+        "  returns (n={}): mean={:.4}% {} sharpe={:.3} {} total={:.2}% {}",
+        r.n,
+        r.mean * 100.0,
+        rc.mean.to_percent_string(),
+        r.sharpe,
+        format_interval(&rc.sharpe),
+        r.total * 100.0,
+        rc.total.to_percent_string(),
+    );
+    let v = &ci.vol_targeted;
+    println!(
+        "  vol-targeted (GARCH σ̂): mean={:.4}% sharpe={:.3} total={:.2}% (n={})",
+        v.mean * 100.0,
+        v.sharpe,
+        v.total * 100.0,
+        v.n,
+    );
+}
+
+fn format_interval(ci: &Interval) -> String {
+    format!("[{:.3}, {:.3}]", ci.lo, ci.hi)
+}
+
 fn evaluate_basic(close: &[f64], strategy: Strategy) -> EvaluatedStrategy {
     debug_assert!(!matches!(strategy, Strategy::ArimaDeltaPos { .. }));
-    finalize(close, signal_series_basic(close, strategy))
+    finalize(
+        &Evaluator::default(),
+        close,
+        signal_series_basic(close, strategy),
+    )
+}
+
+fn finalize(evaluator: &Evaluator, close: &[f64], signal: Vec<Option<bool>>) -> EvaluatedStrategy {
+    let Evaluation {
+        report,
+        metrics,
+        confusion,
+        outcomes,
+    } = evaluator.evaluate_signals(close, &signal);
+    EvaluatedStrategy {
+        report,
+        metrics,
+        confusion,
+        outcomes,
+        intervals: None,
+    }
 }
 
-fn evaluate_arima(
+fn finalize_with_intervals(
+    evaluator: &Evaluator,
+    scale: &[f64],
     close: &[f64],
-    window: usize,
-    forecaster: &mut dyn FnMut(&[f64]) -> f64,
+    signal: Vec<Option<bool>>,
 ) -> EvaluatedStrategy {
-    finalize(
+    let mut evaluated = finalize(evaluator, close, signal.clone());
+    evaluated.intervals = Some(signal_intervals(
+        evaluator,
+        scale,
         close,
-        signal_series_arima(close, window, |diff| forecaster(diff)),
-    )
+        &signal,
+        &evaluated.report,
+        &evaluated.outcomes,
+    ));
+    evaluated
+}
+
+/// scale[t] = ขนาดสถานะที่ถือจาก t ไป t+1 สำหรับ backtest แบบ vol targeting
+pub fn signal_intervals(
+    evaluator: &Evaluator,
+    scale: &[f64],
+    close: &[f64],
+    signal: &[Option<bool>],
+    report: &EvalReport,
+    outcomes: &[Option<Outcome>],
+) -> StrategyIntervals {
+    let config = BootstrapConfig::new(outcomes.len());
+    let returns = strategy_returns(close, signal);
+    StrategyIntervals {
+        level: config.level,
+        wilson: wilson_intervals(report, config.level),
+        bootstrap: evaluator.bootstrap_intervals(outcomes, &config),
+        returns: return_metrics(&returns),
+        returns_ci: return_intervals(&returns, &BootstrapConfig::new(returns.len())),
+        vol_targeted: return_metrics(&strategy_returns_scaled(close, signal, scale)),
+    }
 }
 
-fn finalize(close: &[f64], signal: Vec<Option<bool>>) -> EvaluatedStrategy {
-    let report = eval_with_signals(close, &signal);
-    let metrics = compute_metrics(&report);
-    EvaluatedStrategy { report, metrics }
+/// σ̂ ของ log return แท่ง t -> t+1 ที่รู้ได้ ณ ปิดแท่ง t (GARCH(1,1) walk-forward บน window แท่งก่อนหน้า)
+fn walk_forward_garch_sd(close: &[f64], window: usize, refit_every: usize) -> Vec<f64> {
+    let returns: Vec<f64> = close.windows(2).map(|w| (w[1] / w[0]).ln()).collect();
+    rolling_garch_sd(&returns, GarchKind::Garch, window, refit_every)
 }
 
 fn load_close_series(path: &PathBuf) -> Vec<f64> {
@@ -280,6 +451,13 @@ fn load_close_series(path: &PathBuf) -> Vec<f64> {
 }
 
 const DEFAULT_ARIMA_WINDOW: usize = 252;
+const DEFAULT_ARIMA_ORDER: (usize, usize) = (1, 1);
+/// refit เดือนละครั้ง (~21 วันเทรด)
+const DEFAULT_ARIMA_REFIT_EVERY: usize = 21;
+/// slope เปลี่ยนช้ากว่า level มาก -> trend เรียบพอจะเทียบกับ EMA ช่วงกลาง
+const DEFAULT_KALMAN_SNR: (f64, f64) = (0.01, 0.0001);
+/// σ 1% ต่อวัน (~16% ต่อปี) และ leverage ไม่เกิน 2 เท่า
+const DEFAULT_VOL_TARGET: (f64, f64) = (0.01, 2.0);
 
 fn default_three_eval_config(ema_period: usize, sma_period: usize) -> ThreeEvalConfig {
     let ema_slow_adjusted = if ema_period < sma_period {
@@ -294,5 +472,55 @@ fn default_three_eval_config(ema_period: usize, sma_period: usize) -> ThreeEvalC
         ema_vs_sma: (ema_period, sma_period),
         ema_fast_vs_slow: (ema_period, ema_slow_adjusted),
         arima_window,
+        arima_order: DEFAULT_ARIMA_ORDER,
+        arima_refit_every: DEFAULT_ARIMA_REFIT_EVERY,
+        kalman_snr: DEFAULT_KALMAN_SNR,
+        dead_band: DEFAULT_DEAD_BAND,
+        vol_target: DEFAULT_VOL_TARGET,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> ThreeEvalConfig {
+        ThreeEvalConfig {
+            ema_vs_sma: (5, 10),
+            ema_fast_vs_slow: (5, 10),
+            arima_window: 60,
+            arima_order: (1, 0),
+            arima_refit_every: 20,
+            kalman_snr: DEFAULT_KALMAN_SNR,
+            dead_band: DeadBand::None,
+            vol_target: DEFAULT_VOL_TARGET,
+        }
+    }
+
+    fn close(n: usize) -> Vec<f64> {
+        (0..n)
+            .map(|t| 100.0 * (1.0 + 0.01 * (t as f64 * 0.7).sin() + 0.0005 * t as f64))
+            .collect()
+    }
+
+    #[test]
+    fn test_arima_scores_come_from_one_model() {
+        let close = close(160);
+        let rolling = run_three_eval(&close, &config(), None);
+        assert!(rolling.arima_probabilistic.is_some_and(|p| p.n > 0));
+
+        // forecaster ของผู้เรียกทายขึ้นตลอด: สัญญาณต้องมาจากมัน และไม่มีคะแนนของ rolling ARMA ปนมา
+        let always_up: DeltaForecaster = Box::new(|_| 1.0);
+        let custom = run_three_eval(&close, &config(), Some(always_up));
+        assert!(custom.arima_probabilistic.is_none());
+        let outcomes: Vec<Outcome> = custom
+            .arima_delta_pos
+            .outcomes
+            .iter()
+            .flatten()
+            .copied()
+            .collect();
+        assert_eq!(outcomes.len(), close.len() - 60);
+        assert!(outcomes.iter().all(|o| o.pred == 1));
     }
 }
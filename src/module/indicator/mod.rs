@@ -1,3 +1,5 @@
-mod decide;
+pub mod backtest;
+pub mod decide;
 pub mod eval;
+pub mod pine;
 pub mod prediction;
@@ -0,0 +1,554 @@
+// สร้าง Pine Script v6 จาก Strategy / ArmaModel ที่ optimize แล้ว
+// ค่า input ถูกเติมจากผลที่ได้ และสูตรเขียนให้ตรงกับฝั่ง Rust (ema_series, kalman_trend, arma_residuals)
+// เพื่อให้กราฟบน TradingView ตรงกับ backtest
+
+use std::error::Error;
+use std::fmt::Write as _;
+use std::path::Path;
+
+use crate::module::indicator::decide::Strategy;
+use crate::module::model::{arma::ArmaModel, filter::spec::FilterSpec};
+
+const DIVIDER: &str =
+    "//****************************************************************************//";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PineScriptKind {
+    /// indicator(): วาดเส้น + สัญญาณ + alert
+    Indicator,
+    /// strategy(): long เมื่อสัญญาณขึ้น / short เมื่อสัญญาณลง เหมือน strategy_returns
+    Strategy,
+}
+
+/// float แบบ round-trip (Debug ของ f64 มีจุดทศนิยมเสมอ เช่น 1.0, 1e-7)
+fn lit(v: f64) -> String {
+    format!("{v:?}")
+}
+
+/// Σ c·v เป็น expression ของ Pine โดยเขียนเครื่องหมายลบแทนการบวกค่าติดลบ
+fn linear(terms: &[(f64, &str)]) -> String {
+    let mut out = String::new();
+    for (i, (c, v)) in terms.iter().enumerate() {
+        let term = if *c == 1.0 {
+            v.to_string()
+        } else {
+            format!("{} * {v}", lit(c.abs()))
+        };
+        match (i, c.is_sign_negative()) {
+            (0, true) => out.push_str(&format!("-{term}")),
+            (0, false) => out.push_str(&term),
+            (_, true) => out.push_str(&format!(" - {term}")),
+            (_, false) => out.push_str(&format!(" + {term}")),
+        }
+    }
+    out
+}
+
+struct PineWriter {
+    out: String,
+}
+
+impl PineWriter {
+    fn new(title: &str, kind: PineScriptKind, overlay: bool) -> Self {
+        let mut out = String::new();
+        out.push_str("// This Pine Script® code is subject to the terms of the Mozilla Public License 2.0 at https://mozilla.org/MPL/2.0/\n");
+        let _ = writeln!(
+            out,
+            "// generated by {} {} — ค่า input มาจากผล optimize ฝั่ง Rust",
+            env!("CARGO_PKG_NAME"),
+            env!("CARGO_PKG_VERSION")
+        );
+        out.push_str("\n//@version=6\n");
+        let overlay = if overlay { "true" } else { "false" };
+        match kind {
+            PineScriptKind::Indicator => {
+                let _ = writeln!(out, "indicator(\"{title}\", overlay={overlay})");
+            }
+            // ตัดสินตอนปิดแท่ง t แล้วถือถึงปิดแท่ง t+1 เหมือน strategy_returns
+            PineScriptKind::Strategy => {
+                let _ = writeln!(
+                    out,
+                    "strategy(\"{title}\", overlay={overlay}, process_orders_on_close=true, default_qty_type=strategy.percent_of_equity, default_qty_value=100)"
+                );
+            }
+        }
+        Self { out }
+    }
+
+    fn section(&mut self, name: &str) {
+        let _ = write!(self.out, "\n{DIVIDER}\n// {name}\n");
+    }
+
+    fn line(&mut self, s: &str) {
+        self.out.push_str(s);
+        self.out.push('\n');
+    }
+
+    /// ต้องมีตัวแปร ready (bool) และ up (bool) ก่อนเรียก
+    fn finish(mut self, kind: PineScriptKind, name: &str) -> String {
+        self.section("Signals (long เมื่อ up, short เมื่อไม่ up)");
+        self.line("longNow = ready and up");
+        self.line("shortNow = ready and not up");
+        self.line("buySignal = longNow and not longNow[1]");
+        self.line("sellSignal = shortNow and not shortNow[1]");
+        self.line("");
+        self.line("plotshape(showSignals and buySignal, \"Buy Signal\", shape.triangleup, location.belowbar, color.new(color.green, 0), size=size.small)");
+        self.line("plotshape(showSignals and sellSignal, \"Sell Signal\", shape.triangledown, location.abovebar, color.new(color.red, 0), size=size.small)");
+
+        if kind == PineScriptKind::Strategy {
+            self.section("Orders");
+            self.line("if buySignal");
+            self.line("    strategy.entry(\"Long\", strategy.long)");
+            self.line("if sellSignal");
+            self.line("    strategy.entry(\"Short\", strategy.short)");
+        }
+
+        self.section("Alert Conditions");
+        let _ = writeln!(
+            self.out,
+            "alertcondition(buySignal, title='{name} Buy', message='BUY {{{{exchange}}}}:{{{{ticker}}}} - {name}')"
+        );
+        let _ = writeln!(
+            self.out,
+            "alertcondition(sellSignal, title='{name} Sell', message='SELL {{{{exchange}}}}:{{{{ticker}}}} - {name}')"
+        );
+        self.out
+    }
+}
+
+/// EMA แบบเดียวกับ ema_series: seed ด้วย SMA ของ len แท่งแรก แล้วใช้ alpha = 2.105 / len
+const RUST_EMA_FN: &str = "\
+rustEma(float src, simple int len) =>
+    var float ema = na
+    var int count = 0
+    var float sum = 0.0
+    count += 1
+    if count <= len
+        sum += src
+        if count == len
+            ema := sum / len
+    else
+        ema := 2.105 / len * src + (1 - 2.105 / len) * ema
+    ema";
+
+fn ema_cross_script(
+    kind: PineScriptKind,
+    title: &str,
+    (fast_name, fast_len): (&str, usize),
+    (slow_name, slow_len, slow_is_sma): (&str, usize, bool),
+) -> String {
+    let mut w = PineWriter::new(title, kind, true);
+    w.section("User Inputs");
+    w.line(&format!(
+        "fastLen = input.int(title='{fast_name} period', defval={fast_len}, minval=1)"
+    ));
+    w.line(&format!(
+        "slowLen = input.int(title='{slow_name} period', defval={slow_len}, minval=1)"
+    ));
+    w.line("src = input.source(title='Source', defval=close)");
+    w.line("showSignals = input.bool(title='Show Buy/Sell Signals', defval=true)");
+
+    w.section("Moving Averages (ตรงกับ ema_series / sma_series ฝั่ง Rust)");
+    w.line(RUST_EMA_FN);
+    w.line("");
+    w.line("fastMa = rustEma(src, fastLen)");
+    w.line(if slow_is_sma {
+        "slowMa = ta.sma(src, slowLen)"
+    } else {
+        "slowMa = rustEma(src, slowLen)"
+    });
+    w.line("ready = not na(fastMa) and not na(slowMa)");
+    w.line("up = fastMa > slowMa");
+
+    w.section("Plot");
+    w.line(&format!(
+        "plot(fastMa, \"{fast_name}\", color=color.new(color.red, 0), linewidth=2)"
+    ));
+    w.line(&format!(
+        "plot(slowMa, \"{slow_name}\", color=color.new(color.blue, 0), linewidth=2)"
+    ));
+    w.line("bgcolor(ready ? (up ? color.new(color.green, 95) : color.new(color.red, 95)) : na)");
+    w.finish(kind, title)
+}
+
+fn macd_script(kind: PineScriptKind, fast: usize, slow: usize, signal: usize) -> String {
+    let title = format!("MACD({fast},{slow},{signal}) > signal");
+    let mut w = PineWriter::new(&title, kind, false);
+    w.section("User Inputs");
+    w.line(&format!(
+        "fastLen = input.int(title='Fast length', defval={fast}, minval=1)"
+    ));
+    w.line(&format!(
+        "slowLen = input.int(title='Slow length', defval={slow}, minval=1)"
+    ));
+    w.line(&format!(
+        "signalLen = input.int(title='Signal length', defval={signal}, minval=1)"
+    ));
+    w.line("src = input.source(title='Source', defval=close)");
+    w.line("showSignals = input.bool(title='Show Buy/Sell Signals', defval=true)");
+
+    w.section("MACD (ta.macd ใช้ EMA แบบ Pine เหมือน macd_series)");
+    w.line("[macdLine, signalLine, hist] = ta.macd(src, fastLen, slowLen, signalLen)");
+    w.line("ready = bar_index >= math.max(fastLen, slowLen) + signalLen - 2");
+    w.line("up = macdLine > signalLine");
+
+    w.section("Plot");
+    w.line("plot(macdLine, \"MACD\", color=color.blue, linewidth=2)");
+    w.line("plot(signalLine, \"Signal\", color=color.orange, linewidth=1)");
+    w.line("plot(hist, \"Histogram\", color=hist >= 0 ? color.green : color.red, style=plot.style_columns)");
+    w.finish(kind, &title)
+}
+
+fn rsi_script(kind: PineScriptKind, period: usize, level: f64) -> String {
+    let title = format!("RSI({period}) > {level}");
+    let mut w = PineWriter::new(&title, kind, false);
+    w.section("User Inputs");
+    w.line(&format!(
+        "rsiLen = input.int(title='RSI length', defval={period}, minval=1)"
+    ));
+    w.line(&format!(
+        "level = input.float(title='Level', defval={}, minval=0.0, maxval=100.0)",
+        lit(level)
+    ));
+    w.line("src = input.source(title='Source', defval=close)");
+    w.line("showSignals = input.bool(title='Show Buy/Sell Signals', defval=true)");
+
+    w.section("RSI (ta.rsi แบบ Wilder เหมือน rsi_series)");
+    w.line("rsi = ta.rsi(src, rsiLen)");
+    w.line("ready = not na(rsi)");
+    w.line("up = rsi > level");
+
+    w.section("Plot");
+    w.line("plot(rsi, \"RSI\", color=color.purple, linewidth=2)");
+    w.line("hline(level, \"Level\", color=color.gray, linestyle=hline.style_dashed)");
+    w.finish(kind, &title)
+}
+
+/// เขียน biquad แต่ละ section เป็น direct form II transposed ด้วยสัมประสิทธิ์ที่ออกแบบไว้แล้ว
+/// state เริ่มที่ steady state ของแท่งแรกเหมือน IirStream
+fn filter_script(kind: PineScriptKind, spec: FilterSpec) -> Option<String> {
+    let filter = spec.build()?;
+    let title = spec.label();
+    let mut w = PineWriter::new(&title, kind, !spec.is_oscillator());
+    w.section("User Inputs");
+    w.line("src = input.source(title='Source', defval=close)");
+    w.line("showSignals = input.bool(title='Show Buy/Sell Signals', defval=true)");
+
+    w.section(&format!(
+        "IIR filter: {} second-order sections (สัมประสิทธิ์จาก FilterSpec::build)",
+        filter.sections.len()
+    ));
+    let zi = filter.steady_state();
+    let mut input = "src".to_string();
+    for (i, (s, z)) in filter.sections.iter().zip(&zi).enumerate() {
+        w.line(&format!("var float s{i}z1 = na"));
+        w.line(&format!("var float s{i}z2 = na"));
+        w.line(&format!("if na(s{i}z1)"));
+        w.line(&format!("    s{i}z1 := {} * src", lit(z[0])));
+        w.line(&format!("    s{i}z2 := {} * src", lit(z[1])));
+        let (y, z1, z2) = (format!("s{i}y"), format!("s{i}z1"), format!("s{i}z2"));
+        w.line(&format!(
+            "{y} = {}",
+            linear(&[(s.b[0], &input), (1.0, &z1)])
+        ));
+        w.line(&format!(
+            "{z1} := {}",
+            linear(&[(s.b[1], &input), (-s.a[1], &y), (1.0, &z2)])
+        ));
+        w.line(&format!(
+            "{z2} := {}",
+            linear(&[(s.b[2], &input), (-s.a[2], &y)])
+        ));
+        input = format!("s{i}y");
+    }
+    w.line(&format!("filt = {input}"));
+    // slope ต้องใช้แท่งก่อนหน้าที่ผ่าน warm-up แล้ว (เหมือน filter_signal)
+    if spec.is_oscillator() {
+        w.line(&format!("ready = bar_index >= {}", spec.warmup()));
+        w.line("up = filt > 0");
+    } else {
+        w.line(&format!("ready = bar_index > {}", spec.warmup()));
+        w.line("up = filt > filt[1]");
+    }
+
+    w.section("Plot");
+    w.line(&format!(
+        "plot(filt, \"{title}\", color=ready ? (up ? color.green : color.red) : color.gray, linewidth=2)"
+    ));
+    if spec.is_oscillator() {
+        w.line("hline(0, \"Zero Line\", color=color.gray, linestyle=hline.style_dashed)");
+    }
+    Some(w.finish(kind, &title))
+}
+
+fn kalman_script(kind: PineScriptKind, level_snr: f64, slope_snr: f64) -> String {
+    let title = "Kalman local linear trend";
+    let mut w = PineWriter::new(title, kind, true);
+    w.section("User Inputs");
+    w.line(&format!(
+        "levelSnr = input.float(title='Level SNR', defval={}, minval=0.0)",
+        lit(level_snr)
+    ));
+    w.line(&format!(
+        "slopeSnr = input.float(title='Slope SNR', defval={}, minval=0.0)",
+        lit(slope_snr)
+    ));
+    w.line("src = input.source(title='Source', defval=close)");
+    w.line("showSignals = input.bool(title='Show Buy/Sell Signals', defval=true)");
+
+    w.section("Kalman filter (เหมือน kalman_trend: σ²_ε = 1, P0 = 1e7·I, a0 = (แท่งแรก, 0))");
+    for l in [
+        "var float lvl = na",
+        "var float slp = 0.0",
+        "var float p00 = 1e7",
+        "var float p01 = 0.0",
+        "var float p11 = 1e7",
+        "var int nObs = 0",
+        "if na(lvl)",
+        "    lvl := src",
+        "",
+        "// update",
+        "f = p00 + 1.0",
+        "v = src - lvl",
+        "k0 = p00 / f",
+        "k1 = p01 / f",
+        "level = lvl + k0 * v",
+        "slope = slp + k1 * v",
+        "f00 = p00 - k0 * p00",
+        "f01 = p01 - k0 * p01",
+        "f11 = p11 - k1 * p01",
+        "",
+        "// predict: T = [1 1; 0 1]",
+        "lvl := level + slope",
+        "slp := slope",
+        "p00 := f00 + 2 * f01 + f11 + levelSnr",
+        "p01 := f01 + f11",
+        "p11 := f11 + slopeSnr",
+        "nObs += 1",
+        "",
+        "// สองแท่งแรกยังเป็นช่วง diffuse ของ slope",
+        "ready = nObs > 2",
+        "up = slope > 0",
+    ] {
+        w.line(l);
+    }
+
+    w.section("Plot");
+    w.line("plot(level, \"Kalman level\", color=ready ? (up ? color.green : color.red) : color.gray, linewidth=2)");
+    w.finish(kind, title)
+}
+
+/// Pine script ของ strategy ; None สำหรับ ArimaDeltaPos (ต้องใช้ model ที่ fit แล้ว -> arma_script),
+/// strategy ที่ใช้ homodyne cycle (ยังไม่มีฝั่ง Pine) และ Filter ที่ parameter ใช้ไม่ได้
+pub fn strategy_script(strategy: Strategy, kind: PineScriptKind) -> Option<String> {
+    match strategy {
+        Strategy::EmaGtSma { ema, sma } => Some(ema_cross_script(
+            kind,
+            &format!("EMA({ema}) > SMA({sma})"),
+            ("EMA", ema),
+            ("SMA", sma, true),
+        )),
+        Strategy::EmaFastGtEmaSlow { fast, slow } => Some(ema_cross_script(
+            kind,
+            &format!("EMA({fast}) > EMA({slow})"),
+            ("Fast EMA", fast),
+            ("Slow EMA", slow, false),
+        )),
+        Strategy::KalmanTrend {
+            level_snr,
+            slope_snr,
+        } => Some(kalman_script(kind, level_snr, slope_snr)),
+        Strategy::MacdGtSignal { fast, slow, signal } => {
+            Some(macd_script(kind, fast, slow, signal))
+        }
+        Strategy::RsiGt { period, level } => Some(rsi_script(kind, period, level)),
+        Strategy::Filter { spec } => filter_script(kind, spec),
+        Strategy::ArimaDeltaPos { .. }
+        | Strategy::AdaptiveEmaTrend { .. }
+        | Strategy::CycleModeSwitch => None,
+    }
+}
+
+/// Pine script ของ ARMA(p,q) ที่ fit บน log return (Δln close) เหมือน signal_series_arima
+/// up เมื่อพยากรณ์ Δ_{t+1} > 0
+pub fn arma_script(model: &ArmaModel, kind: PineScriptKind) -> String {
+    let (p, q) = (model.params.phi.len(), model.params.theta.len());
+    let title = format!("ARMA({p},{q}) forecast");
+    let mut w = PineWriter::new(&title, kind, false);
+
+    w.section("User Inputs");
+    w.line(&format!(
+        "constant = input.float(title='Constant', defval={}, step=0.0001)",
+        lit(model.params.c)
+    ));
+    for (i, v) in model.params.phi.iter().enumerate() {
+        w.line(&format!(
+            "phi{0} = input.float(title='AR({0}) Coefficient', defval={1}, step=0.01)",
+            i + 1,
+            lit(*v)
+        ));
+    }
+    for (j, v) in model.params.theta.iter().enumerate() {
+        w.line(&format!(
+            "theta{0} = input.float(title='MA({0}) Coefficient', defval={1}, step=0.01)",
+            j + 1,
+            lit(*v)
+        ));
+    }
+    w.line("src = input.source(title='Source', defval=close)");
+    w.line("showSignals = input.bool(title='Show Buy/Sell Signals', defval=true)");
+
+    w.section("Log Returns (เหมือน signal_series_arima)");
+    w.line("ret = math.log(src) - math.log(src[1])");
+    w.line("r = nz(ret)");
+
+    w.section(&format!("ARMA({p},{q}) recursion"));
+    w.line("// ε_t = r_t - ŷ_t ; ŷ_{t+1} = c + Σ φ_i·r_{t+1-i} + Σ θ_j·ε_{t+1-j}");
+    w.line("var float fcPrev = 0.0");
+    w.line("eps = na(ret) ? 0.0 : r - fcPrev");
+    let mut expr = String::from("constant");
+    for i in 1..=p {
+        let lag = if i == 1 {
+            "r".to_string()
+        } else {
+            format!("nz(r[{}])", i - 1)
+        };
+        let _ = write!(expr, " + phi{i} * {lag}");
+    }
+    for j in 1..=q {
+        let lag = if j == 1 {
+            "eps".to_string()
+        } else {
+            format!("nz(eps[{}])", j - 1)
+        };
+        let _ = write!(expr, " + theta{j} * {lag}");
+    }
+    w.line(&format!("fc = {expr}"));
+    w.line("fcPrev := fc");
+    w.line(&format!("ready = bar_index > {}", p.max(q)));
+    w.line("up = fc > 0");
+
+    w.section("Plot");
+    w.line("plot(fc, \"Forecast next return\", color=color.blue, linewidth=2)");
+    w.line("plot(r, \"Actual Returns\", color=color.gray, linewidth=1)");
+    w.line("plot(eps, \"Residuals (ε)\", color=color.orange, linewidth=1)");
+    w.line("hline(0, \"Zero Line\", color=color.white, linestyle=hline.style_dashed)");
+    w.finish(kind, &title)
+}
+
+pub fn write_script(path: impl AsRef<Path>, script: &str) -> Result<(), Box<dyn Error>> {
+    let path = path.as_ref();
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, script)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::module::model::arma::ArmaParams;
+
+    #[test]
+    fn test_strategy_scripts_prefill_inputs() {
+        let s = strategy_script(
+            Strategy::EmaFastGtEmaSlow {
+                fast: 101,
+                slow: 484,
+            },
+            PineScriptKind::Indicator,
+        )
+        .unwrap();
+        assert!(s.contains("//@version=6\nindicator(\"EMA(101) > EMA(484)\""));
+        assert!(s.contains("defval=101,") && s.contains("defval=484,"));
+        assert!(s.contains("slowMa = rustEma(src, slowLen)"));
+        assert!(!s.contains("strategy.entry"));
+
+        let s = strategy_script(
+            Strategy::EmaGtSma { ema: 12, sma: 30 },
+            PineScriptKind::Strategy,
+        )
+        .unwrap();
+        assert!(s.contains("\nstrategy(\"EMA(12) > SMA(30)\""));
+        assert!(s.contains("ta.sma(src, slowLen)"));
+        assert!(s.contains("strategy.entry(\"Short\", strategy.short)"));
+
+        let s = strategy_script(
+            Strategy::KalmanTrend {
+                level_snr: 0.5,
+                slope_snr: 1e-4,
+            },
+            PineScriptKind::Indicator,
+        )
+        .unwrap();
+        assert!(s.contains("defval=0.5,") && s.contains("defval=0.0001,"));
+
+        let s = strategy_script(
+            Strategy::MacdGtSignal {
+                fast: 12,
+                slow: 26,
+                signal: 9,
+            },
+            PineScriptKind::Indicator,
+        )
+        .unwrap();
+        assert!(s.contains("ta.macd(src, fastLen, slowLen, signalLen)"));
+        assert!(s.contains("defval=26,"));
+
+        let s = strategy_script(
+            Strategy::Filter {
+                spec: FilterSpec::Decycler { period: 20.0 },
+            },
+            PineScriptKind::Indicator,
+        )
+        .unwrap();
+        assert!(s.contains("s0y = ") && !s.contains("s1y"));
+        assert!(s.contains("up = filt > filt[1]"));
+        assert_eq!(
+            linear(&[(0.5, "x"), (-0.25, "y"), (1.0, "z")]),
+            "0.5 * x - 0.25 * y + z"
+        );
+
+        assert!(
+            strategy_script(
+                Strategy::ArimaDeltaPos { window: 100 },
+                PineScriptKind::Indicator
+            )
+            .is_none()
+        );
+    }
+
+    #[test]
+    fn test_arma_script_coefficients_round_trip() {
+        let model = ArmaModel {
+            p: 2,
+            q: 1,
+            params: ArmaParams {
+                c: 0.000123456789,
+                phi: vec![-0.41234567891234, 0.1],
+                theta: vec![0.87],
+            },
+            aic: 0.0,
+            bic: 0.0,
+            aicc: 0.0,
+            n_obs: 0,
+            n_params: 5,
+            sigma2: 1.0,
+            state: Default::default(),
+        };
+        let s = arma_script(&model, PineScriptKind::Strategy);
+        for v in [
+            model.params.c,
+            model.params.phi[0],
+            model.params.phi[1],
+            model.params.theta[0],
+        ] {
+            let needle = format!("defval={v:?},");
+            assert!(s.contains(&needle), "{needle} missing");
+            assert_eq!(needle[7..needle.len() - 1].parse::<f64>().unwrap(), v);
+        }
+        assert!(s.contains("fc = constant + phi1 * r + phi2 * nz(r[1]) + theta1 * eps\n"));
+        assert!(s.contains("ready = bar_index > 2"));
+    }
+}
@@ -0,0 +1,342 @@
+// ARIMA(p, d, q): difference d ครั้งแล้วฟิต ARMA บน ∇^d x
+// พยากรณ์กลับเป็นสเกลระดับเดิมด้วย integrate_n
+// drift = ค่าคงที่ในสมการของ ∇^d x (d = 0 คือ mean, d = 1 คือ drift เชิงเส้น)
+
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::module::{
+    data::read_csv::read_close_series,
+    model::{
+        arma::{
+            ArmaModel, ForecastStep, arma_predict_rolling_dist, arma_residuals,
+            fit_arma_with_ic_const, forecast_std_errors, psi_weights,
+            seasonal::suggest_season_period,
+        },
+        differencing::{diff_n, integrate_n},
+        garch::{GarchKind, GarchModel, fit_garch, garch_forecast_std_errors},
+        store::{SavedModel, TrainingRange},
+        util::polynomial::{ar_poly, diff_poly, poly_mul, poly_to_ar},
+    },
+    plot::plot_forecast::plot_forecast,
+    util::stationarity::ndiffs,
+};
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Arima {
+    pub d: usize,
+    pub drift: bool,
+    pub arma: ArmaModel,
+    /// ระดับล่าสุด d + p ตัวของข้อมูลที่ใช้ฟิต: พอสำหรับ integrate กลับ (lag ของ ARMA อยู่ใน arma.state)
+    tail: Vec<f64>,
+}
+
+impl Arima {
+    /// ฟิต ARIMA(p, d, q) ; d >= 2 จะไม่ใส่ค่าคงที่ (drift กำลังสองไม่สมเหตุผล)
+    pub fn fit(levels: &[f64], p: usize, d: usize, q: usize, drift: bool) -> Option<Self> {
+        let drift = drift && d < 2;
+        let w = diff_n(levels, d);
+        let arma = fit_arma_with_ic_const(&w, p, q, drift)?;
+        Some(Self {
+            d,
+            drift,
+            arma,
+            tail: levels[levels.len().saturating_sub(d + p)..].to_vec(),
+        })
+    }
+
+    /// เลือก d อัตโนมัติด้วย KPSS/ADF ซ้ำ ๆ แล้วฟิต
+    pub fn fit_auto_d(
+        levels: &[f64],
+        p: usize,
+        q: usize,
+        drift: bool,
+        max_d: usize,
+    ) -> Option<Self> {
+        Self::fit(levels, p, ndiffs(levels, max_d), q, drift)
+    }
+
+    pub fn p(&self) -> usize {
+        self.arma.p
+    }
+
+    pub fn q(&self) -> usize {
+        self.arma.q
+    }
+
+    /// พยากรณ์ระดับ h ก้าวถัดจากจุดสุดท้าย
+    pub fn forecast(&self, h: usize) -> Vec<f64> {
+        let w_hat: Vec<f64> = self.arma.forecast(h).iter().map(|f| f.mean).collect();
+        integrate_n(&self.tail, self.d, &w_hat)
+    }
+
+    /// ψ-weights บนสเกลระดับ มาจาก φ(B)(1 - B)^d
+    fn level_psi(&self, h: usize) -> Vec<f64> {
+        let ar = poly_mul(&ar_poly(&self.arma.params.phi, 1), &diff_poly(self.d, 0, 1));
+        psi_weights(&poly_to_ar(&ar), &self.arma.params.theta, h)
+    }
+
+    /// พยากรณ์ระดับพร้อมช่วง 80/95% โดย shock มี variance คงที่ σ²
+    pub fn forecast_intervals(&self, h: usize) -> Vec<ForecastStep> {
+        let se = forecast_std_errors(&self.level_psi(h), self.arma.sigma2);
+        self.forecast(h)
+            .iter()
+            .zip(se)
+            .map(|(m, se)| ForecastStep::new(*m, se))
+            .collect()
+    }
+
+    /// เหมือน forecast_intervals แต่ variance ของ shock มาจาก GARCH ที่ฟิตบน residual ของ ∇^d x
+    /// -> ช่วงกว้างขึ้นเมื่อความผันผวนล่าสุดสูง แล้วค่อย ๆ กลับสู่ระดับยาว
+    pub fn forecast_intervals_garch(&self, garch: &GarchModel, h: usize) -> Vec<ForecastStep> {
+        let se = garch_forecast_std_errors(&self.level_psi(h), &garch.forecast_variance(h));
+        self.forecast(h)
+            .iter()
+            .zip(se)
+            .map(|(m, se)| ForecastStep::new(*m, se))
+            .collect()
+    }
+
+    /// residual ของ ARMA บน ∇^d levels (levels = ข้อมูลเดียวกับที่ใช้ฟิต) สำหรับฟิต GARCH
+    pub fn residuals(&self, levels: &[f64]) -> Vec<f64> {
+        arma_residuals(&diff_n(levels, self.d), &self.arma.params)
+    }
+
+    /// พยากรณ์ 1 ก้าวในตัวอย่างบนสเกลระดับ ; levels = ข้อมูลเดียวกับที่ใช้ฟิต, ค่า i คือ x̂ ของ levels[d + 1 + i]
+    /// ใช้ x̂_{t} = ŵ_{t} + (x_{t} - w_{t}) ซึ่งส่วนหลังขึ้นกับอดีตอย่างเดียว
+    pub fn fitted_next_levels(&self, levels: &[f64]) -> Vec<f64> {
+        let w = diff_n(levels, self.d);
+        arma_predict_rolling_dist(&w, &self.arma.params, self.arma.sigma2)
+            .iter()
+            .enumerate()
+            .map(|(i, g)| {
+                let t = self.d + 1 + i;
+                g.mean + levels[t] - w[i + 1]
+            })
+            .collect()
+    }
+}
+
+/// Auto ARIMA: หา d ก่อนด้วย ndiffs แล้ว grid search (p, q) ด้วย BIC
+pub fn auto_arima(
+    levels: &[f64],
+    max_p: usize,
+    max_d: usize,
+    max_q: usize,
+    drift: bool,
+) -> Option<Arima> {
+    let d = ndiffs(levels, max_d);
+    let mut best: Option<Arima> = None;
+    for p in 0..=max_p {
+        for q in 0..=max_q {
+            let Some(m) = Arima::fit(levels, p, d, q, drift) else {
+                continue;
+            };
+            if best.as_ref().is_none_or(|b| m.arma.bic < b.arma.bic) {
+                best = Some(m);
+            }
+        }
+    }
+    best
+}
+
+pub fn arima_model() {
+    let data_path = PathBuf::from("data/SPX_log.csv");
+    let pairs = match read_close_series(&data_path) {
+        Ok(pairs) => pairs,
+        Err(e) => {
+            eprintln!("unable to read {}: {}", data_path.display(), e);
+            return;
+        }
+    };
+    let levels: Vec<f64> = pairs.iter().map(|(_, v)| *v).collect();
+
+    let Some(model) = auto_arima(&levels, 3, 2, 3, true) else {
+        eprintln!("ARIMA fit failed");
+        return;
+    };
+    let par = &model.arma.params;
+    println!(
+        "Auto ARIMA({},{},{}) drift={}: c={:.6}, phi={:?}, theta={:?}, BIC={:.4}",
+        model.p(),
+        model.d,
+        model.q(),
+        model.drift,
+        par.c,
+        par.phi,
+        par.theta,
+        model.arma.bic
+    );
+    // คาบฤดูกาลที่เด่นที่สุดจาก FFT ของ ∇^d x (ใช้เป็น s ของ SARIMA ได้)
+    match suggest_season_period(&diff_n(&levels, model.d), 2, 252) {
+        Some(s) => println!("  dominant FFT period = {} bars (seasonal s)", s),
+        None => println!("  no dominant FFT period found"),
+    }
+
+    // ช่วงพยากรณ์ใช้ GARCH(1,1) บน residual ถ้าฟิตได้ (ช่วงกว้างตามความผันผวนล่าสุด) ไม่งั้นใช้ σ² คงที่
+    let horizon = 20;
+    let constant = model.forecast_intervals(horizon);
+    let steps = match fit_garch(&model.residuals(&levels), GarchKind::Garch) {
+        Some(g) => {
+            println!(
+                "  GARCH(1,1) on residuals: next σ={:.6} vs constant σ={:.6}",
+                g.variance.last().map_or(f64::NAN, |v| v.sqrt()),
+                model.arma.sigma2.sqrt()
+            );
+            model.forecast_intervals_garch(&g, horizon)
+        }
+        None => {
+            eprintln!("  GARCH fit failed; using constant-variance intervals");
+            constant.clone()
+        }
+    };
+    for (h, (f, c)) in steps.iter().zip(&constant).take(5).enumerate() {
+        println!(
+            "  h={:>2}: {:.6}  80% [{:.6}, {:.6}]  95% [{:.6}, {:.6}]  (constant-σ 95% ±{:.6})",
+            h + 1,
+            f.mean,
+            f.lo80,
+            f.hi80,
+            f.lo95,
+            f.hi95,
+            c.hi95 - c.mean
+        );
+    }
+
+    // ต่อเวลาอนาคตด้วยระยะห่างของแท่งสุดท้าย
+    let times: Vec<i64> = pairs.iter().map(|(t, _)| *t).collect();
+    let step = match times.len() {
+        0 | 1 => 86_400,
+        n => (times[n - 1] - times[n - 2]).max(1),
+    };
+    let last_t = times.last().copied().unwrap_or(0);
+    let future: Vec<(i64, ForecastStep)> = steps
+        .into_iter()
+        .enumerate()
+        .map(|(h, f)| (last_t + step * (h as i64 + 1), f))
+        .collect();
+    // บันทึก model ไว้ใช้พยากรณ์รอบหน้าโดยไม่ต้อง fit ใหม่
+    let model_path = "output/arima_model.json";
+    let saved = SavedModel::new(model, &levels, TrainingRange::from_timestamps(&times));
+    match saved.save(model_path) {
+        Ok(()) => println!(
+            "  model saved to {} (hash={})",
+            model_path, saved.metadata.data_hash
+        ),
+        Err(e) => eprintln!("Error saving model: {}", e),
+    }
+
+    let history = &pairs[pairs.len().saturating_sub(250)..];
+    match plot_forecast(
+        history,
+        &future,
+        "ARIMA forecast",
+        "output/arima_forecast.png",
+    ) {
+        Ok(path) => println!("  forecast plot saved to {}", path.display()),
+        Err(e) => eprintln!("Error plotting forecast: {}", e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::module::util::math::rng::SplitMix64;
+
+    fn random_walk(n: usize, drift: f64, seed: u64) -> Vec<f64> {
+        let mut rng = SplitMix64::new(seed);
+        let mut x = 0.0;
+        (0..n)
+            .map(|_| {
+                x += drift + (rng.next_f64() - 0.5);
+                x
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_auto_d_on_random_walk() {
+        let x = random_walk(600, 0.0, 5);
+        assert_eq!(ndiffs(&x, 2), 1);
+        let diffs = diff_n(&x, 1);
+        assert_eq!(ndiffs(&diffs, 2), 0);
+    }
+
+    #[test]
+    fn test_drift_forecast_extends_trend() {
+        let x = random_walk(500, 0.2, 9);
+        let m = Arima::fit(&x, 0, 1, 0, true).unwrap();
+        let f = m.forecast(10);
+        let last = *x.last().unwrap();
+        // ARIMA(0,1,0) + drift: x̂_{n+h} = x_n + h·c
+        for (h, v) in f.iter().enumerate() {
+            let expected = last + (h + 1) as f64 * m.arma.params.c;
+            assert!((v - expected).abs() < 1e-9);
+        }
+        assert!((m.arma.params.c - 0.2).abs() < 0.05);
+
+        let no_drift = Arima::fit(&x, 0, 1, 0, false).unwrap();
+        assert!(
+            no_drift
+                .forecast(3)
+                .iter()
+                .all(|v| (v - last).abs() < 1e-12)
+        );
+    }
+
+    #[test]
+    fn test_random_walk_interval_grows_with_sqrt_h() {
+        let x = random_walk(300, 0.0, 21);
+        let m = Arima::fit(&x, 0, 1, 0, false).unwrap();
+        let f = m.forecast_intervals(9);
+        let sd = m.arma.sigma2.sqrt();
+        assert!((f[0].se - sd).abs() < 1e-12);
+        assert!((f[8].se - 3.0 * sd).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_garch_intervals_widen_after_turbulence() {
+        use crate::module::model::garch::GarchParams;
+        let x = random_walk(300, 0.0, 21);
+        let m = Arima::fit(&x, 0, 1, 0, false).unwrap();
+        let s2 = m.arma.sigma2;
+        // h ล่าสุด = 4σ² แล้วกลับสู่ σ² ด้วย β = 0.5
+        let garch = GarchModel {
+            kind: GarchKind::Garch,
+            params: GarchParams {
+                omega: 0.5 * s2,
+                alpha: 0.0,
+                beta: 0.5,
+                gamma: 0.0,
+            },
+            std_errors: vec![],
+            loglik: 0.0,
+            aic: 0.0,
+            bic: 0.0,
+            variance: vec![s2, 4.0 * s2],
+        };
+        let g = m.forecast_intervals_garch(&garch, 6);
+        let c = m.forecast_intervals(6);
+        assert!((g[0].se - 2.0 * s2.sqrt()).abs() < 1e-12);
+        // random walk: ψ_j = 1 -> se² สะสม h ทีละก้าว
+        let h = garch.forecast_variance(6);
+        for k in 0..6 {
+            let var: f64 = h[..=k].iter().sum();
+            assert!((g[k].se.powi(2) - var).abs() < 1e-12);
+            assert!(g[k].se > c[k].se);
+            assert_eq!(g[k].mean, c[k].mean);
+        }
+    }
+
+    #[test]
+    fn test_fitted_next_levels_matches_random_walk_identity() {
+        let x = random_walk(50, 0.0, 3);
+        let m = Arima::fit(&x, 0, 1, 0, false).unwrap();
+        let fitted = m.fitted_next_levels(&x);
+        // ไม่มีพารามิเตอร์ -> x̂_{t+1} = x_t
+        for (i, v) in fitted.iter().enumerate() {
+            assert!((v - x[i + 1]).abs() < 1e-12);
+        }
+    }
+}
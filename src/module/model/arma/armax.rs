@@ -0,0 +1,302 @@
+// ARMAX(p, q): y_t = c + β'x_t + Σ φ_i y_{t-i} + Σ θ_j ε_{t-j} + ε_t
+// x_t ต้องรู้ได้ ณ เวลาที่พยากรณ์ y_t (ใช้ lag ของตัวแปรภายนอกจาก data::align)
+
+use std::path::PathBuf;
+
+use nalgebra::{DMatrix, DVector};
+
+use crate::module::{
+    data::{
+        align::{DesignMatrix, day_of_week_dummies, lag, log_returns, log_volume},
+        read_csv::read_bars,
+    },
+    eval::{TargetKind, ZeroRule, evaluator::Evaluator},
+    model::{
+        arma::{
+            ArmaModel, ArmaParams, ArmaState, ForecastStep, css_information_criteria,
+            forecast_std_errors, nelder_mead_min, psi_weights,
+        },
+        util::unpack_params::{ar_to_unconstrained, ma_to_unconstrained, unpack_params},
+    },
+};
+
+#[derive(Clone, Debug)]
+pub struct ArmaxModel {
+    pub arma: ArmaModel,
+    /// สัมประสิทธิ์ของแต่ละคอลัมน์ใน design matrix
+    pub beta: Vec<f64>,
+}
+
+fn exog_effect(beta: &[f64], row: &[f64]) -> f64 {
+    beta.iter().zip(row).map(|(b, x)| b * x).sum()
+}
+
+pub fn armax_residuals(y: &[f64], x: &[Vec<f64>], par: &ArmaParams, beta: &[f64]) -> Vec<f64> {
+    let n = y.len();
+    let mut e = vec![0.0; n];
+    for t in 0..n {
+        let mut yhat = par.c + exog_effect(beta, &x[t]);
+        for (i, phi) in par.phi.iter().enumerate() {
+            if t > i {
+                yhat += phi * y[t - 1 - i];
+            }
+        }
+        for (j, theta) in par.theta.iter().enumerate() {
+            if t > j {
+                yhat += theta * e[t - 1 - j];
+            }
+        }
+        e[t] = y[t] - yhat;
+    }
+    e
+}
+
+/// OLS ของ y บน [1, x] ใช้เป็นจุดเริ่มของ c, β
+fn ols_start(y: &[f64], x: &[Vec<f64>]) -> Option<Vec<f64>> {
+    let n = y.len();
+    let k = x.first().map_or(0, |r| r.len());
+    let design = DMatrix::from_fn(n, k + 1, |t, j| if j == 0 { 1.0 } else { x[t][j - 1] });
+    let target = DVector::from_column_slice(y);
+    let coef = design.svd(true, true).solve(&target, 1e-12).ok()?;
+    Some(coef.iter().copied().collect())
+}
+
+/// CSS ของ ARMAX ; x[t] คือแถวของตัวแปรภายนอกที่คู่กับ y[t]
+pub fn fit_armax(y: &[f64], x: &[Vec<f64>], p: usize, q: usize) -> Option<ArmaxModel> {
+    let n = y.len();
+    let k = x.first().map_or(0, |r| r.len());
+    if x.len() != n
+        || n <= 1 + p + q + k
+        || y.iter().chain(x.iter().flatten()).any(|v| !v.is_finite())
+    {
+        return None;
+    }
+
+    // u = [c, u_ar(p), u_ma(q), β(k)]
+    let ols = ols_start(y, x)?;
+    let mut u0 = vec![ols[0]];
+    u0.extend(ar_to_unconstrained(&vec![0.0; p]));
+    u0.extend(ma_to_unconstrained(&vec![0.0; q]));
+    u0.extend(&ols[1..]);
+
+    let sse = |u: &[f64]| -> f64 {
+        let par = unpack_params(u, p, q);
+        armax_residuals(y, x, &par, &u[1 + p + q..])
+            .iter()
+            .map(|e| e * e)
+            .sum()
+    };
+    let u = nelder_mead_min(u0, sse, 800 + 200 * k, 1e-8);
+    let total = sse(&u);
+    let params = unpack_params(&u, p, q);
+    let dim = 1 + p + q + k;
+    let (aic, bic, aicc) = css_information_criteria(n, total, dim);
+    Some(ArmaxModel {
+        arma: ArmaModel {
+            p,
+            q,
+            params,
+            aic,
+            bic,
+            aicc,
+            n_obs: n,
+            n_params: dim + 1,
+            sigma2: total / n as f64,
+            // lag ของ ARMAX ต้องมี x ด้วย -> ใช้ ArmaxModel::forecast แทน
+            state: ArmaState::default(),
+        },
+        beta: u[1 + p + q..].to_vec(),
+    })
+}
+
+impl ArmaxModel {
+    /// พยากรณ์ 1 ก้าวในตัวอย่าง: ค่า t คือ ŷ_{t+1} (ใช้ x[t + 1])
+    pub fn predict_rolling(&self, y: &[f64], x: &[Vec<f64>]) -> Vec<f64> {
+        let par = &self.arma.params;
+        let e = armax_residuals(y, x, par, &self.beta);
+        (1..y.len()).map(|t| y[t] - e[t]).collect()
+    }
+
+    /// พยากรณ์ h = x_future.len() ก้าวถัดจากปลาย y โดยใช้ค่าภายนอกในอนาคตที่กำหนด
+    pub fn forecast(&self, y: &[f64], x: &[Vec<f64>], x_future: &[Vec<f64>]) -> Vec<ForecastStep> {
+        let par = &self.arma.params;
+        let n = y.len();
+        let h = x_future.len();
+        let e = armax_residuals(y, x, par, &self.beta);
+        let mut ext = y.to_vec();
+        for (k, row) in x_future.iter().enumerate() {
+            let t = n + k;
+            let mut yhat = par.c + exog_effect(&self.beta, row);
+            for (i, phi) in par.phi.iter().enumerate() {
+                if t > i {
+                    yhat += phi * ext[t - 1 - i];
+                }
+            }
+            for (j, theta) in par.theta.iter().enumerate() {
+                if t > j && t - 1 - j < n {
+                    yhat += theta * e[t - 1 - j];
+                }
+            }
+            ext.push(yhat);
+        }
+        let psi = psi_weights(&par.phi, &par.theta, h);
+        ext[n..]
+            .iter()
+            .zip(forecast_std_errors(&psi, self.arma.sigma2))
+            .map(|(m, se)| ForecastStep::new(*m, se))
+            .collect()
+    }
+}
+
+/// (y, x) สำหรับ fit_armax: ช่วงต่อเนื่องที่ยาวที่สุด (ถ้ายาวเท่ากันเอาช่วงล่าสุด) ที่ทั้ง y และทุกคอลัมน์ของ design ครบ
+/// ไม่เติมศูนย์และไม่ต่อแถวข้ามช่องว่าง ไม่งั้น lag ของ AR/MA จะเป็นค่าก่อนช่องว่างแทน lag จริง
+fn armax_sample(
+    y: &[Option<f64>],
+    design: &DesignMatrix,
+) -> Result<(Vec<f64>, Vec<Vec<f64>>), String> {
+    let start = design
+        .complete_from()
+        .ok_or("no row has every regressor; nothing to fit")?;
+    let end = y.len().min(design.len());
+    let rows = design.rows(start, end);
+    let complete = |i: usize| {
+        y[start + i].is_some_and(|v| v.is_finite()) && rows[i].iter().all(|v| v.is_finite())
+    };
+    let (mut best, mut run_start) = (0..0, 0);
+    for i in 0..rows.len() {
+        if !complete(i) {
+            run_start = i + 1;
+        } else if i + 1 - run_start >= best.len() {
+            best = run_start..i + 1;
+        }
+    }
+    if best.is_empty() {
+        return Err("no row has both the target and every regressor".into());
+    }
+    let ys = y[start + best.start..start + best.end]
+        .iter()
+        .map(|v| v.unwrap_or(f64::NAN))
+        .collect();
+    Ok((ys, rows[best].to_vec()))
+}
+
+/// ตัวอย่าง: log return ของ SPX กับ log volume ของแท่งก่อนหน้าและ dummy วันในสัปดาห์
+pub fn armax_model() {
+    let data_path = PathBuf::from("data/SPX.csv");
+    let bars = match read_bars(&data_path) {
+        Ok(b) => b,
+        Err(e) => {
+            eprintln!("unable to read {}: {}", data_path.display(), e);
+            return;
+        }
+    };
+    let ts: Vec<i64> = bars.iter().map(|b| b.timestamp).collect();
+    let close: Vec<Option<f64>> = bars.iter().map(|b| Some(b.close)).collect();
+    let ret = log_returns(&close);
+
+    let mut design = DesignMatrix::new()
+        .with("log_volume_lag1", lag(&log_volume(&bars), 1))
+        .with("return_lag2", lag(&ret, 2));
+    for (name, col) in day_of_week_dummies(&ts) {
+        design.push(name, col);
+    }
+    let design = design.forward_fill();
+
+    let (y, x) = match armax_sample(&ret, &design) {
+        Ok(sample) => sample,
+        Err(e) => {
+            eprintln!("ARMAX: {}", e);
+            return;
+        }
+    };
+
+    let Some(model) = fit_armax(&y, &x, 1, 1) else {
+        eprintln!("ARMAX fit failed");
+        return;
+    };
+    let par = &model.arma.params;
+    println!(
+        "ARMAX(1,1): c={:.6e} phi={:?} theta={:?} AIC={:.2} BIC={:.2}",
+        par.c, par.phi, par.theta, model.arma.aic, model.arma.bic
+    );
+    for (name, b) in design.names.iter().zip(&model.beta) {
+        println!("  β[{name}] = {b:.6e}");
+    }
+
+    // ทิศทางของ return ถัดไป (y เป็น return อยู่แล้ว จึงเทียบกับระดับสะสม)
+    let pred = model.predict_rolling(&y, &x);
+    let mut level = vec![0.0];
+    for r in &y {
+        level.push(level.last().unwrap() + r);
+    }
+    let evaluation =
+        Evaluator::new(TargetKind::Diff, ZeroRule::Ignore).evaluate_forecasts(&level[1..], &pred);
+    println!(
+        "  directional accuracy = {:.2}% (n={})",
+        evaluation.metrics.accuracy * 100.0,
+        evaluation.report.total
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::module::util::math::rng::SplitMix64;
+
+    #[test]
+    fn test_fit_armax_recovers_beta() {
+        let mut rng = SplitMix64::new(13);
+        let mut draw = || rng.next_normal();
+        let n = 1500;
+        let x: Vec<Vec<f64>> = (0..n)
+            .map(|t| vec![draw(), (t % 5 == 0) as u8 as f64])
+            .collect();
+        let mut y = vec![0.0; n];
+        for t in 0..n {
+            let prev = if t > 0 { y[t - 1] } else { 0.0 };
+            y[t] = 0.1 + 0.8 * x[t][0] - 0.5 * x[t][1] + 0.4 * prev + 0.5 * draw();
+        }
+        let m = fit_armax(&y, &x, 1, 0).unwrap();
+        assert!((m.beta[0] - 0.8).abs() < 0.05, "{:?}", m.beta);
+        assert!((m.beta[1] + 0.5).abs() < 0.1, "{:?}", m.beta);
+        assert!((m.arma.params.phi[0] - 0.4).abs() < 0.05);
+
+        let f = m.forecast(&y, &x, &[vec![1.0, 0.0], vec![0.0, 1.0]]);
+        let par = &m.arma.params;
+        let step1 = par.c + m.beta[0] + par.phi[0] * y[n - 1];
+        assert!((f[0].mean - step1).abs() < 1e-12);
+        let step2 = par.c + m.beta[1] + par.phi[0] * step1;
+        assert!((f[1].mean - step2).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_armax_sample_never_crosses_a_gap() {
+        let design = DesignMatrix::new().with(
+            "x",
+            vec![None, Some(1.0), Some(2.0), Some(3.0), Some(4.0), Some(5.0)],
+        );
+        let y = [Some(0.5), Some(0.1), Some(0.2), None, Some(0.3), Some(0.4)];
+        let (ys, xs) = armax_sample(&y, &design).unwrap();
+        // ช่วงยาวเท่ากันสองช่วง (t=1..3, t=4..6) -> ใช้ช่วงล่าสุด ; y[t-1] ของ t=4 ต้องไม่ใช่ y[2]
+        assert_eq!(ys, vec![0.3, 0.4]);
+        assert_eq!(xs, vec![vec![4.0], vec![5.0]]);
+
+        let y = [Some(0.5), Some(0.1), Some(0.2), Some(0.6), None, Some(0.4)];
+        let (ys, xs) = armax_sample(&y, &design).unwrap();
+        assert_eq!(ys, vec![0.1, 0.2, 0.6]);
+        assert_eq!(xs, vec![vec![1.0], vec![2.0], vec![3.0]]);
+        // AR(1) residual ของแถวแรกหลังจุดเริ่มไม่มี lag จากนอกช่วง
+        let par = ArmaParams {
+            c: 0.0,
+            phi: vec![0.5],
+            theta: vec![],
+        };
+        let e = armax_residuals(&ys, &xs, &par, &[0.0]);
+        assert_eq!(e[0], 0.1);
+        assert!((e[1] - (0.2 - 0.05)).abs() < 1e-12);
+
+        let empty = DesignMatrix::new().with("x", vec![None, None]);
+        assert!(armax_sample(&[Some(1.0), Some(2.0)], &empty).is_err());
+        assert!(armax_sample(&[None, None, None], &design).is_err());
+    }
+}
@@ -0,0 +1,280 @@
+// Exact Gaussian MLE ของ ARMA(p, q) ผ่าน Kalman filter บน state-space form (Harvey)
+//   y_t - μ = Z α_t,  α_{t+1} = T α_t + R ε_{t+1},  ε ~ N(0, σ²)
+// σ² ถูก concentrate ออกจาก likelihood, AR/MA ถูก reparameterize ผ่าน PACF
+// เพื่อให้ stationary / invertible ทุกจุดที่ optimizer เดินไป
+
+use nalgebra::DMatrix;
+
+use crate::module::model::{
+    arma::{ArmaModel, ArmaParams, ArmaState, fit_arma_css, information_criteria, nelder_mead_min},
+    state_space::stationary_cov,
+    util::{
+        numdiff::{hessian_std_errors, numerical_hessian},
+        unpack_params::{
+            ar_to_pacf, ar_to_unconstrained, ma_to_unconstrained, unconstrained_to_ar,
+            unconstrained_to_ma,
+        },
+    },
+};
+
+#[derive(Clone, Debug)]
+pub struct ArmaMleFit {
+    pub model: ArmaModel,
+    /// ค่าเฉลี่ยของกระบวนการ μ = c / (1 - Σφ)
+    pub mean: f64,
+    pub loglik: f64,
+    /// เรียงตาม [c, φ_1..φ_p, θ_1..θ_q]
+    pub std_errors: Vec<f64>,
+    pub t_stats: Vec<f64>,
+}
+
+impl ArmaMleFit {
+    /// ชื่อพารามิเตอร์เรียงตรงกับ std_errors / t_stats
+    pub fn param_names(&self) -> Vec<String> {
+        let mut names = vec!["c".to_string()];
+        names.extend((1..=self.model.p).map(|i| format!("ar{i}")));
+        names.extend((1..=self.model.q).map(|j| format!("ma{j}")));
+        names
+    }
+
+    pub fn param_values(&self) -> Vec<f64> {
+        let par = &self.model.params;
+        let mut v = vec![par.c];
+        v.extend(&par.phi);
+        v.extend(&par.theta);
+        v
+    }
+}
+
+/// exact log-likelihood ที่ concentrate σ² ออกแล้ว คืน (loglik, σ̂²)
+/// None เมื่อพารามิเตอร์ไม่ stationary หรือ filter เสื่อม
+pub fn arma_exact_loglik(y: &[f64], mean: f64, phi: &[f64], theta: &[f64]) -> Option<(f64, f64)> {
+    let n = y.len();
+    if n == 0 {
+        return None;
+    }
+    let p = phi.len();
+    let q = theta.len();
+    let r = p.max(q + 1);
+
+    let t = DMatrix::from_fn(r, r, |i, j| {
+        if j == 0 && i < p {
+            phi[i]
+        } else if j == i + 1 {
+            1.0
+        } else {
+            0.0
+        }
+    });
+    let rvec: Vec<f64> = (0..r)
+        .map(|i| {
+            if i == 0 {
+                1.0
+            } else {
+                theta.get(i - 1).copied().unwrap_or(0.0)
+            }
+        })
+        .collect();
+    let rr = DMatrix::from_fn(r, r, |i, j| rvec[i] * rvec[j]);
+    let p0 = stationary_cov(&t, &rr)?;
+
+    // ใช้ array แบน ๆ ใน loop หลักเพื่อความเร็ว
+    let mut a = vec![0.0; r];
+    let mut pm: Vec<f64> = (0..r * r).map(|k| p0[(k / r, k % r)]).collect();
+    let mut tmp = vec![0.0; r * r];
+    let mut sum_log_f = 0.0;
+    let mut sum_v2f = 0.0;
+
+    for &yt in y {
+        let f = pm[0];
+        if !(f.is_finite() && f > 1e-12) {
+            return None;
+        }
+        let v = yt - mean - a[0];
+        sum_log_f += f.ln();
+        sum_v2f += v * v / f;
+
+        // update: a += P[:,0] v / F, P -= P[:,0] P[0,:] / F (P สมมาตร)
+        let col0: Vec<f64> = pm[..r].to_vec();
+        for i in 0..r {
+            a[i] += col0[i] * v / f;
+            for j in 0..r {
+                pm[i * r + j] -= col0[i] * col0[j] / f;
+            }
+        }
+
+        // predict: a = T a, P = T P T' + R R'
+        let a0 = a[0];
+        for i in 0..r {
+            let next = if i + 1 < r { a[i + 1] } else { 0.0 };
+            a[i] = if i < p { phi[i] * a0 } else { 0.0 } + next;
+        }
+        // tmp = T P
+        for i in 0..r {
+            for j in 0..r {
+                let next = if i + 1 < r { pm[(i + 1) * r + j] } else { 0.0 };
+                let ar = if i < p { phi[i] * pm[j] } else { 0.0 };
+                tmp[i * r + j] = ar + next;
+            }
+        }
+        // P = tmp T' + R R'
+        for i in 0..r {
+            for j in 0..r {
+                let next = if j + 1 < r { tmp[i * r + j + 1] } else { 0.0 };
+                let ar = if j < p { tmp[i * r] * phi[j] } else { 0.0 };
+                pm[i * r + j] = ar + next + rvec[i] * rvec[j];
+            }
+        }
+    }
+
+    let nf = n as f64;
+    let sigma2 = sum_v2f / nf;
+    if !(sigma2.is_finite() && sigma2 > 0.0) {
+        return None;
+    }
+    let loglik =
+        -0.5 * (nf * (2.0 * std::f64::consts::PI).ln() + nf + nf * sigma2.ln() + sum_log_f);
+    Some((loglik, sigma2))
+}
+
+fn neg_loglik_unconstrained(y: &[f64], u: &[f64], p: usize, q: usize) -> f64 {
+    let phi = unconstrained_to_ar(&u[1..1 + p]);
+    let theta = unconstrained_to_ma(&u[1 + p..1 + p + q]);
+    match arma_exact_loglik(y, u[0], &phi, &theta) {
+        Some((ll, _)) => -ll,
+        None => f64::INFINITY,
+    }
+}
+
+/// -loglik บนพารามิเตอร์ธรรมชาติ [c, φ, θ] (ใช้หา Hessian)
+fn neg_loglik_natural(y: &[f64], x: &[f64], p: usize, q: usize) -> f64 {
+    let phi = &x[1..1 + p];
+    let theta = &x[1 + p..1 + p + q];
+    let ar_sum: f64 = phi.iter().sum();
+    if (1.0 - ar_sum).abs() < 1e-12 || ar_to_pacf(phi).is_none() {
+        return f64::INFINITY;
+    }
+    let mean = x[0] / (1.0 - ar_sum);
+    match arma_exact_loglik(y, mean, phi, theta) {
+        Some((ll, _)) => -ll,
+        None => f64::INFINITY,
+    }
+}
+
+/// Exact MLE ของ ARMA(p, q) เริ่มจากค่า CSS แล้ว maximize likelihood ของ Kalman filter
+pub fn fit_arma_mle(series: &[f64], p: usize, q: usize) -> Option<ArmaMleFit> {
+    let n = series.len();
+    if n < 2 || n <= p + q + 2 {
+        return None;
+    }
+
+    let css = fit_arma_css(series, p, q);
+    let mean0 = series.iter().sum::<f64>() / n as f64;
+    let mut u0 = vec![mean0];
+    u0.extend(ar_to_unconstrained(&css.phi));
+    u0.extend(ma_to_unconstrained(&css.theta));
+
+    let obj = |u: &[f64]| neg_loglik_unconstrained(series, u, p, q);
+    // Nelder–Mead สองรอบ (restart) กันติด simplex ที่แบน
+    let u1 = nelder_mead_min(u0, obj, 1000, 1e-9);
+    let ubest = nelder_mead_min(u1, obj, 1000, 1e-10);
+
+    let mean = ubest[0];
+    let phi = unconstrained_to_ar(&ubest[1..1 + p]);
+    let theta = unconstrained_to_ma(&ubest[1 + p..1 + p + q]);
+    let (loglik, sigma2) = arma_exact_loglik(series, mean, &phi, &theta)?;
+    let c = mean * (1.0 - phi.iter().sum::<f64>());
+    let params = ArmaParams { c, phi, theta };
+    let state = ArmaState::from_series(series, &params);
+
+    let mut x = vec![params.c];
+    x.extend(&params.phi);
+    x.extend(&params.theta);
+    let hess = numerical_hessian(&|x: &[f64]| neg_loglik_natural(series, x, p, q), &x);
+    let std_errors = hessian_std_errors(&hess);
+    let t_stats = x.iter().zip(&std_errors).map(|(v, se)| v / se).collect();
+
+    let (aic, bic, aicc) = information_criteria(loglik, n, p + q + 2);
+    Some(ArmaMleFit {
+        model: ArmaModel {
+            p,
+            q,
+            params,
+            aic,
+            bic,
+            aicc,
+            n_obs: n,
+            n_params: p + q + 2,
+            sigma2,
+            state,
+        },
+        mean,
+        loglik,
+        std_errors,
+        t_stats,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::module::model::arma::simulate_arma;
+
+    #[test]
+    fn test_white_noise_loglik_matches_closed_form() {
+        let y = [0.3, -1.2, 0.8, 0.1, -0.4];
+        let (ll, s2) = arma_exact_loglik(&y, 0.0, &[], &[]).unwrap();
+        let n = y.len() as f64;
+        let s2_ref = y.iter().map(|v| v * v).sum::<f64>() / n;
+        let ll_ref = -0.5 * n * ((2.0 * std::f64::consts::PI).ln() + 1.0 + s2_ref.ln());
+        assert!((s2 - s2_ref).abs() < 1e-12);
+        assert!((ll - ll_ref).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_ar1_loglik_matches_exact_formula() {
+        // exact AR(1): ℓ = -n/2 ln 2πσ² + ½ ln(1-φ²) - [ (1-φ²) y₁² + Σ (y_t - φ y_{t-1})² ] / 2σ²
+        let y: [f64; 6] = [0.5, 0.1, -0.3, 0.9, 0.4, -0.2];
+        let phi = 0.6;
+        let (ll, s2) = arma_exact_loglik(&y, 0.0, &[phi], &[]).unwrap();
+        let n = y.len() as f64;
+        let mut ss = (1.0 - phi * phi) * y[0] * y[0];
+        for t in 1..y.len() {
+            ss += (y[t] - phi * y[t - 1]).powi(2);
+        }
+        assert!((s2 - ss / n).abs() < 1e-12);
+        let ll_ref = -0.5 * n * (2.0 * std::f64::consts::PI * s2).ln()
+            + 0.5 * (1.0 - phi * phi).ln()
+            - ss / (2.0 * s2);
+        assert!((ll - ll_ref).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_fit_arma_mle_recovers_parameters() {
+        let y = simulate_arma(2000, 0.1, &[0.5, -0.3], &[0.4], 11);
+        let fit = fit_arma_mle(&y, 2, 1).unwrap();
+        let par = &fit.model.params;
+        assert!((par.phi[0] - 0.5).abs() < 0.1, "phi = {:?}", par.phi);
+        assert!((par.phi[1] + 0.3).abs() < 0.1, "phi = {:?}", par.phi);
+        assert!((par.theta[0] - 0.4).abs() < 0.1, "theta = {:?}", par.theta);
+        assert!((fit.model.sigma2 - 1.0).abs() < 0.1);
+        assert!(fit.std_errors.iter().all(|se| se.is_finite() && *se > 0.0));
+        assert!(fit.model.aicc > fit.model.aic);
+    }
+
+    #[test]
+    fn test_css_and_mle_criteria_share_scale() {
+        // ARMA(0,0): CSS กับ exact MLE ได้ loglik เดียวกัน -> AIC / BIC ต้องตรงกัน
+        let y = simulate_arma(300, 0.2, &[], &[], 5);
+        let css = crate::module::model::arma::fit_arma_with_ic(&y, 0, 0).unwrap();
+        let mle = fit_arma_mle(&y, 0, 0).unwrap();
+        assert!(
+            (css.aic - mle.model.aic).abs() < 1e-3,
+            "{} vs {}",
+            css.aic,
+            mle.model.aic
+        );
+        assert!((css.bic - mle.model.bic).abs() < 1e-3);
+        assert!((css.aic - (-2.0 * mle.loglik + 4.0)).abs() < 1e-3);
+    }
+}
@@ -0,0 +1,915 @@
+pub mod armax;
+pub mod mle;
+pub mod rolling;
+pub mod seasonal;
+pub mod select;
+
+use std::collections::VecDeque;
+use std::path::PathBuf;
+// use std::time::Instant;
+
+use serde::{Deserialize, Serialize};
+
+use mle::{ArmaMleFit, fit_arma_mle};
+use rolling::RollingArmaForecaster;
+use select::{ArmaCriterion, AutoArmaConfig, select_arma};
+
+use crate::module::{
+    data::read_csv::read_close_series,
+    eval::{
+        DEFAULT_DEAD_BAND, TargetKind, ZeroRule,
+        confusion::{ConfusionMatrix, Direction},
+        evaluator::Evaluator,
+        probabilistic::{GaussianForecast, evaluate_probabilistic},
+        wilson_intervals,
+    },
+    model::{
+        diagnostics::DiagnosticsReport,
+        differencing::{differencing, invert_diff_1},
+        garch::{ArmaGarch, GarchKind, fit_garch},
+        pacf::{
+            acf_and_choose_q, choose_p_cutoff_first_drop, pacf_levinson, pacf_ols,
+            plot_acf_pacf_analysis,
+        },
+        store::non_finite,
+        streaming::Indicator,
+        util::{
+            optimize::{NelderMead, OptimResult, Optimizer},
+            unpack_params::{pack_params, unpack_params},
+        },
+    },
+    plot::plot_reliability::plot_reliability,
+    util::{
+        function::smooth_ma::smooth_graph,
+        math::{bootstrap::BootstrapConfig, distribution::two_sided_z},
+        stationarity::print_stationarity_checks,
+    },
+};
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct ArmaParams {
+    pub c: f64,
+    pub phi: Vec<f64>,
+    pub theta: Vec<f64>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ArmaModel {
+    pub p: usize,
+    pub q: usize,
+    pub params: ArmaParams,
+    /// -2·loglik + 2k (k นับ σ² ด้วย) ทั้ง CSS และ exact MLE ; AICc เป็น ∞ เมื่อ n <= k + 1
+    #[serde(with = "non_finite")]
+    pub aic: f64,
+    #[serde(with = "non_finite")]
+    pub bic: f64,
+    #[serde(with = "non_finite")]
+    pub aicc: f64,
+    /// จำนวนจุดที่ใช้ฟิตจริง (series หลัง differencing)
+    pub n_obs: usize,
+    /// k ที่ใช้ใน AIC / BIC / AICc
+    pub n_params: usize,
+    /// ความแปรปรวนของ residual (SSE / n) ใช้เป็น variance ของการพยากรณ์ 1 ก้าว
+    #[serde(with = "non_finite")]
+    pub sigma2: f64,
+    /// lag ของ series ที่ใช้ฟิต สำหรับ forecast(h)
+    #[serde(default)]
+    pub state: ArmaState,
+}
+
+/// y ล่าสุด p ตัวและ residual ล่าสุด q ตัวของ series ที่ใช้ฟิต (ใหม่สุดอยู่ท้าย)
+/// ว่างถ้า model ไม่ได้ฟิตจาก series ตรง ๆ (เช่น ARMA ส่วนในของ ARMAX)
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ArmaState {
+    pub y: Vec<f64>,
+    pub e: Vec<f64>,
+}
+
+impl ArmaState {
+    pub fn from_series(y: &[f64], par: &ArmaParams) -> Self {
+        let e = arma_residuals(y, par);
+        Self {
+            y: y[y.len().saturating_sub(par.phi.len())..].to_vec(),
+            e: e[e.len().saturating_sub(par.theta.len())..].to_vec(),
+        }
+    }
+
+    /// ค่าเฉลี่ยพยากรณ์ h ก้าว: เหมือน arma_forecast_mean แต่ใช้เฉพาะ lag ที่เก็บไว้
+    fn forecast_mean(&self, par: &ArmaParams, h: usize) -> Vec<f64> {
+        let mut ext = self.y.clone();
+        let m = ext.len();
+        for k in 0..h {
+            let mut yhat = par.c;
+            for (i, phi) in par.phi.iter().enumerate() {
+                if ext.len() > i {
+                    yhat += phi * ext[ext.len() - 1 - i];
+                }
+            }
+            // residual ในอนาคตเป็นศูนย์ -> ใช้ได้เฉพาะ lag j >= k
+            for (j, theta) in par.theta.iter().enumerate() {
+                if j >= k && j - k < self.e.len() {
+                    yhat += theta * self.e[self.e.len() - 1 - (j - k)];
+                }
+            }
+            ext.push(yhat);
+        }
+        ext.split_off(m)
+    }
+}
+
+/// ค่าพยากรณ์ h ก้าวพร้อมช่วงพยากรณ์ 80% / 95% (Gaussian)
+#[derive(Clone, Copy, Debug)]
+pub struct ForecastStep {
+    pub mean: f64,
+    pub se: f64,
+    pub lo80: f64,
+    pub hi80: f64,
+    pub lo95: f64,
+    pub hi95: f64,
+}
+
+impl ForecastStep {
+    pub fn new(mean: f64, se: f64) -> Self {
+        let z80 = two_sided_z(0.80);
+        let z95 = two_sided_z(0.95);
+        Self {
+            mean,
+            se,
+            lo80: mean - z80 * se,
+            hi80: mean + z80 * se,
+            lo95: mean - z95 * se,
+            hi95: mean + z95 * se,
+        }
+    }
+}
+
+/// ψ-weights ของ MA(∞): ψ_0 = 1, ψ_j = θ_j + Σ_{i=1..min(j,p)} φ_i ψ_{j-i}
+pub fn psi_weights(phi: &[f64], theta: &[f64], h: usize) -> Vec<f64> {
+    let mut psi = vec![0.0; h.max(1)];
+    psi[0] = 1.0;
+    for j in 1..h {
+        let mut v = theta.get(j - 1).copied().unwrap_or(0.0);
+        for i in 1..=phi.len().min(j) {
+            v += phi[i - 1] * psi[j - i];
+        }
+        psi[j] = v;
+    }
+    psi.truncate(h);
+    psi
+}
+
+/// se ของการพยากรณ์ k ก้าว: σ √(Σ_{j<k} ψ_j²)
+pub fn forecast_std_errors(psi: &[f64], sigma2: f64) -> Vec<f64> {
+    let mut acc = 0.0;
+    psi.iter()
+        .map(|w| {
+            acc += w * w;
+            (sigma2.max(0.0) * acc).sqrt()
+        })
+        .collect()
+}
+
+impl ArmaModel {
+    /// พยากรณ์ h ก้าวถัดจากปลาย series ที่ใช้ฟิต (จาก state) พร้อมช่วง 80/95%
+    pub fn forecast(&self, h: usize) -> Vec<ForecastStep> {
+        self.with_intervals(self.state.forecast_mean(&self.params, h))
+    }
+
+    /// พยากรณ์ h ก้าวถัดจากปลาย y ใด ๆ (เช่นข้อมูลใหม่หลังฟิต) พร้อมช่วง 80/95%
+    pub fn forecast_from(&self, y: &[f64], h: usize) -> Vec<ForecastStep> {
+        self.with_intervals(arma_forecast_mean(y, &self.params, h))
+    }
+
+    fn with_intervals(&self, mean: Vec<f64>) -> Vec<ForecastStep> {
+        let psi = psi_weights(&self.params.phi, &self.params.theta, mean.len());
+        mean.iter()
+            .zip(forecast_std_errors(&psi, self.sigma2))
+            .map(|(m, se)| ForecastStep::new(*m, se))
+            .collect()
+    }
+}
+
+// function calculate arma residuals
+pub(crate) fn arma_residuals(y: &[f64], par: &ArmaParams) -> Vec<f64> {
+    let n = y.len();
+    let p = par.phi.len();
+    let q = par.theta.len();
+    let mut e = vec![0.0; n];
+    for t in 0..n {
+        let mut yhat = par.c;
+        // AR part: φ_i · y_{t-i}
+        for i in 1..=p {
+            if t >= i {
+                yhat += par.phi[i - 1] * y[t - i];
+            }
+        }
+
+        // MA part: θ_j · ε_{t-j}
+        for j in 1..=q {
+            if t >= j {
+                yhat += par.theta[j - 1] * e[t - j];
+            }
+        }
+        e[t] = y[t] - yhat; // residual
+    }
+    e
+}
+
+fn css_sse(y: &[f64], u: &[f64], p: usize, q: usize) -> f64 {
+    let par = unpack_params(u, p, q);
+    arma_residuals(y, &par).iter().map(|v| v * v).sum()
+}
+
+/// Nelder–Mead แบบเดิม (คืนเฉพาะจุดต่ำสุด) ; ใช้ util::optimize โดยตรงถ้าต้องการสถานะการลู่เข้า
+pub(crate) fn nelder_mead_min<F>(x0: Vec<f64>, f: F, max_iter: usize, tol: f64) -> Vec<f64>
+where
+    F: Fn(&[f64]) -> f64,
+{
+    NelderMead::new(max_iter, tol).minimize(&f, &x0).x
+}
+
+pub(crate) fn fit_arma_css(y: &[f64], p: usize, q: usize) -> ArmaParams {
+    fit_arma_css_with(y, p, q, true)
+}
+
+/// include_const = false ตรึง c = 0 (เช่น ARIMA ที่ d >= 1 แต่ไม่มี drift)
+pub(crate) fn fit_arma_css_with(y: &[f64], p: usize, q: usize, include_const: bool) -> ArmaParams {
+    if !include_const {
+        let u = nelder_mead_min(
+            vec![0.0; p + q],
+            |u| {
+                let mut full = vec![0.0];
+                full.extend_from_slice(u);
+                css_sse(y, &full, p, q)
+            },
+            800,
+            1e-7,
+        );
+        let mut full = vec![0.0];
+        full.extend(u);
+        return unpack_params(&full, p, q);
+    }
+    let dim = 1 + p + q;
+    let mut x0 = vec![0.0; dim];
+    let mu = if y.is_empty() {
+        0.0
+    } else {
+        y.iter().sum::<f64>() / (y.len() as f64)
+    };
+    x0[0] = mu;
+    let ubest = nelder_mead_min(x0, |u| css_sse(y, u, p, q), 800, 1e-7);
+    unpack_params(&ubest, p, q)
+}
+
+/// CSS (มีค่าคงที่) ด้วย optimizer ที่เลือกเอง คืนสถานะการลู่เข้ามาด้วย
+pub fn fit_arma_css_using(
+    y: &[f64],
+    p: usize,
+    q: usize,
+    optimizer: &dyn Optimizer,
+) -> (ArmaParams, OptimResult) {
+    let mut x0 = vec![0.0; 1 + p + q];
+    if !y.is_empty() {
+        x0[0] = y.iter().sum::<f64>() / y.len() as f64;
+    }
+    let res = optimizer.minimize(&|u| css_sse(y, u, p, q), &x0);
+    (unpack_params(&res.x, p, q), res)
+}
+
+/// CSS เริ่มจากพารามิเตอร์เดิม (warm start) ใช้ตอน refit บนหน้าต่างที่เลื่อนไป
+pub(crate) fn fit_arma_css_from(y: &[f64], init: &ArmaParams, max_iter: usize) -> ArmaParams {
+    let (p, q) = (init.phi.len(), init.theta.len());
+    let ubest = nelder_mead_min(pack_params(init), |u| css_sse(y, u, p, q), max_iter, 1e-7);
+    unpack_params(&ubest, p, q)
+}
+
+/// Gaussian log-likelihood แบบ conditional ที่ σ² = SSE / n (ค่าสูงสุดของ CSS)
+pub(crate) fn css_loglik(n: usize, sse: f64) -> f64 {
+    let nf = n as f64;
+    -0.5 * nf * ((2.0 * std::f64::consts::PI * sse / nf).ln() + 1.0)
+}
+
+/// (AIC, BIC, AICc) แบบ -2·loglik ; k นับ σ² ด้วย
+/// ใช้ทั้ง CSS และ exact MLE เพื่อให้ ArmaModel.aic / bic เทียบข้ามวิธี fit ได้
+pub(crate) fn information_criteria(loglik: f64, n: usize, k: usize) -> (f64, f64, f64) {
+    let nf = n as f64;
+    let kf = k as f64;
+    let aic = -2.0 * loglik + 2.0 * kf;
+    let bic = -2.0 * loglik + kf * nf.ln();
+    let aicc = if n > k + 1 {
+        aic + 2.0 * kf * (kf + 1.0) / (nf - kf - 1.0)
+    } else {
+        f64::INFINITY
+    };
+    (aic, bic, aicc)
+}
+
+/// (AIC, BIC, AICc) จาก SSE ของ CSS ; k = จำนวนพารามิเตอร์ของ mean, σ² นับเพิ่มให้
+pub(crate) fn css_information_criteria(n: usize, sse: f64, k: usize) -> (f64, f64, f64) {
+    if n == 0 || sse <= 0.0 {
+        return (f64::INFINITY, f64::INFINITY, f64::INFINITY);
+    }
+    information_criteria(css_loglik(n, sse), n, k + 1)
+}
+
+/// Calculate AIC (Akaike Information Criterion)
+/// k = จำนวนพารามิเตอร์ของ mean (c, φ, θ, ...) ; σ² ถูกนับเพิ่มให้เหมือน exact MLE
+pub(crate) fn calculate_aic(n: usize, sse: f64, k: usize) -> f64 {
+    css_information_criteria(n, sse, k).0
+}
+
+/// Calculate BIC (Bayesian Information Criterion)
+pub(crate) fn calculate_bic(n: usize, sse: f64, k: usize) -> f64 {
+    css_information_criteria(n, sse, k).1
+}
+
+/// Fit ARMA model and calculate AIC/BIC
+pub fn fit_arma_with_ic(series: &[f64], p: usize, q: usize) -> Option<ArmaModel> {
+    fit_arma_with_ic_const(series, p, q, true)
+}
+
+pub fn fit_arma_with_ic_const(
+    series: &[f64],
+    p: usize,
+    q: usize,
+    include_const: bool,
+) -> Option<ArmaModel> {
+    if series.len() < 2 || series.len() <= p + q {
+        return None;
+    }
+
+    let params = fit_arma_css_with(series, p, q, include_const);
+    let state = ArmaState::from_series(series, &params);
+    let residuals = arma_residuals(series, &params);
+    let sse: f64 = residuals.iter().map(|e| e * e).sum();
+
+    let n = series.len();
+    let k = usize::from(include_const) + p + q; // number of parameters (c + phi + theta)
+
+    let (aic, bic, aicc) = css_information_criteria(n, sse, k);
+
+    Some(ArmaModel {
+        p,
+        q,
+        params,
+        aic,
+        bic,
+        aicc,
+        n_obs: n,
+        n_params: k + 1,
+        sigma2: sse / n as f64,
+        state,
+    })
+}
+
+fn arma_predict_rolling(y: &[f64], par: &ArmaParams) -> Vec<f64> {
+    if y.len() <= 1 {
+        return Vec::new();
+    }
+    let mut stream = ArmaOneStep::new(par.clone());
+    y[..y.len() - 1]
+        .iter()
+        .map(|&v| stream.update(v).unwrap_or(f64::NAN))
+        .collect()
+}
+
+/// พยากรณ์ 1 ก้าวแบบ streaming: update(y_t) -> ŷ_{t+1} ด้วยพารามิเตอร์คงที่
+/// ε_t = y_t - ŷ_t เหมือน arma_residuals (ŷ_0 = c)
+#[derive(Clone, Debug)]
+pub struct ArmaOneStep {
+    params: ArmaParams,
+    /// y_t, y_{t-1}, ... (ตัวแรกคือล่าสุด)
+    y_lags: VecDeque<f64>,
+    e_lags: VecDeque<f64>,
+    forecast: f64,
+}
+
+impl ArmaOneStep {
+    pub fn new(params: ArmaParams) -> Self {
+        Self {
+            y_lags: VecDeque::with_capacity(params.phi.len() + 1),
+            e_lags: VecDeque::with_capacity(params.theta.len() + 1),
+            forecast: params.c,
+            params,
+        }
+    }
+
+    /// ŷ ของแท่งถัดไป
+    pub fn forecast(&self) -> f64 {
+        self.forecast
+    }
+
+    /// residual ล่าสุด
+    pub fn last_residual(&self) -> Option<f64> {
+        self.e_lags.front().copied()
+    }
+}
+
+impl Indicator for ArmaOneStep {
+    type Input = f64;
+
+    fn update(&mut self, y: f64) -> Option<f64> {
+        let e = y - self.forecast;
+        self.y_lags.push_front(y);
+        self.y_lags.truncate(self.params.phi.len());
+        self.e_lags.push_front(e);
+        self.e_lags.truncate(self.params.theta.len());
+
+        let mut yhat = self.params.c;
+        for (phi, v) in self.params.phi.iter().zip(&self.y_lags) {
+            yhat += phi * v;
+        }
+        for (theta, e) in self.params.theta.iter().zip(&self.e_lags) {
+            yhat += theta * e;
+        }
+        self.forecast = yhat;
+        Some(yhat)
+    }
+
+    fn reset(&mut self) {
+        *self = Self::new(std::mem::take(&mut self.params));
+    }
+}
+
+/// พยากรณ์ h ก้าวถัดจากปลาย y แบบ recursive (ε ในอนาคต = 0)
+pub fn arma_forecast_mean(y: &[f64], par: &ArmaParams, h: usize) -> Vec<f64> {
+    let n = y.len();
+    let e = arma_residuals(y, par);
+    let mut ext = y.to_vec();
+    for k in 0..h {
+        let t = n + k;
+        let mut yhat = par.c;
+        for (i, phi) in par.phi.iter().enumerate() {
+            if t > i {
+                yhat += phi * ext[t - 1 - i];
+            }
+        }
+        for (j, theta) in par.theta.iter().enumerate() {
+            // มีแค่ residual ในอดีตเท่านั้นที่ไม่เป็นศูนย์
+            if t > j && t - 1 - j < n {
+                yhat += theta * e[t - 1 - j];
+            }
+        }
+        ext.push(yhat);
+    }
+    ext.split_off(n)
+}
+
+/// การแจกแจงพยากรณ์ 1 ก้าว N(ŷ_{t+1}, σ²) โดย σ² มาจาก SSE ของ residual
+pub fn arma_predict_rolling_dist(
+    y: &[f64],
+    par: &ArmaParams,
+    sigma2: f64,
+) -> Vec<GaussianForecast> {
+    let sd = sigma2.max(0.0).sqrt();
+    arma_predict_rolling(y, par)
+        .into_iter()
+        .map(|mean| GaussianForecast { mean, sd })
+        .collect()
+}
+
+#[inline]
+fn values_only(ts: &[(i64, f64)]) -> Vec<f64> {
+    ts.iter().map(|(_, v)| *v).collect()
+}
+
+/// Auto ARMA: grid search (p, q) ด้วย BIC บน series ที่ stationary แล้ว (ถ้าต้องหา d ด้วยใช้ arima::auto_arima)
+/// ใช้ select::select_arma โดยตรงถ้าต้องการ criterion อื่น, stepwise หรือตารางอันดับทั้งหมด
+pub fn auto_arma(series: &[f64], max_p: usize, max_q: usize) -> Option<ArmaModel> {
+    if series.len() < 2 {
+        eprintln!("Series too short for ARMA search");
+        return None;
+    }
+
+    println!("\n=== Auto ARMA Grid Search ===");
+    println!("Searching p=[0..{}], q=[0..{}]", max_p, max_q);
+
+    let config = AutoArmaConfig::new(max_p, max_q)
+        .with_criterion(ArmaCriterion::Bic)
+        .with_stepwise(false);
+    let best_model = select_arma(series, &config)?
+        .ranked
+        .into_iter()
+        .next()?
+        .model;
+
+    println!(
+        "\n🏆 Best ARMA({},{}) - AIC={:.4}, BIC={:.4}",
+        best_model.p, best_model.q, best_model.aic, best_model.bic
+    );
+    println!(
+        "    c={:.6}, phi={:?}, theta={:?}",
+        best_model.params.c, best_model.params.phi, best_model.params.theta
+    );
+
+    Some(best_model)
+}
+
+pub fn arma_model() {
+    // 1. มีข้อมูลแบบ time series
+    // ref: https://medium.com/@lengyi/arima-model-%E0%B8%95%E0%B8%AD%E0%B8%99%E0%B8%97%E0%B8%B5%E0%B9%88-1-%E0%B9%80%E0%B8%82%E0%B9%89%E0%B8%B2%E0%B9%83%E0%B8%88-arima-%E0%B9%81%E0%B8%9A%E0%B8%9A-practical-6d66a36f4e82?source=post_page-----d0d2bc916c68---------------------------------------
+    let data_path = PathBuf::from("data/SPX_log.csv");
+
+    let diff = differencing(data_path.clone());
+
+    // smooth graph using ema
+    let period: usize = 1;
+    let diff_smooth = smooth_graph(&diff, period);
+
+    // 2. หา integradted (d) และความเป็น stationnary alaysis
+    // https://lengyi.medium.com/arima-model-%E0%B8%95%E0%B8%AD%E0%B8%99%E0%B8%97%E0%B8%B5%E0%B9%88-2-%E0%B8%AB%E0%B8%B2-integrated-d-%E0%B9%81%E0%B8%A5%E0%B8%B0-stationary-analysis-38df96394207
+
+    print_stationarity_checks(&diff);
+
+    // summary ค่า diffence เป็น stationary อยู่แล้ว -> เลือกใช้ ARMA (ตรึง d = 0)
+
+    // 3. หา AR (p) ด้วย Partial Autocorrelation Function และ MA (q) ด้วย Autocorrelation Function
+    // ref: https://lengyi.medium.com/arima-model-%E0%B8%95%E0%B8%AD%E0%B8%99%E0%B8%97%E0%B8%B5%E0%B9%88-3-%E0%B8%AB%E0%B8%B2-ar-p-%E0%B8%94%E0%B9%89%E0%B8%A7%E0%B8%A2-partial-autocorrelation-function-5f5afb359683
+    // ref: https://lengyi.medium.com/arima-model-%E0%B8%95%E0%B8%AD%E0%B8%99%E0%B8%97%E0%B8%B5%E0%B9%88-4-%E0%B8%AB%E0%B8%B2-ma-q-%E0%B8%94%E0%B9%89%E0%B8%A7%E0%B8%A2-autocorrelation-function-ac3a07d12a57
+    let lag = 24;
+
+    // Plot ACF และ PACF พร้อมวิเคราะห์หาค่า p และ q
+    let (p, q) = match plot_acf_pacf_analysis(
+        &diff_smooth,
+        lag,
+        "output/acf_plot.png",
+        "output/pacf_plot.png",
+    ) {
+        Ok((p_val, q_val)) => (p_val, q_val),
+        Err(e) => {
+            eprintln!("Error plotting ACF/PACF: {}", e);
+            // Fallback to manual calculation
+            let pacf_ld = pacf_levinson(&diff_smooth, lag, true);
+            let pacf_ols = pacf_ols(&diff_smooth, lag, true, false);
+            let p_ld = choose_p_cutoff_first_drop(&pacf_ld, diff.len());
+            let p_ols = choose_p_cutoff_first_drop(&pacf_ols, diff.len());
+            let p = p_ld.max(p_ols);
+            let q = acf_and_choose_q(&diff_smooth, lag);
+            (p, q)
+        }
+    };
+
+    if diff.len() < 2 {
+        eprintln!("need at least 2 differenced points for ARMA evaluation");
+        return;
+    }
+
+    let Some(model) = fit_arma_with_ic(&diff_smooth, p, q) else {
+        eprintln!("not enough data to fit ARMA({},{})", p, q);
+        return;
+    };
+    let params = &model.params;
+    println!(
+        "Fitted ARMA({},{}): c={:.6}, phi={:?}, theta={:?}",
+        p, q, params.c, params.phi, params.theta
+    );
+    if let Some(mle) = fit_arma_mle(&diff_smooth, p, q) {
+        print_mle_table(&mle);
+    }
+
+    // volatility clustering ของ residual
+    let resid = arma_residuals(&diff_smooth, params);
+    let diag = DiagnosticsReport::new(&model, &diff_smooth, lag);
+    diag.print();
+    if let Err(e) = diag.plot(&resid, "output") {
+        eprintln!("Error plotting diagnostics: {}", e);
+    }
+    let mut garch11 = None;
+    for kind in [GarchKind::Garch, GarchKind::Gjr, GarchKind::Egarch] {
+        if let Some(g) = fit_garch(&resid, kind) {
+            let next_sd = g
+                .forecast_variance(1)
+                .first()
+                .map_or(f64::NAN, |v| v.sqrt());
+            println!(
+                "{:?}: ω={:.3e} α={:.4} β={:.4} γ={:.4} persistence={:.4} loglik={:.2} next σ={:.6}",
+                kind,
+                g.params.omega,
+                g.params.alpha,
+                g.params.beta,
+                g.params.gamma,
+                g.persistence(),
+                g.loglik,
+                next_sd
+            );
+            if kind == GarchKind::Garch {
+                garch11 = Some(g);
+            }
+        }
+    }
+    // ช่วงพยากรณ์ ARMA + GARCH(1,1): กว้างกว่า σ² คงที่เมื่อ h ล่าสุดสูงกว่าระดับยาว
+    if let Some(garch) = garch11 {
+        let arma_garch = ArmaGarch {
+            arma: model.clone(),
+            garch,
+        };
+        for (h, (f, c)) in arma_garch
+            .forecast(5)
+            .iter()
+            .zip(model.forecast(5))
+            .enumerate()
+        {
+            println!(
+                "  ARMA-GARCH h={}: mean={:.6} se={:.6} 95% [{:.6}, {:.6}] (constant σ se={:.6})",
+                h + 1,
+                f.mean,
+                f.se,
+                f.lo95,
+                f.hi95,
+                c.se
+            );
+        }
+    }
+
+    let pred_next_diff = arma_predict_rolling(&diff_smooth, params);
+    if pred_next_diff.len() + 1 != diff.len() {
+        eprintln!("prediction series shorter than needed; skip evaluation");
+        return;
+    }
+
+    let levels_pairs = read_close_series(&data_path).expect("read close");
+    let levels = values_only(&levels_pairs);
+    if levels.len() < 3 {
+        eprintln!("need at least 3 data points for directional evaluation");
+        return;
+    }
+
+    let levels_for_eval = &levels[1..];
+    let _pred_next_level = invert_diff_1(levels_for_eval, &pred_next_diff);
+
+    // walk-forward: refit ทุก 21 แท่งบนหน้าต่าง 252 แท่ง ใช้เฉพาะข้อมูลถึง t (ไม่มี look-ahead)
+    // ตัวเลขหลักทั้งหมดมาจากชุดนี้ ; การฟิตครั้งเดียวทั้งก้อนแล้ววัดบนข้อมูลเดิมเป็นแค่ค่าอ้างอิง in-sample
+    let window = 252;
+    let refit_every = 21;
+    let mut rolling = RollingArmaForecaster::new(p, q, refit_every);
+    let oos_dist: Vec<Option<GaussianForecast>> = (0..pred_next_diff.len())
+        .map(|i| (i + 1 >= window).then(|| rolling.forecast(&diff_smooth[i + 1 - window..=i])))
+        .collect();
+    let oos_mean: Vec<f64> = oos_dist
+        .iter()
+        .map(|g| g.map_or(f64::NAN, |g| g.mean))
+        .collect();
+
+    let evaluator = Evaluator::new(TargetKind::Diff, ZeroRule::Ignore);
+    let evaluation = evaluator.evaluate_forecasts(levels_for_eval, &oos_mean);
+    let (rep, metrics) = (&evaluation.report, &evaluation.metrics);
+    let boot = BootstrapConfig::new(evaluation.outcomes.len());
+    let wilson = wilson_intervals(rep, boot.level);
+    let ci = evaluator.bootstrap_intervals(&evaluation.outcomes, &boot);
+    println!(
+        "Directional Accuracy (walk-forward ARMA({},{}) window={} refit={}, fits={}) = {:.2}% {} (hits={} / total={})",
+        p,
+        q,
+        window,
+        refit_every,
+        rolling.n_fits(),
+        metrics.accuracy * 100.0,
+        wilson.accuracy.to_percent_string(),
+        rep.hits,
+        rep.total,
+    );
+    println!(
+        "  bootstrap: acc={} prec={} recall={} f1={}",
+        ci.accuracy.to_percent_string(),
+        ci.precision.to_percent_string(),
+        ci.recall.to_percent_string(),
+        ci.f1.to_percent_string(),
+    );
+
+    // แบบ 3 คลาส: |return| < 0.1σ ถือว่าทรงตัว ทั้งฝั่งจริงและฝั่งพยากรณ์
+    // band คิดจาก Δ / level จึงต้องกลับเป็นราคาก่อน (Δ ของ log-close หารด้วย log-close ไม่ใช่ผลตอบแทน)
+    let price: Vec<f64> = levels_for_eval.iter().map(|x| x.exp()).collect();
+    let pred_price: Vec<f64> = levels_for_eval
+        .iter()
+        .zip(&oos_mean)
+        .map(|(l, m)| (l + m).exp())
+        .collect();
+    let banded = Evaluator::new(TargetKind::Level, ZeroRule::Ignore)
+        .with_dead_band(DEFAULT_DEAD_BAND, DEFAULT_DEAD_BAND)
+        .evaluate_forecasts(&price, &pred_price);
+    print_confusion(&banded.confusion);
+    let mc = banded.confusion.metrics();
+    println!(
+        "  dead-band 0.1σ: acc={:.2}% macro_f1={:.2}% micro p/r/f1={:.2}%/{:.2}%/{:.2}% kappa={:.4} mcc={:.4} (binary acc={:.2}%)",
+        mc.accuracy * 100.0,
+        mc.macro_f1 * 100.0,
+        mc.micro_precision * 100.0,
+        mc.micro_recall * 100.0,
+        mc.micro_f1 * 100.0,
+        mc.kappa,
+        mc.mcc,
+        banded.metrics.accuracy * 100.0
+    );
+
+    // ความน่าจะเป็นที่จะขึ้นจาก N(ŷ, σ²) ของแต่ละรอบ refit แทนการดูแค่ sign
+    let prob = evaluate_probabilistic(levels_for_eval, &oos_dist, TargetKind::Diff, 10);
+    println!(
+        "  probabilistic: brier={:.5} log_loss={:.5} crps={:.6} n={}",
+        prob.brier, prob.log_loss, prob.crps, prob.n
+    );
+    if let Err(e) = plot_reliability(&prob.reliability, "output/reliability.png") {
+        eprintln!("Error plotting reliability diagram: {}", e);
+    }
+
+    // อ้างอิง: ฟิตครั้งเดียวบนทั้ง sample แล้ววัดบนข้อมูลเดิม (มองโลกในแง่ดีเกินจริง)
+    let in_sample = evaluator.evaluate_forecasts(levels_for_eval, &pred_next_diff);
+    let in_sample_dist: Vec<Option<GaussianForecast>> =
+        arma_predict_rolling_dist(&diff_smooth, params, model.sigma2)
+            .into_iter()
+            .map(Some)
+            .collect();
+    let in_sample_prob =
+        evaluate_probabilistic(levels_for_eval, &in_sample_dist, TargetKind::Diff, 10);
+    println!(
+        "  in-sample (single fit, σ={:.6}): acc={:.2}% (hits={} / total={}) brier={:.5} log_loss={:.5}",
+        model.sigma2.sqrt(),
+        in_sample.metrics.accuracy * 100.0,
+        in_sample.report.hits,
+        in_sample.report.total,
+        in_sample_prob.brier,
+        in_sample_prob.log_loss
+    );
+    // println!(
+    //     "Breakdown: up&up={}  down&down={}  up&down={}  down&up={}",
+    //     rep.up_up, rep.down_down, rep.up_down, rep.down_up
+    // );
+    // println!(
+    //     "Directional Precision = {:.2}%  Recall = {:.2}%",
+    //     metrics.precision * 100.0,
+    //     metrics.recall * 100.0
+    // );
+
+    // println!(
+    //     "Sample predicted levels (first 5) = {:?}",
+    //     &pred_next_level[..pred_next_level.len().min(5)]
+    // );
+}
+
+fn print_mle_table(fit: &ArmaMleFit) {
+    println!(
+        "Exact MLE ARMA({},{}): loglik={:.3} aic={:.3} aicc={:.3} bic={:.3} σ²={:.6e}",
+        fit.model.p,
+        fit.model.q,
+        fit.loglik,
+        fit.model.aic,
+        fit.model.aicc,
+        fit.model.bic,
+        fit.model.sigma2
+    );
+    println!(
+        "  {:>6} {:>12} {:>12} {:>8}",
+        "param", "coef", "std_err", "t"
+    );
+    for (((name, v), se), t) in fit
+        .param_names()
+        .iter()
+        .zip(fit.param_values())
+        .zip(&fit.std_errors)
+        .zip(&fit.t_stats)
+    {
+        println!("  {name:>6} {v:>12.6} {se:>12.6} {t:>8.3}");
+    }
+}
+
+fn print_confusion(m: &ConfusionMatrix) {
+    println!("  pred \\ actual   down   flat     up");
+    for (name, p) in [
+        ("down", Direction::Down),
+        ("flat", Direction::Flat),
+        ("up", Direction::Up),
+    ] {
+        println!(
+            "  {name:>13} {:>6} {:>6} {:>6}",
+            m.get(p, Direction::Down),
+            m.get(p, Direction::Flat),
+            m.get(p, Direction::Up)
+        );
+    }
+}
+
+/// ARMA(p, q) จาก shock N(0, 1) ทิ้ง 200 แท่งแรกให้หลุดจากค่าเริ่มต้น ; ใช้ร่วมกันใน test
+#[cfg(test)]
+pub(crate) fn simulate_arma(n: usize, c: f64, phi: &[f64], theta: &[f64], seed: u64) -> Vec<f64> {
+    let mut rng = crate::module::util::math::rng::SplitMix64::new(seed);
+    let burn = 200;
+    let mut y = vec![0.0; n + burn];
+    let mut e = vec![0.0; n + burn];
+    for t in 0..n + burn {
+        e[t] = rng.next_normal();
+        let mut v = c + e[t];
+        for (i, a) in phi.iter().enumerate() {
+            if t > i {
+                v += a * y[t - 1 - i];
+            }
+        }
+        for (j, b) in theta.iter().enumerate() {
+            if t > j {
+                v += b * e[t - 1 - j];
+            }
+        }
+        y[t] = v;
+    }
+    y[burn..].to_vec()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_psi_weights_ar1_and_ma1() {
+        let psi = psi_weights(&[0.5], &[], 5);
+        for (j, w) in psi.iter().enumerate() {
+            assert!((w - 0.5f64.powi(j as i32)).abs() < 1e-12);
+        }
+        assert_eq!(psi_weights(&[], &[0.4], 4), vec![1.0, 0.4, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_forecast_from_state_matches_series() {
+        let y = simulate_arma(400, 0.05, &[0.6], &[0.3], 9);
+        let model = fit_arma_with_ic(&y, 2, 2).unwrap();
+        assert_eq!(model.state.y, y[398..].to_vec());
+        assert_eq!(model.state.e.len(), 2);
+        let a = model.forecast(12);
+        let b = model.forecast_from(&y, 12);
+        for (x, z) in a.iter().zip(&b) {
+            assert!((x.mean - z.mean).abs() < 1e-12);
+            assert_eq!(x.se, z.se);
+        }
+    }
+
+    #[test]
+    fn test_forecast_converges_to_mean_with_widening_bands() {
+        let model = ArmaModel {
+            p: 1,
+            q: 0,
+            params: ArmaParams {
+                c: 1.0,
+                phi: vec![0.5],
+                theta: vec![],
+            },
+            aic: 0.0,
+            bic: 0.0,
+            aicc: 0.0,
+            n_obs: 0,
+            n_params: 2,
+            sigma2: 1.0,
+            state: ArmaState::default(),
+        };
+        let f = model.forecast_from(&[0.0, 4.0], 40);
+        // ŷ_{n+1} = 1 + 0.5·4 = 3, ค่าเฉลี่ยระยะยาว = c / (1 - φ) = 2
+        assert!((f[0].mean - 3.0).abs() < 1e-12);
+        assert!((f[0].se - 1.0).abs() < 1e-12);
+        assert!((f[39].mean - 2.0).abs() < 1e-9);
+        // variance ระยะยาว σ² / (1 - φ²)
+        assert!((f[39].se - (1.0f64 / 0.75).sqrt()).abs() < 1e-9);
+        assert!(
+            f.windows(2)
+                .all(|w| w[1].hi95 - w[1].lo95 >= w[0].hi95 - w[0].lo95)
+        );
+        assert!(f[0].lo95 < f[0].lo80 && f[0].hi80 < f[0].hi95);
+    }
+
+    #[test]
+    fn test_css_optimizers_agree() {
+        use crate::module::model::util::optimize::{Bfgs, LevenbergMarquardt};
+
+        let y = simulate_arma(600, 0.2, &[0.5], &[0.3], 3);
+        let sse = |par: &ArmaParams| -> f64 { arma_residuals(&y, par).iter().map(|e| e * e).sum() };
+        let (nm, nm_res) = fit_arma_css_using(&y, 1, 1, &NelderMead::default());
+        let (bfgs, bfgs_res) = fit_arma_css_using(&y, 1, 1, &Bfgs::default());
+        let (lm, lm_res) = fit_arma_css_using(&y, 1, 1, &LevenbergMarquardt::default());
+        assert!(
+            bfgs_res.converged() && lm_res.converged(),
+            "{bfgs_res:?} {lm_res:?}"
+        );
+        assert!(nm_res.iterations > 0 && nm_res.grad_norm.is_finite());
+        for par in [&bfgs, &lm] {
+            assert!((sse(par) - sse(&nm)).abs() < 1e-3 * sse(&nm));
+            assert!((par.phi[0] - nm.phi[0]).abs() < 1e-2);
+        }
+    }
+
+    #[test]
+    fn test_streaming_one_step_matches_batch_residuals() {
+        let y = [0.4, -0.1, 0.3, 0.8, -0.5, 0.2, 0.0, -0.3, 0.6];
+        let par = ArmaParams {
+            c: 0.05,
+            phi: vec![0.5, -0.2],
+            theta: vec![0.3, 0.1, -0.05],
+        };
+        let e = arma_residuals(&y, &par);
+        let mut stream = ArmaOneStep::new(par.clone());
+        let mut prev = stream.forecast();
+        for (t, &v) in y.iter().enumerate() {
+            let next = stream.update(v).unwrap();
+            assert_eq!(v - prev, e[t]);
+            assert_eq!(stream.last_residual(), Some(e[t]));
+            prev = next;
+        }
+        assert_eq!(arma_forecast_mean(&y, &par, 1)[0], prev);
+        stream.reset();
+        assert_eq!(stream.forecast(), par.c);
+    }
+}
@@ -0,0 +1,115 @@
+// ARMA แบบ walk-forward: ฟิตใหม่ทุก k แท่งบนหน้าต่างย้อนหลัง (ไม่มี look-ahead)
+// ระหว่างรอบ refit ใช้พารามิเตอร์เดิมพยากรณ์ต่อจากหน้าต่างปัจจุบัน
+
+use crate::module::{
+    eval::probabilistic::GaussianForecast,
+    model::arma::{
+        ArmaParams, arma_forecast_mean, arma_residuals, fit_arma_css, fit_arma_css_from,
+    },
+};
+
+/// จำนวน iteration ของ Nelder–Mead ตอน warm start (จุดเริ่มใกล้คำตอบแล้ว)
+const WARM_START_ITER: usize = 300;
+
+#[derive(Clone, Debug)]
+pub struct RollingArmaForecaster {
+    pub p: usize,
+    pub q: usize,
+    /// refit ทุก ๆ กี่ครั้งที่ถูกเรียก (1 = ทุกแท่ง)
+    pub refit_every: usize,
+    /// เริ่ม optimizer จากพารามิเตอร์ของรอบก่อน
+    pub warm_start: bool,
+    params: Option<ArmaParams>,
+    sigma2: f64,
+    since_fit: usize,
+    fits: usize,
+}
+
+impl RollingArmaForecaster {
+    pub fn new(p: usize, q: usize, refit_every: usize) -> Self {
+        Self {
+            p,
+            q,
+            refit_every: refit_every.max(1),
+            warm_start: true,
+            params: None,
+            sigma2: f64::NAN,
+            since_fit: 0,
+            fits: 0,
+        }
+    }
+
+    pub fn with_warm_start(mut self, warm_start: bool) -> Self {
+        self.warm_start = warm_start;
+        self
+    }
+
+    /// จำนวนครั้งที่ฟิตไปแล้ว
+    pub fn n_fits(&self) -> usize {
+        self.fits
+    }
+
+    pub fn params(&self) -> Option<&ArmaParams> {
+        self.params.as_ref()
+    }
+
+    fn refit(&mut self, window: &[f64]) {
+        let params = match (&self.params, self.warm_start) {
+            (Some(prev), true) => fit_arma_css_from(window, prev, WARM_START_ITER),
+            _ => fit_arma_css(window, self.p, self.q),
+        };
+        let sse: f64 = arma_residuals(window, &params).iter().map(|e| e * e).sum();
+        self.sigma2 = sse / window.len() as f64;
+        self.params = Some(params);
+        self.since_fit = 0;
+        self.fits += 1;
+    }
+
+    /// พยากรณ์ค่าถัดจากปลาย window ; window ต้องมีแค่ข้อมูลถึงเวลาปัจจุบัน
+    pub fn forecast(&mut self, window: &[f64]) -> GaussianForecast {
+        if window.len() <= 1 + self.p + self.q {
+            return GaussianForecast {
+                mean: 0.0,
+                sd: f64::NAN,
+            };
+        }
+        if self.params.is_none() || self.since_fit >= self.refit_every {
+            self.refit(window);
+        }
+        self.since_fit += 1;
+        let params = self.params.as_ref().expect("fitted above");
+        GaussianForecast {
+            mean: arma_forecast_mean(window, params, 1)[0],
+            sd: self.sigma2.sqrt(),
+        }
+    }
+
+    pub fn forecast_mean(&mut self, window: &[f64]) -> f64 {
+        self.forecast(window).mean
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_refits_every_k_calls_and_forecasts_ar1() {
+        let mut y = vec![1.0];
+        for t in 1..200 {
+            let shock = if t % 7 == 0 { 0.5 } else { -0.1 };
+            y.push(0.6 * y[t - 1] + shock);
+        }
+        let mut f = RollingArmaForecaster::new(1, 0, 10);
+        for t in 100..130 {
+            f.forecast(&y[t - 100..t]);
+        }
+        assert_eq!(f.n_fits(), 3);
+
+        let g = f.forecast(&y[100..200]);
+        assert_eq!(f.n_fits(), 4);
+        let par = f.params().unwrap();
+        assert!((g.mean - (par.c + par.phi[0] * y[199])).abs() < 1e-12);
+        assert!(g.sd.is_finite() && g.sd > 0.0);
+    }
+}
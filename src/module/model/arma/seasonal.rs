@@ -0,0 +1,311 @@
+// SARIMA(p, d, q)(P, D, Q)_s แบบ multiplicative
+//   φ(B) Φ(B^s) (1 - B)^d (1 - B^s)^D x_t = c + θ(B) Θ(B^s) ε_t
+// คูณพหุนามออกมาเป็น ARMA ธรรมดาแล้วใช้ CSS / forecast ของ ARMA ต่อได้เลย
+
+use crate::module::{
+    model::{
+        arma::{
+            ArmaParams, ForecastStep, arma_forecast_mean, arma_residuals, calculate_aic,
+            calculate_bic, forecast_std_errors, nelder_mead_min, psi_weights,
+        },
+        util::{
+            polynomial::{ar_poly, diff_poly, ma_poly, poly_mul, poly_to_ar, poly_to_ma},
+            unpack_params::{unconstrained_to_ar, unconstrained_to_ma},
+        },
+    },
+    util::{
+        function::fft_spectrum::{TRADING_DAYS_PER_YEAR, fft_spectrum},
+        stationarity::ndiffs,
+    },
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SeasonalOrder {
+    pub p: usize,
+    pub d: usize,
+    pub q: usize,
+    pub seasonal_p: usize,
+    pub seasonal_d: usize,
+    pub seasonal_q: usize,
+    /// คาบฤดูกาล s (จำนวนแท่ง)
+    pub period: usize,
+}
+
+#[derive(Clone, Debug)]
+pub struct SarimaParams {
+    pub c: f64,
+    pub phi: Vec<f64>,
+    pub theta: Vec<f64>,
+    pub seasonal_phi: Vec<f64>,
+    pub seasonal_theta: Vec<f64>,
+}
+
+impl SarimaParams {
+    /// กระจาย φ(B)Φ(B^s) และ θ(B)Θ(B^s) เป็น ARMA(p + sP, q + sQ)
+    pub fn expanded(&self, period: usize) -> ArmaParams {
+        let ar = poly_mul(&ar_poly(&self.phi, 1), &ar_poly(&self.seasonal_phi, period));
+        let ma = poly_mul(
+            &ma_poly(&self.theta, 1),
+            &ma_poly(&self.seasonal_theta, period),
+        );
+        ArmaParams {
+            c: self.c,
+            phi: poly_to_ar(&ar),
+            theta: poly_to_ma(&ma),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct SarimaModel {
+    pub order: SeasonalOrder,
+    pub params: SarimaParams,
+    pub sigma2: f64,
+    pub aic: f64,
+    pub bic: f64,
+    levels: Vec<f64>,
+}
+
+/// w_t = Σ δ_k x_{t-k} สำหรับ t ที่มีอดีตครบ
+fn apply_diff_poly(x: &[f64], delta: &[f64]) -> Vec<f64> {
+    let m = delta.len().saturating_sub(1);
+    (m..x.len())
+        .map(|t| delta.iter().enumerate().map(|(k, dk)| dk * x[t - k]).sum())
+        .collect()
+}
+
+fn unpack_seasonal(u: &[f64], order: &SeasonalOrder, include_const: bool) -> SarimaParams {
+    let (c, rest) = if include_const {
+        (u[0], &u[1..])
+    } else {
+        (0.0, u)
+    };
+    let SeasonalOrder {
+        p,
+        q,
+        seasonal_p: sp,
+        seasonal_q: sq,
+        ..
+    } = *order;
+    SarimaParams {
+        c,
+        phi: unconstrained_to_ar(&rest[..p]),
+        seasonal_phi: unconstrained_to_ar(&rest[p..p + sp]),
+        theta: unconstrained_to_ma(&rest[p + sp..p + sp + q]),
+        seasonal_theta: unconstrained_to_ma(&rest[p + sp + q..p + sp + q + sq]),
+    }
+}
+
+impl SarimaModel {
+    /// ฟิตด้วย CSS ; ค่าคงที่ใส่ได้เฉพาะเมื่อ d + D < 2 (เหมือน Arima)
+    pub fn fit(levels: &[f64], order: SeasonalOrder, include_const: bool) -> Option<Self> {
+        let s = order.period.max(1);
+        let include_const = include_const && order.d + order.seasonal_d < 2;
+        let w = apply_diff_poly(levels, &diff_poly(order.d, order.seasonal_d, s));
+        let max_lag = order.p + s * order.seasonal_p + order.q + s * order.seasonal_q;
+        if w.len() < 2 || w.len() <= max_lag {
+            return None;
+        }
+
+        let dim =
+            usize::from(include_const) + order.p + order.q + order.seasonal_p + order.seasonal_q;
+        let mut x0 = vec![0.0; dim];
+        if include_const {
+            x0[0] = w.iter().sum::<f64>() / w.len() as f64;
+        }
+        let sse = |u: &[f64]| -> f64 {
+            let par = unpack_seasonal(u, &order, include_const).expanded(s);
+            arma_residuals(&w, &par).iter().map(|e| e * e).sum()
+        };
+        let u = nelder_mead_min(x0, sse, 1000, 1e-8);
+        let total = sse(&u);
+        let params = unpack_seasonal(&u, &order, include_const);
+
+        let n = w.len();
+        Some(Self {
+            order,
+            params,
+            sigma2: total / n as f64,
+            aic: calculate_aic(n, total, dim),
+            bic: calculate_bic(n, total, dim),
+            levels: levels.to_vec(),
+        })
+    }
+
+    pub fn differenced(&self) -> Vec<f64> {
+        let o = &self.order;
+        apply_diff_poly(&self.levels, &diff_poly(o.d, o.seasonal_d, o.period.max(1)))
+    }
+
+    /// พยากรณ์ระดับ h ก้าวพร้อมช่วง 80/95%
+    pub fn forecast(&self, h: usize) -> Vec<ForecastStep> {
+        let s = self.order.period.max(1);
+        let delta = diff_poly(self.order.d, self.order.seasonal_d, s);
+        let par = self.params.expanded(s);
+        let w_hat = arma_forecast_mean(&self.differenced(), &par, h);
+
+        // x̂_t = ŵ_t - Σ_{k>=1} δ_k x_{t-k}
+        let mut ext = self.levels.clone();
+        for w in &w_hat {
+            let t = ext.len();
+            let lagged: f64 = delta
+                .iter()
+                .enumerate()
+                .skip(1)
+                .filter(|(k, _)| *k <= t)
+                .map(|(k, dk)| dk * ext[t - k])
+                .sum();
+            ext.push(w - lagged);
+        }
+        let means = ext.split_off(self.levels.len());
+
+        let phi_star = poly_to_ar(&poly_mul(&ar_poly(&par.phi, 1), &delta));
+        let psi = psi_weights(&phi_star, &par.theta, h);
+        means
+            .iter()
+            .zip(forecast_std_errors(&psi, self.sigma2))
+            .map(|(m, se)| ForecastStep::new(*m, se))
+            .collect()
+    }
+}
+
+/// เสนอคาบฤดูกาลจาก peak ของ FFT amplitude ในช่วงคาบ [min_period, max_period] แท่ง
+pub fn suggest_season_period(
+    series: &[f64],
+    min_period: usize,
+    max_period: usize,
+) -> Option<usize> {
+    // นับคาบเป็นแท่ง: ให้ 1 แท่ง = 1 วัน แล้วแปลง cycles/year กลับด้วยค่าคงที่ตัวเดียวกับ fft_spectrum
+    let bars_per_day = 1.0;
+    let (freqs_cpy, mags) = fft_spectrum(series, bars_per_day).ok()?;
+    let bars_per_year = bars_per_day * TRADING_DAYS_PER_YEAR;
+    freqs_cpy
+        .iter()
+        .zip(&mags)
+        .filter(|(f, _)| **f > 0.0)
+        .map(|(f, m)| (bars_per_year / f, *m))
+        .filter(|(period, _)| *period >= min_period as f64 && *period <= max_period as f64)
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(period, _)| period.round() as usize)
+}
+
+/// D = 1 เมื่อ autocorrelation ที่ lag s เกิน 0.5
+/// (เท่ากับเงื่อนไขที่ (1 - B^s) ลด variance ของ series ได้)
+pub fn seasonal_ndiffs(w: &[f64], period: usize) -> usize {
+    let n = w.len();
+    if period == 0 || n <= 2 * period {
+        return 0;
+    }
+    let mean = w.iter().sum::<f64>() / n as f64;
+    let c0: f64 = w.iter().map(|v| (v - mean).powi(2)).sum();
+    if c0 <= 0.0 {
+        return 0;
+    }
+    let cs: f64 = (period..n)
+        .map(|t| (w[t] - mean) * (w[t - period] - mean))
+        .sum();
+    usize::from(cs / c0 > 0.5)
+}
+
+/// หา d ด้วย KPSS/ADF, s จาก FFT (ถ้าไม่ได้ระบุ), D จาก seasonal_ndiffs
+/// แล้ว grid search p, q ∈ [0, max], P, Q ∈ {0, 1} ด้วย BIC
+pub fn auto_sarima(
+    levels: &[f64],
+    max_p: usize,
+    max_q: usize,
+    period: Option<usize>,
+    include_const: bool,
+) -> Option<SarimaModel> {
+    let d = ndiffs(levels, 2);
+    let w = apply_diff_poly(levels, &diff_poly(d, 0, 1));
+    let period = period.or_else(|| suggest_season_period(&w, 2, w.len() / 3))?;
+    let seasonal_d = seasonal_ndiffs(&w, period);
+
+    let mut best: Option<SarimaModel> = None;
+    for p in 0..=max_p {
+        for q in 0..=max_q {
+            for seasonal_p in 0..=1 {
+                for seasonal_q in 0..=1 {
+                    let order = SeasonalOrder {
+                        p,
+                        d,
+                        q,
+                        seasonal_p,
+                        seasonal_d,
+                        seasonal_q,
+                        period,
+                    };
+                    let Some(m) = SarimaModel::fit(levels, order, include_const) else {
+                        continue;
+                    };
+                    if best.as_ref().is_none_or(|b| m.bic < b.bic) {
+                        best = Some(m);
+                    }
+                }
+            }
+        }
+    }
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::module::model::arma::simulate_arma;
+
+    #[test]
+    fn test_suggest_period_from_fft_peak() {
+        let e = simulate_arma(600, 0.0, &[], &[], 1);
+        let x: Vec<f64> = (0..600)
+            .map(|t| 3.0 * (2.0 * std::f64::consts::PI * t as f64 / 12.0).sin() + e[t])
+            .collect();
+        assert_eq!(suggest_season_period(&x, 2, 200), Some(12));
+    }
+
+    #[test]
+    fn test_fit_seasonal_ar_recovers_coefficient() {
+        let e = simulate_arma(1500, 0.0, &[], &[], 7);
+        let mut x = vec![0.0; e.len()];
+        for t in 0..x.len() {
+            x[t] = e[t] + if t >= 12 { 0.6 * x[t - 12] } else { 0.0 };
+        }
+        let order = SeasonalOrder {
+            p: 0,
+            d: 0,
+            q: 0,
+            seasonal_p: 1,
+            seasonal_d: 0,
+            seasonal_q: 0,
+            period: 12,
+        };
+        let m = SarimaModel::fit(&x, order, false).unwrap();
+        assert!((m.params.seasonal_phi[0] - 0.6).abs() < 0.08);
+        // expanded AR มีค่าเฉพาะ lag 12
+        let par = m.params.expanded(12);
+        assert_eq!(par.phi.len(), 12);
+        assert!(par.phi[..11].iter().all(|v| v.abs() < 1e-12));
+    }
+
+    #[test]
+    fn test_seasonal_random_walk_forecast_repeats_last_season() {
+        let e = simulate_arma(100, 0.0, &[], &[], 3);
+        let pattern = [1.0, 5.0, -2.0, 3.0];
+        let x: Vec<f64> = (0..100).map(|t| pattern[t % 4] + 0.01 * e[t]).collect();
+        let order = SeasonalOrder {
+            p: 0,
+            d: 0,
+            q: 0,
+            seasonal_p: 0,
+            seasonal_d: 1,
+            seasonal_q: 0,
+            period: 4,
+        };
+        let m = SarimaModel::fit(&x, order, false).unwrap();
+        let f = m.forecast(8);
+        for (h, step) in f.iter().enumerate() {
+            assert!((step.mean - x[96 + h % 4]).abs() < 1e-12);
+        }
+        // ฤดูกาลถัดไปมี error สะสม 2 เท่าของ variance
+        assert!((f[4].se / f[0].se - 2f64.sqrt()).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,404 @@
+// เลือก order ของ ARMA: fit หลาย (p, q) พร้อมกันหลาย thread และ warm start จาก order ข้างเคียง
+// - grid: ไล่ทีละ "wave" ของ p + q แล้วเริ่มจากพารามิเตอร์ของ (p-1, q) / (p, q-1) ที่ fit แล้ว
+// - stepwise: Hyndman–Khandakar (2008) เริ่มจาก 4 model แล้วขยับ p, q ทีละ ±1 จนไม่ดีขึ้น
+
+use std::collections::HashMap;
+
+use crate::module::model::{
+    arma::{
+        ArmaModel, ArmaParams, ArmaState, arma_residuals, css_loglik, css_sse, fit_arma_css_with,
+        information_criteria, nelder_mead_min,
+    },
+    util::unpack_params::{pack_params, unpack_params},
+};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ArmaCriterion {
+    Aic,
+    Aicc,
+    Bic,
+    Hqic,
+    /// MSE ของ one-step forecast บน holdout แถวสุดท้าย (fit บนส่วนที่เหลือ)
+    OutOfSample {
+        holdout: usize,
+    },
+}
+
+#[derive(Clone, Debug)]
+pub struct AutoArmaConfig {
+    pub max_p: usize,
+    pub max_q: usize,
+    pub include_const: bool,
+    pub criterion: ArmaCriterion,
+    pub stepwise: bool,
+    pub threads: usize,
+}
+
+impl AutoArmaConfig {
+    pub fn new(max_p: usize, max_q: usize) -> Self {
+        Self {
+            max_p,
+            max_q,
+            include_const: true,
+            criterion: ArmaCriterion::Aicc,
+            stepwise: true,
+            threads: std::thread::available_parallelism().map_or(1, |n| n.get()),
+        }
+    }
+
+    pub fn with_criterion(mut self, criterion: ArmaCriterion) -> Self {
+        self.criterion = criterion;
+        self
+    }
+
+    /// false = ค้นทุก (p, q) ใน grid
+    pub fn with_stepwise(mut self, stepwise: bool) -> Self {
+        self.stepwise = stepwise;
+        self
+    }
+
+    pub fn with_threads(mut self, threads: usize) -> Self {
+        self.threads = threads.max(1);
+        self
+    }
+
+    pub fn without_const(mut self) -> Self {
+        self.include_const = false;
+        self
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct CandidateFit {
+    pub model: ArmaModel,
+    pub hqic: f64,
+    /// NaN ถ้า criterion ไม่ใช่ OutOfSample
+    pub oos_mse: f64,
+    /// ค่าที่ใช้จัดอันดับ (ยิ่งน้อยยิ่งดี)
+    pub score: f64,
+}
+
+#[derive(Clone, Debug)]
+pub struct ArmaSelection {
+    pub criterion: ArmaCriterion,
+    /// เรียงจาก score น้อยไปมาก
+    pub ranked: Vec<CandidateFit>,
+}
+
+impl ArmaSelection {
+    pub fn best(&self) -> Option<&CandidateFit> {
+        self.ranked.first()
+    }
+
+    pub fn print_table(&self, top: usize) {
+        println!(
+            "{:>3} {:>3} {:>12} {:>12} {:>12} {:>12} {:>12}   ({:?})",
+            "p", "q", "AIC", "AICc", "BIC", "HQIC", "OOS MSE", self.criterion
+        );
+        for c in self.ranked.iter().take(top) {
+            println!(
+                "{:>3} {:>3} {:>12.3} {:>12.3} {:>12.3} {:>12.3} {:>12.4e}",
+                c.model.p, c.model.q, c.model.aic, c.model.aicc, c.model.bic, c.hqic, c.oos_mse
+            );
+        }
+    }
+}
+
+/// map แบบขนานด้วย scoped thread (แบ่ง items เป็นก้อนละเท่า ๆ กัน) คืนผลตามลำดับเดิม
+fn par_map<T, R, F>(items: &[T], threads: usize, f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+{
+    if threads <= 1 || items.len() <= 1 {
+        return items.iter().map(&f).collect();
+    }
+    let chunk = items.len().div_ceil(threads);
+    std::thread::scope(|s| {
+        let handles: Vec<_> = items
+            .chunks(chunk)
+            .map(|c| s.spawn(|| c.iter().map(&f).collect::<Vec<R>>()))
+            .collect();
+        handles
+            .into_iter()
+            .flat_map(|h| h.join().expect("ARMA fit thread panicked"))
+            .collect()
+    })
+}
+
+/// ปรับ u = [c, u_ar, u_ma] ของ order อื่นให้เป็น (p, q): เติม PACF = 0 หรือตัดทิ้ง
+/// (ยังคง stationary / invertible เพราะอยู่ในพื้นที่ unconstrained)
+fn resize_start(par: &ArmaParams, p: usize, q: usize) -> Vec<f64> {
+    let u = pack_params(par);
+    let (p0, q0) = (par.phi.len(), par.theta.len());
+    let mut out = vec![u[0]];
+    out.extend((0..p).map(|i| if i < p0 { u[1 + i] } else { 0.0 }));
+    out.extend((0..q).map(|j| if j < q0 { u[1 + p0 + j] } else { 0.0 }));
+    out
+}
+
+fn fit_params(
+    y: &[f64],
+    p: usize,
+    q: usize,
+    include_const: bool,
+    start: Option<&ArmaParams>,
+) -> ArmaParams {
+    let Some(init) = start else {
+        return fit_arma_css_with(y, p, q, include_const);
+    };
+    let u0 = resize_start(init, p, q);
+    if include_const {
+        let u = nelder_mead_min(u0, |u| css_sse(y, u, p, q), 800, 1e-7);
+        unpack_params(&u, p, q)
+    } else {
+        let u = nelder_mead_min(
+            u0[1..].to_vec(),
+            |u| {
+                let mut full = vec![0.0];
+                full.extend_from_slice(u);
+                css_sse(y, &full, p, q)
+            },
+            800,
+            1e-7,
+        );
+        let mut full = vec![0.0];
+        full.extend(u);
+        unpack_params(&full, p, q)
+    }
+}
+
+fn fit_candidate(
+    y: &[f64],
+    p: usize,
+    q: usize,
+    config: &AutoArmaConfig,
+    start: Option<&ArmaParams>,
+) -> Option<CandidateFit> {
+    let holdout = match config.criterion {
+        ArmaCriterion::OutOfSample { holdout } => holdout,
+        _ => 0,
+    };
+    let train = &y[..y.len().checked_sub(holdout)?];
+    let n = train.len();
+    let k = usize::from(config.include_const) + p + q;
+    if n <= k + 2 {
+        return None;
+    }
+
+    let params = fit_params(train, p, q, config.include_const, start);
+    let state = ArmaState::from_series(train, &params);
+    let sse: f64 = arma_residuals(train, &params).iter().map(|e| e * e).sum();
+    if sse <= 0.0 {
+        return None;
+    }
+    // สเกล -2·loglik เดียวกับ exact MLE ; k นับ σ² ด้วย
+    let loglik = css_loglik(n, sse);
+    let (aic, bic, aicc) = information_criteria(loglik, n, k + 1);
+    if !aic.is_finite() {
+        return None;
+    }
+    let nf = n as f64;
+    let hqic = -2.0 * loglik + 2.0 * (k + 1) as f64 * nf.ln().ln();
+    let oos_mse = if holdout > 0 {
+        let e = arma_residuals(y, &params);
+        e[n..].iter().map(|v| v * v).sum::<f64>() / holdout as f64
+    } else {
+        f64::NAN
+    };
+    let score = match config.criterion {
+        ArmaCriterion::Aic => aic,
+        ArmaCriterion::Aicc => aicc,
+        ArmaCriterion::Bic => bic,
+        ArmaCriterion::Hqic => hqic,
+        ArmaCriterion::OutOfSample { .. } => oos_mse,
+    };
+    Some(CandidateFit {
+        model: ArmaModel {
+            p,
+            q,
+            params,
+            aic,
+            bic,
+            aicc,
+            n_obs: n,
+            n_params: k + 1,
+            sigma2: sse / nf,
+            state,
+        },
+        hqic,
+        oos_mse,
+        score,
+    })
+}
+
+type FitCache = HashMap<(usize, usize), Option<CandidateFit>>;
+
+fn allowed(p: usize, q: usize, config: &AutoArmaConfig) -> bool {
+    p <= config.max_p && q <= config.max_q && (p + q > 0 || config.include_const)
+}
+
+/// fit ทุก order ใน jobs พร้อมกัน ; job = ((p, q), จุดเริ่ม)
+fn fit_batch(
+    y: &[f64],
+    jobs: &[((usize, usize), Option<ArmaParams>)],
+    config: &AutoArmaConfig,
+    cache: &mut FitCache,
+) {
+    let fits = par_map(jobs, config.threads, |((p, q), start)| {
+        fit_candidate(y, *p, *q, config, start.as_ref())
+    });
+    for ((order, _), fit) in jobs.iter().zip(fits) {
+        cache.insert(*order, fit);
+    }
+}
+
+fn best_in(cache: &FitCache) -> Option<&CandidateFit> {
+    cache
+        .values()
+        .flatten()
+        .filter(|c| c.score.is_finite())
+        .min_by(|a, b| a.score.total_cmp(&b.score))
+}
+
+fn grid_search(y: &[f64], config: &AutoArmaConfig, cache: &mut FitCache) {
+    for s in 0..=config.max_p + config.max_q {
+        let jobs: Vec<_> = (0..=s.min(config.max_p))
+            .map(|p| (p, s - p))
+            .filter(|&(p, q)| allowed(p, q, config))
+            .map(|(p, q)| {
+                // order ข้างเคียงที่เล็กกว่าหนึ่งขั้นและ score ดีที่สุด
+                let start = [(p.wrapping_sub(1), q), (p, q.wrapping_sub(1))]
+                    .iter()
+                    .filter_map(|o| cache.get(o).and_then(Option::as_ref))
+                    .min_by(|a, b| a.score.total_cmp(&b.score))
+                    .map(|c| c.model.params.clone());
+                ((p, q), start)
+            })
+            .collect();
+        fit_batch(y, &jobs, config, cache);
+    }
+}
+
+fn stepwise_search(y: &[f64], config: &AutoArmaConfig, cache: &mut FitCache) {
+    let mut init: Vec<(usize, usize)> = Vec::new();
+    for (p, q) in [(2, 2), (0, 0), (1, 0), (0, 1)] {
+        let o = (p.min(config.max_p), q.min(config.max_q));
+        if allowed(o.0, o.1, config) && !init.contains(&o) {
+            init.push(o);
+        }
+    }
+    let jobs: Vec<_> = init.into_iter().map(|o| (o, None)).collect();
+    fit_batch(y, &jobs, config, cache);
+
+    let Some(mut best) = best_in(cache).cloned() else {
+        return;
+    };
+    loop {
+        let (p, q) = (best.model.p as isize, best.model.q as isize);
+        let mut jobs = Vec::new();
+        for dp in -1..=1 {
+            for dq in -1..=1 {
+                let (np, nq) = (p + dp, q + dq);
+                if np < 0 || nq < 0 {
+                    continue;
+                }
+                let o = (np as usize, nq as usize);
+                if allowed(o.0, o.1, config) && !cache.contains_key(&o) {
+                    jobs.push((o, Some(best.model.params.clone())));
+                }
+            }
+        }
+        if jobs.is_empty() {
+            break;
+        }
+        fit_batch(y, &jobs, config, cache);
+        match best_in(cache) {
+            Some(b) if b.score < best.score => best = b.clone(),
+            _ => break,
+        }
+    }
+}
+
+/// เลือก ARMA(p, q) บน series ที่ stationary แล้ว คืนตารางเรียงตาม criterion
+pub fn select_arma(series: &[f64], config: &AutoArmaConfig) -> Option<ArmaSelection> {
+    if series.len() < 2 {
+        return None;
+    }
+    let mut cache = FitCache::new();
+    if config.stepwise {
+        stepwise_search(series, config, &mut cache);
+    } else {
+        grid_search(series, config, &mut cache);
+    }
+    let mut ranked: Vec<CandidateFit> = cache
+        .into_values()
+        .flatten()
+        .filter(|c| c.score.is_finite())
+        .collect();
+    if ranked.is_empty() {
+        return None;
+    }
+    ranked.sort_by(|a, b| {
+        a.score
+            .total_cmp(&b.score)
+            .then((a.model.p + a.model.q).cmp(&(b.model.p + b.model.q)))
+    });
+    Some(ArmaSelection {
+        criterion: config.criterion,
+        ranked,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::module::model::arma::simulate_arma;
+
+    #[test]
+    fn test_grid_and_stepwise_agree_on_arma11() {
+        let y = simulate_arma(1500, 0.0, &[0.6], &[0.4], 41);
+        let grid = select_arma(
+            &y,
+            &AutoArmaConfig::new(3, 3)
+                .with_criterion(ArmaCriterion::Bic)
+                .with_stepwise(false),
+        )
+        .unwrap();
+        assert_eq!(grid.ranked.len(), 16);
+        let best = grid.best().unwrap();
+        assert_eq!((best.model.p, best.model.q), (1, 1));
+        assert!(grid.ranked.windows(2).all(|w| w[0].score <= w[1].score));
+
+        let step = select_arma(
+            &y,
+            &AutoArmaConfig::new(3, 3).with_criterion(ArmaCriterion::Bic),
+        )
+        .unwrap();
+        let sb = step.best().unwrap();
+        assert_eq!((sb.model.p, sb.model.q), (1, 1));
+        assert!(step.ranked.len() < grid.ranked.len());
+        assert!((sb.score - best.score).abs() < 1e-3 * best.score.abs());
+    }
+
+    #[test]
+    fn test_out_of_sample_and_single_thread() {
+        let y = simulate_arma(800, 0.0, &[0.6], &[0.4], 41);
+        let config = AutoArmaConfig::new(2, 2)
+            .with_criterion(ArmaCriterion::OutOfSample { holdout: 100 })
+            .with_stepwise(false)
+            .with_threads(1);
+        let sel = select_arma(&y, &config).unwrap();
+        let best = sel.best().unwrap();
+        assert!(best.oos_mse.is_finite() && best.oos_mse > 0.0);
+        assert_eq!(best.score, best.oos_mse);
+        // white-noise mean model (0,0) ต้องแย่กว่า ARMA(1,1)
+        let mean_only = sel
+            .ranked
+            .iter()
+            .find(|c| c.model.p == 0 && c.model.q == 0)
+            .unwrap();
+        assert!(mean_only.oos_mse > best.oos_mse);
+    }
+}
@@ -0,0 +1,418 @@
+// วัดรอบ (cycle) ที่เปลี่ยนตามเวลา
+// - analytic signal ผ่าน FFT (rustfft): amplitude / phase / period ขณะหนึ่ง ใช้ข้อมูลทั้งก้อน -> วิเคราะห์ย้อนหลัง
+// - homodyne discriminator ของ Ehlers: dominant cycle แบบ causal ทีละแท่ง + sine wave + trend/cycle mode
+//   ลำดับการคำนวณและค่าคงที่ตาม HT_DCPERIOD / HT_SINE / HT_TRENDMODE ของ TA-Lib
+
+use std::collections::VecDeque;
+use std::f64::consts::PI;
+
+use rustfft::{FftPlanner, num_complex::Complex};
+
+use crate::module::model::streaming::Indicator;
+
+/// จำนวนแท่งก่อน period นิ่ง (lookback ของ HT_DCPERIOD)
+pub const DC_PERIOD_WARMUP: usize = 32;
+/// จำนวนแท่งก่อน sine / trend mode ใช้ได้ (lookback ของ HT_SINE)
+pub const SINE_WARMUP: usize = 63;
+
+#[derive(Clone, Debug)]
+pub struct AnalyticSignal {
+    pub amplitude: Vec<f64>,
+    /// phase (rad) แบบ unwrap แล้ว
+    pub phase: Vec<f64>,
+    /// 2π / Δphase (bars) ; None ที่แท่งแรกหรือ phase ไม่เดินหน้า
+    pub period: Vec<Option<f64>>,
+}
+
+/// x + j·H[x] ด้วย FFT (ลบ mean ก่อน) ; ปลายทั้งสองข้างมี edge effect
+pub fn analytic_signal(x: &[f64]) -> Vec<Complex<f64>> {
+    let n = x.len();
+    if n == 0 {
+        return Vec::new();
+    }
+    let mean = x.iter().sum::<f64>() / n as f64;
+    let mut buf: Vec<Complex<f64>> = x.iter().map(|v| Complex::new(v - mean, 0.0)).collect();
+    let mut planner = FftPlanner::<f64>::new();
+    planner.plan_fft_forward(n).process(&mut buf);
+    // คูณ 2 ที่ความถี่บวก, ตัดความถี่ลบทิ้ง (DC / Nyquist คงไว้)
+    for (k, c) in buf.iter_mut().enumerate() {
+        if k == 0 || (n.is_multiple_of(2) && k == n / 2) {
+            continue;
+        }
+        *c *= if k < n.div_ceil(2) { 2.0 } else { 0.0 };
+    }
+    planner.plan_fft_inverse(n).process(&mut buf);
+    buf.iter().map(|c| c / n as f64).collect()
+}
+
+pub fn instantaneous(x: &[f64]) -> AnalyticSignal {
+    let z = analytic_signal(x);
+    let amplitude = z.iter().map(|c| c.norm()).collect();
+    let mut phase: Vec<f64> = Vec::with_capacity(z.len());
+    for c in &z {
+        let raw = c.arg();
+        let v = match phase.last() {
+            Some(&prev) => raw + 2.0 * PI * ((prev - raw) / (2.0 * PI)).round(),
+            None => raw,
+        };
+        phase.push(v);
+    }
+    let period = (0..phase.len())
+        .map(|t| {
+            let d = phase[t] - phase[t.checked_sub(1)?];
+            (d > 0.0).then(|| 2.0 * PI / d)
+        })
+        .collect();
+    AnalyticSignal {
+        amplitude,
+        phase,
+        period,
+    }
+}
+
+/// ค่าย้อนหลังแบบจำกัดความยาว ; lag ที่ยังไม่มีข้อมูลให้ 0 เหมือน TA-Lib
+#[derive(Clone, Debug)]
+struct Lagged {
+    values: VecDeque<f64>,
+    cap: usize,
+}
+
+impl Lagged {
+    fn new(cap: usize) -> Self {
+        Self {
+            values: VecDeque::with_capacity(cap + 1),
+            cap,
+        }
+    }
+
+    fn push(&mut self, v: f64) {
+        self.values.push_front(v);
+        self.values.truncate(self.cap);
+    }
+
+    fn get(&self, lag: usize) -> f64 {
+        self.values.get(lag).copied().unwrap_or(0.0)
+    }
+}
+
+/// ค่าของ homodyne ที่แท่งล่าสุด
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CyclePoint {
+    /// dominant cycle (bars) ก่อน smooth รอบสุดท้าย
+    pub period: f64,
+    /// dominant cycle ที่ใช้งาน (HT_DCPERIOD)
+    pub smooth_period: f64,
+    /// phase ของ dominant cycle (degrees)
+    pub dc_phase: f64,
+    pub sine: f64,
+    pub lead_sine: f64,
+    /// instantaneous trendline (HT_TRENDLINE)
+    pub trendline: f64,
+    pub smooth_price: f64,
+    /// true = trend mode, false = cycle mode
+    pub trend_mode: bool,
+}
+
+/// homodyne dominant cycle แบบ streaming ; update คืน smooth_period หลังผ่าน DC_PERIOD_WARMUP แท่ง
+#[derive(Clone, Debug)]
+pub struct HomodyneCycle {
+    count: usize,
+    price: Lagged,
+    smooth: Lagged,
+    detrender: Lagged,
+    q1: Lagged,
+    i1: Lagged,
+    i_trend: Lagged,
+    i2: f64,
+    q2: f64,
+    re: f64,
+    im: f64,
+    period: f64,
+    smooth_period: f64,
+    dc_phase: f64,
+    sine: f64,
+    lead_sine: f64,
+    days_in_trend: usize,
+    last: Option<CyclePoint>,
+}
+
+impl Default for HomodyneCycle {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HomodyneCycle {
+    pub fn new() -> Self {
+        Self {
+            count: 0,
+            price: Lagged::new(50),
+            smooth: Lagged::new(50),
+            detrender: Lagged::new(7),
+            q1: Lagged::new(7),
+            i1: Lagged::new(7),
+            i_trend: Lagged::new(4),
+            i2: 0.0,
+            q2: 0.0,
+            re: 0.0,
+            im: 0.0,
+            period: 0.0,
+            smooth_period: 0.0,
+            dc_phase: 0.0,
+            sine: 0.0,
+            lead_sine: 0.0,
+            days_in_trend: 0,
+            last: None,
+        }
+    }
+
+    /// ค่าทั้งหมดของแท่งล่าสุด ; None จนกว่าจะผ่าน SINE_WARMUP
+    pub fn point(&self) -> Option<CyclePoint> {
+        self.last.filter(|_| self.count > SINE_WARMUP)
+    }
+
+    fn hilbert(s: &Lagged, gain: f64) -> f64 {
+        (0.0962 * s.get(0) + 0.5769 * s.get(2) - 0.5769 * s.get(4) - 0.0962 * s.get(6)) * gain
+    }
+
+    fn step(&mut self, price: f64) -> CyclePoint {
+        self.count += 1;
+        self.price.push(price);
+        let smooth_price = (4.0 * self.price.get(0)
+            + 3.0 * self.price.get(1)
+            + 2.0 * self.price.get(2)
+            + self.price.get(3))
+            / 10.0;
+        self.smooth.push(smooth_price);
+
+        // Hilbert transform (FIR 7 tap) ปรับ gain ตาม period ก่อนหน้า
+        let gain = 0.075 * self.period + 0.54;
+        self.detrender.push(Self::hilbert(&self.smooth, gain));
+        self.q1.push(Self::hilbert(&self.detrender, gain));
+        self.i1.push(self.detrender.get(3));
+        let j_i = Self::hilbert(&self.i1, gain);
+        let j_q = Self::hilbert(&self.q1, gain);
+
+        // phasor บวก 90° แล้ว smooth
+        let (prev_i2, prev_q2) = (self.i2, self.q2);
+        self.i2 = 0.2 * (self.i1.get(0) - j_q) + 0.8 * prev_i2;
+        self.q2 = 0.2 * (self.q1.get(0) + j_i) + 0.8 * prev_q2;
+
+        // homodyne discriminator
+        self.re = 0.2 * (self.i2 * prev_i2 + self.q2 * prev_q2) + 0.8 * self.re;
+        self.im = 0.2 * (self.i2 * prev_q2 - self.q2 * prev_i2) + 0.8 * self.im;
+        let prev_period = self.period;
+        let mut period = prev_period;
+        if self.im != 0.0 && self.re != 0.0 {
+            period = 2.0 * PI / (self.im / self.re).atan();
+        }
+        period = period.min(1.5 * prev_period).max(0.67 * prev_period);
+        period = period.clamp(6.0, 50.0);
+        self.period = 0.2 * period + 0.8 * prev_period;
+        self.smooth_period = 0.33 * self.period + 0.67 * self.smooth_period;
+
+        // phase ของ dominant cycle จาก DFT หนึ่งรอบ
+        let dc_period = ((self.smooth_period + 0.5) as usize).max(1);
+        let (mut real, mut imag) = (0.0, 0.0);
+        for i in 0..dc_period {
+            let a = 2.0 * PI * i as f64 / dc_period as f64;
+            let v = self.smooth.get(i);
+            real += a.sin() * v;
+            imag += a.cos() * v;
+        }
+        let prev_dc_phase = self.dc_phase;
+        if imag.abs() > 0.0 {
+            self.dc_phase = (real / imag).atan().to_degrees();
+        } else if real < 0.0 {
+            self.dc_phase -= 90.0;
+        } else if real > 0.0 {
+            self.dc_phase += 90.0;
+        }
+        self.dc_phase += 90.0;
+        // ชดเชย lag ของ WMA 4 แท่ง
+        self.dc_phase += 360.0 / self.smooth_period;
+        if imag < 0.0 {
+            self.dc_phase += 180.0;
+        }
+        if self.dc_phase > 315.0 {
+            self.dc_phase -= 360.0;
+        }
+        let (prev_sine, prev_lead) = (self.sine, self.lead_sine);
+        self.sine = self.dc_phase.to_radians().sin();
+        self.lead_sine = (self.dc_phase + 45.0).to_radians().sin();
+
+        // instantaneous trendline: ค่าเฉลี่ยราคาหนึ่งรอบแล้ว WMA 4 แท่ง
+        let avg = (0..dc_period).map(|i| self.price.get(i)).sum::<f64>() / dc_period as f64;
+        self.i_trend.push(avg);
+        let trendline = (4.0 * self.i_trend.get(0)
+            + 3.0 * self.i_trend.get(1)
+            + 2.0 * self.i_trend.get(2)
+            + self.i_trend.get(3))
+            / 10.0;
+
+        // trend mode เว้นแต่ sine กับ lead sine เพิ่งตัดกัน หรือ phase เดินตามจังหวะของรอบ
+        let mut trend_mode = true;
+        let crossed = (self.sine > self.lead_sine && prev_sine <= prev_lead)
+            || (self.sine < self.lead_sine && prev_sine >= prev_lead);
+        if crossed {
+            self.days_in_trend = 0;
+            trend_mode = false;
+        }
+        self.days_in_trend += 1;
+        if (self.days_in_trend as f64) < 0.5 * self.smooth_period {
+            trend_mode = false;
+        }
+        let d_phase = self.dc_phase - prev_dc_phase;
+        if self.smooth_period != 0.0
+            && d_phase > 0.67 * 360.0 / self.smooth_period
+            && d_phase < 1.5 * 360.0 / self.smooth_period
+        {
+            trend_mode = false;
+        }
+        if trendline != 0.0 && ((smooth_price - trendline) / trendline).abs() >= 0.015 {
+            trend_mode = true;
+        }
+
+        CyclePoint {
+            period: self.period,
+            smooth_period: self.smooth_period,
+            dc_phase: self.dc_phase,
+            sine: self.sine,
+            lead_sine: self.lead_sine,
+            trendline,
+            smooth_price,
+            trend_mode,
+        }
+    }
+}
+
+impl Indicator for HomodyneCycle {
+    type Input = f64;
+
+    fn update(&mut self, price: f64) -> Option<f64> {
+        let point = self.step(price);
+        self.last = Some(point);
+        (self.count > DC_PERIOD_WARMUP).then_some(point.smooth_period)
+    }
+
+    fn reset(&mut self) {
+        *self = Self::new();
+    }
+}
+
+/// dominant cycle period ต่อแท่ง (HT_DCPERIOD)
+pub fn dominant_cycle_series(data: &[f64]) -> Vec<Option<f64>> {
+    HomodyneCycle::new().run(data.iter().copied())
+}
+
+/// CyclePoint ต่อแท่ง ; None ช่วง SINE_WARMUP
+pub fn cycle_points(data: &[f64]) -> Vec<Option<CyclePoint>> {
+    let mut hc = HomodyneCycle::new();
+    data.iter()
+        .map(|&x| {
+            hc.update(x);
+            hc.point()
+        })
+        .collect()
+}
+
+/// EMA ที่ alpha = 2 / (fraction·period_t + 1) เปลี่ยนตาม period ของแต่ละแท่ง
+/// เริ่มที่แท่งแรกที่มี period ; fraction = 0.5 คือ EMA ครึ่งรอบ
+pub fn adaptive_ema_series(
+    data: &[f64],
+    periods: &[Option<f64>],
+    fraction: f64,
+) -> Vec<Option<f64>> {
+    let mut ema: Option<f64> = None;
+    data.iter()
+        .zip(periods)
+        .map(|(&x, p)| {
+            let p = (*p)?;
+            let alpha = 2.0 / (fraction * p + 1.0);
+            let next = match ema {
+                Some(e) => alpha.min(1.0) * x + (1.0 - alpha.min(1.0)) * e,
+                None => x,
+            };
+            ema = Some(next);
+            ema
+        })
+        .collect()
+}
+
+/// สัญญาณสลับโหมด: trend mode -> smooth price > trendline, cycle mode -> lead sine > sine
+pub fn cycle_switch_signal(data: &[f64]) -> Vec<Option<bool>> {
+    cycle_points(data)
+        .iter()
+        .map(|p| {
+            p.map(|p| {
+                if p.trend_mode {
+                    p.smooth_price > p.trendline
+                } else {
+                    p.lead_sine > p.sine
+                }
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // แอมพลิจูด 1% ต่ำกว่าเกณฑ์ 1.5% จาก trendline -> ไม่ถูกบังคับเป็น trend mode
+    fn sine(n: usize, period: f64) -> Vec<f64> {
+        (0..n)
+            .map(|t| 100.0 + (2.0 * PI * t as f64 / period).sin())
+            .collect()
+    }
+
+    #[test]
+    fn test_analytic_signal_recovers_amplitude_and_period() {
+        // period 32 ลงตัวกับ n -> ไม่มี leakage
+        let x: Vec<f64> = (0..512)
+            .map(|t| 3.0 * (2.0 * PI * t as f64 / 32.0).cos())
+            .collect();
+        let a = instantaneous(&x);
+        for t in 10..500 {
+            assert!((a.amplitude[t] - 3.0).abs() < 1e-9);
+            assert!((a.period[t].unwrap() - 32.0).abs() < 1e-6);
+        }
+        assert!(a.period[0].is_none());
+    }
+
+    #[test]
+    fn test_homodyne_tracks_cycle_and_warmup() {
+        for period in [15.0, 30.0] {
+            let dc = dominant_cycle_series(&sine(400, period));
+            assert!(dc[DC_PERIOD_WARMUP - 1].is_none() && dc[DC_PERIOD_WARMUP].is_some());
+            let tail: Vec<f64> = dc[300..].iter().flatten().copied().collect();
+            let mean = tail.iter().sum::<f64>() / tail.len() as f64;
+            assert!((mean - period).abs() < 0.1 * period, "{period}: {mean}");
+        }
+
+        let pts = cycle_points(&sine(300, 20.0));
+        assert!(pts[SINE_WARMUP - 1].is_none() && pts[SINE_WARMUP].is_some());
+        // cycle ล้วน -> ส่วนใหญ่เป็น cycle mode, sine แกว่งครบ
+        let tail: Vec<CyclePoint> = pts[150..].iter().flatten().copied().collect();
+        let cycle_mode = tail.iter().filter(|p| !p.trend_mode).count();
+        assert!(cycle_mode * 2 > tail.len(), "{cycle_mode}/{}", tail.len());
+        assert!(tail.iter().any(|p| p.sine > 0.9) && tail.iter().any(|p| p.sine < -0.9));
+
+        // แนวโน้มชัดเจน -> trend mode
+        let trend: Vec<f64> = (0..300).map(|t| 100.0 * 1.01f64.powi(t)).collect();
+        let last = cycle_points(&trend)[299].unwrap();
+        assert!(last.trend_mode);
+    }
+
+    #[test]
+    fn test_adaptive_ema() {
+        let x = [1.0, 2.0, 3.0, 4.0];
+        let p = [None, Some(3.0), Some(1.0), Some(3.0)];
+        let e = adaptive_ema_series(&x, &p, 1.0);
+        assert_eq!(e[0], None);
+        assert_eq!(e[1], Some(2.0));
+        // period 1 -> alpha 1
+        assert_eq!(e[2], Some(3.0));
+        assert_eq!(e[3], Some(0.5 * 4.0 + 0.5 * 3.0));
+    }
+}
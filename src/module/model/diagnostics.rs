@@ -0,0 +1,282 @@
+// ตรวจความเหมาะสมของ ARMA ที่ fit แล้วจาก residual
+// - Ljung–Box / Box–Pierce: residual ยังมี autocorrelation เหลือหรือไม่
+// - Jarque–Bera: residual เป็น normal หรือไม่ (skewness / kurtosis)
+// - ARCH-LM: ε² มี autocorrelation (volatility clustering) หรือไม่
+// - inverse roots ของ AR/MA: ต้องอยู่ในวงกลมหนึ่งหน่วยจึง stationary / invertible
+
+use nalgebra::{Complex, DMatrix, DVector};
+
+use crate::module::{
+    eval::skill::TestResult,
+    model::{
+        arma::{ArmaModel, arma_residuals},
+        pacf::{acf_fft, plot_acf},
+    },
+    plot::plot_diagnostics::{plot_qq, plot_unit_circle},
+    util::math::distribution::chi2_sf,
+};
+
+#[derive(Clone, Debug)]
+pub struct DiagnosticsReport {
+    pub lags: usize,
+    pub ljung_box: TestResult,
+    pub box_pierce: TestResult,
+    pub jarque_bera: TestResult,
+    pub arch_lm: TestResult,
+    /// inverse roots ของ 1 - φ_1 z - ... - φ_p z^p
+    pub ar_roots: Vec<Complex<f64>>,
+    /// inverse roots ของ 1 + θ_1 z + ... + θ_q z^q
+    pub ma_roots: Vec<Complex<f64>>,
+}
+
+fn nan_result(n: usize) -> TestResult {
+    TestResult {
+        statistic: f64::NAN,
+        p_value: f64::NAN,
+        n,
+    }
+}
+
+/// Q แบบ Ljung–Box (ljung = true) หรือ Box–Pierce ; df = lags - fitdf
+fn portmanteau(resid: &[f64], lags: usize, fitdf: usize, ljung: bool) -> TestResult {
+    let n = resid.len();
+    if lags == 0 || lags <= fitdf || n <= lags + 1 {
+        return nan_result(n);
+    }
+    let r = acf_fft(resid, lags, true);
+    let nf = n as f64;
+    let q: f64 = (1..=lags)
+        .map(|k| {
+            if ljung {
+                r[k] * r[k] / (nf - k as f64)
+            } else {
+                r[k] * r[k]
+            }
+        })
+        .sum::<f64>()
+        * if ljung { nf * (nf + 2.0) } else { nf };
+    TestResult {
+        statistic: q,
+        p_value: chi2_sf(q, (lags - fitdf) as f64),
+        n,
+    }
+}
+
+pub fn ljung_box(resid: &[f64], lags: usize, fitdf: usize) -> TestResult {
+    portmanteau(resid, lags, fitdf, true)
+}
+
+pub fn box_pierce(resid: &[f64], lags: usize, fitdf: usize) -> TestResult {
+    portmanteau(resid, lags, fitdf, false)
+}
+
+/// JB = n/6 · (S² + (K - 3)² / 4) ~ χ²(2)
+pub fn jarque_bera(x: &[f64]) -> TestResult {
+    let n = x.len();
+    if n < 3 {
+        return nan_result(n);
+    }
+    let nf = n as f64;
+    let mean = x.iter().sum::<f64>() / nf;
+    let moment = |k: i32| x.iter().map(|v| (v - mean).powi(k)).sum::<f64>() / nf;
+    let m2 = moment(2);
+    if m2 <= 0.0 {
+        return nan_result(n);
+    }
+    let skew = moment(3) / m2.powf(1.5);
+    let kurt = moment(4) / (m2 * m2);
+    let jb = nf / 6.0 * (skew * skew + (kurt - 3.0).powi(2) / 4.0);
+    TestResult {
+        statistic: jb,
+        p_value: chi2_sf(jb, 2.0),
+        n,
+    }
+}
+
+/// Engle ARCH-LM: regress ε²_t บน ε²_{t-1..t-lags} แล้ว LM = T·R² ~ χ²(lags)
+pub fn arch_lm(resid: &[f64], lags: usize) -> TestResult {
+    let e2: Vec<f64> = resid.iter().map(|e| e * e).collect();
+    let n = e2.len();
+    if lags == 0 || n <= 2 * lags + 1 {
+        return nan_result(n);
+    }
+    let t = n - lags;
+    let x = DMatrix::from_fn(
+        t,
+        lags + 1,
+        |r, c| {
+            if c == 0 { 1.0 } else { e2[lags + r - c] }
+        },
+    );
+    let y = DVector::from_column_slice(&e2[lags..]);
+    let Ok(b) = x.clone().svd(true, true).solve(&y, 1e-12) else {
+        return nan_result(t);
+    };
+    let mean = y.mean();
+    let tss: f64 = y.iter().map(|v| (v - mean).powi(2)).sum();
+    if tss <= 0.0 {
+        return nan_result(t);
+    }
+    let r2 = 1.0 - (&y - &x * b).norm_squared() / tss;
+    let lm = t as f64 * r2;
+    TestResult {
+        statistic: lm,
+        p_value: chi2_sf(lm, lags as f64),
+        n: t,
+    }
+}
+
+/// eigenvalues ของ companion matrix = inverse roots ของ 1 - c_1 z - ... - c_m z^m
+fn inverse_roots(coef: &[f64]) -> Vec<Complex<f64>> {
+    let m = coef.len();
+    if m == 0 {
+        return Vec::new();
+    }
+    let companion = DMatrix::from_fn(m, m, |i, j| {
+        if i == 0 {
+            coef[j]
+        } else if i == j + 1 {
+            1.0
+        } else {
+            0.0
+        }
+    });
+    companion.complex_eigenvalues().iter().copied().collect()
+}
+
+pub fn ar_inverse_roots(phi: &[f64]) -> Vec<Complex<f64>> {
+    inverse_roots(phi)
+}
+
+pub fn ma_inverse_roots(theta: &[f64]) -> Vec<Complex<f64>> {
+    let neg: Vec<f64> = theta.iter().map(|t| -t).collect();
+    inverse_roots(&neg)
+}
+
+impl DiagnosticsReport {
+    /// y คือ series ที่ใช้ fit (ก่อนคำนวณ residual)
+    pub fn new(model: &ArmaModel, y: &[f64], lags: usize) -> Self {
+        let resid = arma_residuals(y, &model.params);
+        Self::from_residuals(&resid, model, lags)
+    }
+
+    pub fn from_residuals(resid: &[f64], model: &ArmaModel, lags: usize) -> Self {
+        let fitdf = model.p + model.q;
+        Self {
+            lags,
+            ljung_box: ljung_box(resid, lags, fitdf),
+            box_pierce: box_pierce(resid, lags, fitdf),
+            jarque_bera: jarque_bera(resid),
+            arch_lm: arch_lm(resid, lags.min(12)),
+            ar_roots: ar_inverse_roots(&model.params.phi),
+            ma_roots: ma_inverse_roots(&model.params.theta),
+        }
+    }
+
+    pub fn is_stationary(&self) -> bool {
+        self.ar_roots.iter().all(|r| r.norm() < 1.0)
+    }
+
+    pub fn is_invertible(&self) -> bool {
+        self.ma_roots.iter().all(|r| r.norm() < 1.0)
+    }
+
+    pub fn print(&self) {
+        let row = |name: &str, t: &TestResult| {
+            println!(
+                "  {:<12} stat={:>10.4} p={:.4} n={}",
+                name, t.statistic, t.p_value, t.n
+            );
+        };
+        println!("Residual diagnostics (lags={}):", self.lags);
+        row("Ljung-Box", &self.ljung_box);
+        row("Box-Pierce", &self.box_pierce);
+        row("Jarque-Bera", &self.jarque_bera);
+        row("ARCH-LM", &self.arch_lm);
+        let fmt = |roots: &[Complex<f64>]| {
+            roots
+                .iter()
+                .map(|r| format!("{:.3}{:+.3}i (|{:.3}|)", r.re, r.im, r.norm()))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        println!(
+            "  AR inverse roots: [{}] stationary={}",
+            fmt(&self.ar_roots),
+            self.is_stationary()
+        );
+        println!(
+            "  MA inverse roots: [{}] invertible={}",
+            fmt(&self.ma_roots),
+            self.is_invertible()
+        );
+    }
+
+    /// ACF ของ residual, QQ plot และ unit circle ลงใน output_dir
+    pub fn plot(&self, resid: &[f64], output_dir: &str) -> Result<(), Box<dyn std::error::Error>> {
+        std::fs::create_dir_all(output_dir)?;
+        plot_acf(resid, self.lags, &format!("{output_dir}/residual_acf.png"))?;
+        plot_qq(resid, &format!("{output_dir}/residual_qq.png"))?;
+        plot_unit_circle(
+            &self.ar_roots,
+            &self.ma_roots,
+            &format!("{output_dir}/arma_roots.png"),
+        )?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::module::model::arma::simulate_arma;
+
+    #[test]
+    fn test_portmanteau_and_normality() {
+        let e = simulate_arma(2000, 0.0, &[], &[], 5);
+        assert!(ljung_box(&e, 10, 0).p_value > 0.01);
+        assert!(jarque_bera(&e).p_value > 0.01);
+        assert!(arch_lm(&e, 5).p_value > 0.01);
+
+        // AR(1) φ=0.5 -> autocorrelation ชัดเจน
+        let mut y = e.clone();
+        for t in 1..y.len() {
+            y[t] += 0.5 * y[t - 1];
+        }
+        let lb = ljung_box(&y, 10, 0);
+        let bp = box_pierce(&y, 10, 0);
+        assert!(lb.p_value < 1e-6);
+        assert!(lb.statistic > bp.statistic);
+
+        // exponential มี skew ชัดเจน
+        let skewed: Vec<f64> = e.iter().map(|v| v.exp()).collect();
+        assert!(jarque_bera(&skewed).p_value < 1e-6);
+    }
+
+    #[test]
+    fn test_arch_lm_detects_clustering() {
+        let z = simulate_arma(3000, 0.0, &[], &[], 9);
+        let mut e = vec![0.0; z.len()];
+        for t in 1..z.len() {
+            let h: f64 = 0.2 + 0.6 * e[t - 1] * e[t - 1];
+            e[t] = h.sqrt() * z[t];
+        }
+        assert!(arch_lm(&e, 5).p_value < 1e-6);
+    }
+
+    #[test]
+    fn test_inverse_roots() {
+        // 1 - 0.5z : inverse root 0.5
+        let r = ar_inverse_roots(&[0.5]);
+        assert!((r[0].re - 0.5).abs() < 1e-12 && r[0].im.abs() < 1e-12);
+        // 1 - 1.2z + 0.36z² = (1 - 0.6z)² ; 1 + 0.3z : inverse root -0.3
+        let r = ar_inverse_roots(&[1.2, -0.36]);
+        assert!(r.iter().all(|z| (z.norm() - 0.6).abs() < 1e-6));
+        let m = ma_inverse_roots(&[0.3]);
+        assert!((m[0].re + 0.3).abs() < 1e-12);
+        // complex pair ของ 1 - z + 0.5z² มี |root| = √0.5
+        let r = ar_inverse_roots(&[1.0, -0.5]);
+        assert!(r.iter().all(|z| (z.norm() - 0.5f64.sqrt()).abs() < 1e-12));
+        assert!(r.iter().any(|z| z.im.abs() > 0.1));
+    }
+}
@@ -54,9 +54,61 @@ fn cal_differencing(data: Vec<(i64, f64)>) -> Vec<f64> {
     diffs
 }
 
+/// ∇^d ของ series (ยาวลดลง d จุด)
+pub fn diff_n(levels: &[f64], d: usize) -> Vec<f64> {
+    let mut out = levels.to_vec();
+    for _ in 0..d {
+        if out.len() < 2 {
+            return Vec::new();
+        }
+        out = out.windows(2).map(|w| w[1] - w[0]).collect();
+    }
+    out
+}
+
+/// ระดับถัดไปจากผลต่างที่ทำนาย: x̂_{t+1} = x_t + Δ̂_{t+1}
+pub fn invert_diff_1(levels: &[f64], diff_pred_next: &[f64]) -> Vec<f64> {
+    assert_eq!(
+        levels.len().saturating_sub(1),
+        diff_pred_next.len(),
+        "length mismatch"
+    );
+    (0..diff_pred_next.len())
+        .map(|i| levels[i] + diff_pred_next[i])
+        .collect()
+}
+
+/// ย้อนการ difference d ครั้งของค่าพยากรณ์ที่ต่อท้าย levels
+/// (forecasts อยู่บนสเกล ∇^d ; คืนค่าบนสเกลเดิมความยาวเท่ากัน)
+pub fn integrate_n(levels: &[f64], d: usize, forecasts: &[f64]) -> Vec<f64> {
+    let mut out = forecasts.to_vec();
+    for k in (0..d).rev() {
+        let last = diff_n(levels, k).last().copied().unwrap_or(0.0);
+        let mut acc = last;
+        for v in out.iter_mut() {
+            acc += *v;
+            *v = acc;
+        }
+    }
+    out
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::module::model::differencing::cal_differencing;
+    use crate::module::model::differencing::{cal_differencing, diff_n, integrate_n};
+
+    #[test]
+    fn test_integrate_inverts_diff_n() {
+        let x: Vec<f64> = (0..12).map(|t| 0.5 * (t * t) as f64 + t as f64).collect();
+        for d in 0..=2 {
+            let (hist, future) = x.split_at(8);
+            let future_diff = diff_n(&x, d).split_off(8 - d);
+            let back = integrate_n(hist, d, &future_diff);
+            for (a, b) in back.iter().zip(future) {
+                assert!((a - b).abs() < 1e-9, "d={d}: {a} vs {b}");
+            }
+        }
+    }
 
     #[test]
     fn test_diffencing() {
@@ -1,23 +1,237 @@
+use crate::module::model::streaming::Indicator;
+
+/// สูตร EMA ที่เลือกได้ ; แต่ละแบบให้ค่าไม่เท่ากัน จึงต้องเลือกให้ตรงกับที่ใช้บนกราฟ
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum EmaConvention {
+    /// แบบเดิมของ repo: alpha = 2.105 / n, seed ด้วย SMA ของ n แท่งแรก
+    #[default]
+    Legacy,
+    /// TradingView ta.ema: alpha = 2 / (n + 1), seed ด้วยค่าแรก
+    Pine,
+    /// pandas ewm(span=n, adjust=...) ; adjust=false เท่ากับ Pine
+    Pandas { adjust: bool },
+    /// Wilder / ta.rma: alpha = 1 / n, seed ด้วย SMA
+    Wilder,
+    /// alpha กำหนดเอง (0, 1] ; sma_seed = false คือ seed ด้วยค่าแรก
+    Custom { alpha: f64, sma_seed: bool },
+}
+
+impl EmaConvention {
+    pub fn alpha(self, period: usize) -> f64 {
+        let n = period as f64;
+        match self {
+            Self::Legacy => 2.105 / n,
+            Self::Pine | Self::Pandas { .. } => 2.0 / (n + 1.0),
+            Self::Wilder => 1.0 / n,
+            Self::Custom { alpha, .. } => alpha,
+        }
+    }
+
+    fn sma_seed(self) -> bool {
+        match self {
+            Self::Legacy | Self::Wilder => true,
+            Self::Custom { sma_seed, .. } => sma_seed,
+            Self::Pine | Self::Pandas { .. } => false,
+        }
+    }
+}
+
 pub fn ema_series(data: &[f64], period: usize) -> Vec<Option<f64>> {
-    let n = data.len();
-    let mut out = vec![None; n];
-    // ข้อมูลไม่พอ
-    if period == 0 || n < period {
-        return out;
+    ema_series_with(data, period, EmaConvention::Legacy)
+}
+
+/// แบบ seed ด้วย SMA จะเป็น None จนครบ period แท่ง ; แบบอื่นมีค่าตั้งแต่แท่งแรก
+pub fn ema_series_with(data: &[f64], period: usize, convention: EmaConvention) -> Vec<Option<f64>> {
+    Ema::new(period, convention).run(data.iter().copied())
+}
+
+/// EMA แบบ streaming ; ema_series_with คือการป้อนทีละแท่งด้วย struct นี้
+#[derive(Clone, Debug)]
+pub struct Ema {
+    period: usize,
+    convention: EmaConvention,
+    alpha: f64,
+    count: usize,
+    /// ผลรวมช่วง seed (SMA) หรือตัวเศษของ pandas adjust
+    sum: f64,
+    /// ผลรวมน้ำหนักของ pandas adjust
+    weight: f64,
+    value: Option<f64>,
+}
+
+impl Ema {
+    pub fn new(period: usize, convention: EmaConvention) -> Self {
+        Self {
+            period,
+            convention,
+            alpha: convention.alpha(period),
+            count: 0,
+            sum: 0.0,
+            weight: 0.0,
+            value: None,
+        }
     }
 
-    // ค่า ema ตอนเริ่มต้น
-    let mut ema = data[..period].iter().sum::<f64>() / period as f64;
-    out[period - 1] = Some(ema);
+    pub fn value(&self) -> Option<f64> {
+        self.value
+    }
+}
+
+impl Indicator for Ema {
+    type Input = f64;
+
+    fn update(&mut self, x: f64) -> Option<f64> {
+        if self.period == 0 {
+            return None;
+        }
+        self.count += 1;
+        let alpha = self.alpha;
+        if let EmaConvention::Pandas { adjust: true } = self.convention {
+            // ถ่วงน้ำหนัก (1-α)^i แล้วหารด้วยผลรวมน้ำหนัก
+            self.sum = x + (1.0 - alpha) * self.sum;
+            self.weight = 1.0 + (1.0 - alpha) * self.weight;
+            self.value = Some(self.sum / self.weight);
+        } else if self.convention.sma_seed() && self.count <= self.period {
+            // ค่า ema ตอนเริ่มต้น
+            self.sum += x;
+            if self.count == self.period {
+                self.value = Some(self.sum / self.period as f64);
+            }
+        } else {
+            self.value = Some(match self.value {
+                Some(ema) => alpha * x + (1.0 - alpha) * ema,
+                None => x,
+            });
+        }
+        self.value
+    }
+
+    fn reset(&mut self) {
+        *self = Self::new(self.period, self.convention);
+    }
+}
 
-    // smooth graph
-    let alpha = 2.105 / (period as f64);
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // ค่าอ้างอิงคำนวณด้วยเศษส่วนแบบ exact (period = 4)
+    const DATA: [f64; 10] = [
+        22.27, 22.19, 22.08, 22.17, 22.18, 22.13, 22.23, 22.43, 22.24, 22.29,
+    ];
+
+    fn assert_golden(convention: EmaConvention, golden: &[Option<f64>]) {
+        let got = ema_series_with(&DATA, 4, convention);
+        assert_eq!(got.len(), golden.len());
+        for (t, (g, r)) in got.iter().zip(golden).enumerate() {
+            match (g, r) {
+                (Some(g), Some(r)) => {
+                    assert!((g - r).abs() < 1e-10, "{convention:?} t={t}: {g} vs {r}")
+                }
+                (None, None) => {}
+                _ => panic!("{convention:?} t={t}: {g:?} vs {r:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_legacy_and_wilder_golden() {
+        assert_golden(
+            EmaConvention::Legacy,
+            &[
+                None,
+                None,
+                None,
+                Some(22.1775),
+                Some(22.178815625),
+                Some(22.15312640234375),
+                Some(22.193581133110353),
+                Some(22.31799656181103),
+                Some(22.276950871157975),
+                Some(22.28381797521109),
+            ],
+        );
+        assert_eq!(
+            ema_series(&DATA, 4),
+            ema_series_with(&DATA, 4, EmaConvention::Legacy)
+        );
+        assert_golden(
+            EmaConvention::Wilder,
+            &[
+                None,
+                None,
+                None,
+                Some(22.1775),
+                Some(22.178125),
+                Some(22.16609375),
+                Some(22.1820703125),
+                Some(22.244052734375),
+                Some(22.24303955078125),
+                Some(22.254779663085937),
+            ],
+        );
+    }
+
+    #[test]
+    fn test_streaming_matches_after_reset() {
+        let mut ema = Ema::new(4, EmaConvention::Pine);
+        let first = ema.run(DATA);
+        assert_eq!(ema.value(), first[9]);
+        ema.reset();
+        assert_eq!(ema.run(DATA), first);
+    }
 
-    // ema ตัวต่อไป
-    out[period - 1] = Some(ema);
-    for t in period..n {
-        ema = alpha * data[t] + (1.0 - alpha) * ema;
-        out[t] = Some(ema);
+    #[test]
+    fn test_pine_and_pandas_golden() {
+        let pine = [
+            22.27,
+            22.238,
+            22.1748,
+            22.17288,
+            22.175728,
+            22.1574368,
+            22.18646208,
+            22.283877248,
+            22.2663263488,
+            22.27579580928,
+        ]
+        .map(Some);
+        assert_golden(EmaConvention::Pine, &pine);
+        assert_golden(EmaConvention::Pandas { adjust: false }, &pine);
+        assert_golden(
+            EmaConvention::Pandas { adjust: true },
+            &[
+                22.27,
+                22.22,
+                22.14857142857143,
+                22.15841911764706,
+                22.167779319916725,
+                22.151928034371643,
+                22.18405620374516,
+                22.284114314280952,
+                22.266288949965915,
+                22.275831067515465,
+            ]
+            .map(Some),
+        );
+        assert_golden(
+            EmaConvention::Custom {
+                alpha: 0.3,
+                sma_seed: false,
+            },
+            &[
+                22.27,
+                22.246,
+                22.1962,
+                22.18834,
+                22.185838,
+                22.1690866,
+                22.18736062,
+                22.260152434,
+                22.2541067038,
+                22.26487469266,
+            ]
+            .map(Some),
+        );
     }
-    out
 }
@@ -0,0 +1,129 @@
+// Butterworth IIR ด้วย bilinear transform (prewarp ที่ความถี่ตัด)
+// ระบุความถี่ตัดเป็น period (bars ต่อรอบ) เหมือน filter ของ Ehlers
+
+use std::f64::consts::PI;
+
+use crate::module::model::filter::iir::{Biquad, IirFilter};
+
+#[derive(Clone, Copy)]
+enum Pass {
+    Low,
+    High,
+}
+
+fn design(order: usize, period: f64, pass: Pass) -> Option<IirFilter> {
+    // ตัดที่ต่ำกว่า Nyquist เท่านั้น (period > 2)
+    if order == 0 || period.is_nan() || period <= 2.0 {
+        return None;
+    }
+    let k = (PI / period).tan();
+    let k2 = k * k;
+    let mut sections = Vec::with_capacity(order.div_ceil(2));
+    for i in 0..order / 2 {
+        // 1/Q ของคู่ pole ที่ i
+        let inv_q = 2.0 * (PI * (2 * i + 1) as f64 / (2 * order) as f64).sin();
+        let norm = 1.0 / (1.0 + k * inv_q + k2);
+        let a1 = 2.0 * (k2 - 1.0) * norm;
+        let a2 = (1.0 - k * inv_q + k2) * norm;
+        let b = match pass {
+            Pass::Low => [k2 * norm, 2.0 * k2 * norm, k2 * norm],
+            Pass::High => [norm, -2.0 * norm, norm],
+        };
+        sections.push(Biquad::new(b, a1, a2));
+    }
+    if order % 2 == 1 {
+        let norm = 1.0 / (1.0 + k);
+        let a1 = (k - 1.0) * norm;
+        let b = match pass {
+            Pass::Low => [k * norm, k * norm, 0.0],
+            Pass::High => [norm, -norm, 0.0],
+        };
+        sections.push(Biquad::new(b, a1, 0.0));
+    }
+    Some(IirFilter::new(sections))
+}
+
+/// low-pass: ผ่านรอบที่ยาวกว่า period ; |H| = -3 dB ที่ period
+pub fn butterworth_low_pass(order: usize, period: f64) -> Option<IirFilter> {
+    design(order, period, Pass::Low)
+}
+
+/// high-pass: ผ่านรอบที่สั้นกว่า period
+pub fn butterworth_high_pass(order: usize, period: f64) -> Option<IirFilter> {
+    design(order, period, Pass::High)
+}
+
+/// band-pass = high-pass(long_period) ต่อด้วย low-pass(short_period) ; เก็บรอบระหว่าง short กับ long
+pub fn butterworth_band_pass(
+    order: usize,
+    short_period: f64,
+    long_period: f64,
+) -> Option<IirFilter> {
+    if long_period.is_nan() || long_period <= short_period {
+        return None;
+    }
+    Some(
+        butterworth_high_pass(order, long_period)?
+            .then(&butterworth_low_pass(order, short_period)?),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_butterworth_cutoff_and_rolloff() {
+        for order in 1..=5 {
+            let lp = butterworth_low_pass(order, 20.0).unwrap();
+            let hp = butterworth_high_pass(order, 20.0).unwrap();
+            assert_eq!(lp.sections.len(), order.div_ceil(2));
+            assert!((lp.dc_gain() - 1.0).abs() < 1e-12);
+            assert!(hp.dc_gain().abs() < 1e-12);
+            // -3 dB ที่ความถี่ตัด และ LP + HP แบบ power complementary
+            let (l, h) = (
+                lp.frequency_response(1.0 / 20.0).norm(),
+                hp.frequency_response(1.0 / 20.0).norm(),
+            );
+            assert!((l - 0.5f64.sqrt()).abs() < 1e-9, "order {order}: {l}");
+            assert!((h - 0.5f64.sqrt()).abs() < 1e-9);
+            let f = 0.13;
+            let (l, h) = (
+                lp.frequency_response(f).norm(),
+                hp.frequency_response(f).norm(),
+            );
+            assert!((l * l + h * h - 1.0).abs() < 1e-9);
+        }
+        // order สูงกว่าตัดชันกว่า
+        let far = 1.0 / 5.0;
+        let a2 = butterworth_low_pass(2, 20.0)
+            .unwrap()
+            .frequency_response(far)
+            .norm();
+        let a4 = butterworth_low_pass(4, 20.0)
+            .unwrap()
+            .frequency_response(far)
+            .norm();
+        assert!(a4 < a2 * a2 * 1.5);
+        assert!(butterworth_low_pass(2, 2.0).is_none());
+        assert!(butterworth_band_pass(2, 40.0, 10.0).is_none());
+    }
+
+    #[test]
+    fn test_band_pass_and_filtfilt_zero_phase() {
+        let bp = butterworth_band_pass(2, 10.0, 40.0).unwrap();
+        assert!(bp.dc_gain().abs() < 1e-12);
+        assert!(bp.frequency_response(1.0 / 20.0).norm() > 0.8);
+
+        // sine period 50 ผ่าน low-pass period 10: filtfilt ไม่มี lag แต่ filter ธรรมดามี
+        let x: Vec<f64> = (0..500)
+            .map(|t| (2.0 * PI * t as f64 / 50.0).sin())
+            .collect();
+        let lp = butterworth_low_pass(4, 10.0).unwrap();
+        let zf = lp.filtfilt(&x);
+        let causal = lp.filter(&x);
+        let err = |y: &[f64]| (100..400).map(|t| (y[t] - x[t]).abs()).fold(0.0, f64::max);
+        assert!(err(&zf) < 0.01, "{}", err(&zf));
+        assert!(err(&causal) > 0.1);
+    }
+}
@@ -0,0 +1,100 @@
+// filter ของ John Ehlers (Cycle Analytics for Traders) เขียนเป็น biquad
+// super smoother = 2-pole low-pass ที่ไม่มี overshoot มาก, roofing = high-pass + super smoother,
+// decycler = price - high-pass (เหลือแต่แนวโน้ม, lag น้อยกว่า SMA ที่ period เท่ากัน)
+
+use std::f64::consts::{PI, SQRT_2};
+
+use crate::module::model::filter::iir::{Biquad, IirFilter};
+
+/// super smoother 2-pole: c1·(x + x[1])/2 + c2·y[1] + c3·y[2]
+pub fn super_smoother(period: f64) -> Option<IirFilter> {
+    if period.is_nan() || period <= 2.0 {
+        return None;
+    }
+    let a = (-SQRT_2 * PI / period).exp();
+    let c2 = 2.0 * a * (SQRT_2 * PI / period).cos();
+    let c3 = -a * a;
+    let c1 = 1.0 - c2 - c3;
+    Some(IirFilter::new(vec![Biquad::new(
+        [c1 / 2.0, c1 / 2.0, 0.0],
+        -c2,
+        -c3,
+    )]))
+}
+
+/// high-pass 2-pole ของ Ehlers (ใช้ใน roofing filter)
+pub fn high_pass_2pole(period: f64) -> Option<IirFilter> {
+    if period.is_nan() || period <= 2.0 {
+        return None;
+    }
+    let w = SQRT_2 / 2.0 * 2.0 * PI / period;
+    let alpha = (w.cos() + w.sin() - 1.0) / w.cos();
+    let g = (1.0 - alpha / 2.0).powi(2);
+    Some(IirFilter::new(vec![Biquad::new(
+        [g, -2.0 * g, g],
+        -2.0 * (1.0 - alpha),
+        (1.0 - alpha).powi(2),
+    )]))
+}
+
+/// roofing filter: ตัดแนวโน้มที่ยาวกว่า hp_period และ noise ที่สั้นกว่า lp_period (ค่าแกว่งรอบศูนย์)
+pub fn roofing(hp_period: f64, lp_period: f64) -> Option<IirFilter> {
+    if hp_period.is_nan() || hp_period <= lp_period {
+        return None;
+    }
+    Some(high_pass_2pole(hp_period)?.then(&super_smoother(lp_period)?))
+}
+
+/// simple decycler: x - HP1(x) โดย HP1 เป็น high-pass 1-pole
+/// 1 - H_hp ยุบได้เป็น low-pass 1-pole: (α/2)(x + x[1]) + (1 - α)·y[1]
+pub fn decycler(period: f64) -> Option<IirFilter> {
+    if period.is_nan() || period <= 2.0 {
+        return None;
+    }
+    let w = 2.0 * PI / period;
+    let alpha = (w.cos() + w.sin() - 1.0) / w.cos();
+    Some(IirFilter::new(vec![Biquad::new(
+        [alpha / 2.0, alpha / 2.0, 0.0],
+        -(1.0 - alpha),
+        0.0,
+    )]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ehlers_filters() {
+        let ss = super_smoother(10.0).unwrap();
+        assert!((ss.dc_gain() - 1.0).abs() < 1e-12);
+        // zero ที่ Nyquist จาก (x + x[1]) / 2
+        assert!(ss.frequency_response(0.5).norm() < 1e-12);
+
+        let hp = high_pass_2pole(48.0).unwrap();
+        assert!(hp.dc_gain().abs() < 1e-12);
+        assert!(hp.frequency_response(0.25).norm() > 0.95);
+
+        let roof = roofing(48.0, 10.0).unwrap();
+        assert_eq!(roof.sections.len(), 2);
+        let mid = roof.frequency_response(1.0 / 20.0).norm();
+        assert!(mid > roof.frequency_response(1.0 / 200.0).norm());
+        assert!(mid > roof.frequency_response(1.0 / 3.0).norm());
+        assert!(roofing(10.0, 48.0).is_none());
+
+        // decycler = x - HP1 ; เทียบกับสูตร HP1 โดยตรง
+        let x = [10.0, 10.5, 10.2, 11.0, 11.4, 11.1, 12.0];
+        let alpha = {
+            let w = 2.0 * PI / 20.0;
+            (w.cos() + w.sin() - 1.0) / w.cos()
+        };
+        let mut hp1 = vec![0.0; x.len()];
+        for t in 1..x.len() {
+            hp1[t] = (1.0 - alpha / 2.0) * (x[t] - x[t - 1]) + (1.0 - alpha) * hp1[t - 1];
+        }
+        let dec = decycler(20.0).unwrap().filter(&x);
+        for t in 0..x.len() {
+            assert!((dec[t] - (x[t] - hp1[t])).abs() < 1e-12, "t={t}");
+        }
+    }
+}
@@ -0,0 +1,243 @@
+// IIR filter แบบ cascade ของ second-order sections (biquad, direct form II transposed)
+// ความถี่ทั้งหมดเป็น cycles/bar (0 ถึง 0.5 = Nyquist) ; period = 1 / freq
+
+use std::f64::consts::PI;
+
+use nalgebra::Complex;
+
+use crate::module::model::streaming::Indicator;
+
+/// H(z) = (b0 + b1 z⁻¹ + b2 z⁻²) / (1 + a1 z⁻¹ + a2 z⁻²) ; a[0] = 1 เสมอ
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Biquad {
+    pub b: [f64; 3],
+    pub a: [f64; 3],
+}
+
+impl Biquad {
+    pub fn new(b: [f64; 3], a1: f64, a2: f64) -> Self {
+        Self {
+            b,
+            a: [1.0, a1, a2],
+        }
+    }
+
+    /// gain ที่ DC (z = 1)
+    pub fn dc_gain(&self) -> f64 {
+        self.b.iter().sum::<f64>() / self.a.iter().sum::<f64>()
+    }
+
+    fn response(&self, z1: Complex<f64>) -> Complex<f64> {
+        let z2 = z1 * z1;
+        let num = z1 * self.b[1] + z2 * self.b[2] + self.b[0];
+        let den = z1 * self.a[1] + z2 * self.a[2] + self.a[0];
+        num / den
+    }
+
+    /// state (z1, z2) ที่ทำให้ output คงที่เมื่อ input = 1 ตลอด
+    fn unit_steady_state(&self) -> [f64; 2] {
+        let g = self.dc_gain();
+        let z2 = self.b[2] - self.a[2] * g;
+        let z1 = self.b[1] - self.a[1] * g + z2;
+        [z1, z2]
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct IirFilter {
+    pub sections: Vec<Biquad>,
+}
+
+#[derive(Clone, Debug)]
+pub struct FrequencyResponse {
+    /// cycles/bar
+    pub freq: Vec<f64>,
+    pub magnitude: Vec<f64>,
+    pub magnitude_db: Vec<f64>,
+    /// phase (rad) แบบ unwrap แล้ว
+    pub phase: Vec<f64>,
+    /// phase delay = -φ/ω (bars) ; ที่ DC ใช้ความชันของ phase แทน
+    pub delay: Vec<f64>,
+}
+
+impl IirFilter {
+    pub fn new(sections: Vec<Biquad>) -> Self {
+        Self { sections }
+    }
+
+    /// ต่อ filter สองตัวแบบอนุกรม
+    pub fn then(mut self, other: &IirFilter) -> Self {
+        self.sections.extend_from_slice(&other.sections);
+        self
+    }
+
+    pub fn dc_gain(&self) -> f64 {
+        self.sections.iter().map(Biquad::dc_gain).product()
+    }
+
+    /// state เริ่มต้นต่อ section สำหรับ input คงที่ = 1 (เหมือน lfilter_zi / sosfilt_zi)
+    pub fn steady_state(&self) -> Vec<[f64; 2]> {
+        let mut gain = 1.0;
+        self.sections
+            .iter()
+            .map(|s| {
+                let [z1, z2] = s.unit_steady_state();
+                let zi = [z1 * gain, z2 * gain];
+                gain *= s.dc_gain();
+                zi
+            })
+            .collect()
+    }
+
+    /// ค่า H(e^{j2πf}) ที่ความถี่ f (cycles/bar)
+    pub fn frequency_response(&self, freq: f64) -> Complex<f64> {
+        let z1 = Complex::from_polar(1.0, -2.0 * PI * freq);
+        self.sections
+            .iter()
+            .fold(Complex::new(1.0, 0.0), |h, s| h * s.response(z1))
+    }
+
+    /// response ที่ n_points ความถี่จาก 0 ถึง Nyquist
+    pub fn response(&self, n_points: usize) -> FrequencyResponse {
+        let n_points = n_points.max(2);
+        let freq: Vec<f64> = (0..n_points)
+            .map(|i| 0.5 * i as f64 / (n_points - 1) as f64)
+            .collect();
+        let h: Vec<Complex<f64>> = freq.iter().map(|&f| self.frequency_response(f)).collect();
+        let magnitude: Vec<f64> = h.iter().map(|c| c.norm()).collect();
+        let magnitude_db = magnitude
+            .iter()
+            .map(|m| 20.0 * m.max(1e-300).log10())
+            .collect();
+
+        let mut phase = Vec::with_capacity(n_points);
+        let mut offset = 0.0;
+        let mut prev = h[0].arg();
+        for (i, c) in h.iter().enumerate() {
+            let raw = c.arg();
+            if i > 0 {
+                let d = raw - prev;
+                if d > PI {
+                    offset -= 2.0 * PI;
+                } else if d < -PI {
+                    offset += 2.0 * PI;
+                }
+            }
+            prev = raw;
+            phase.push(raw + offset);
+        }
+
+        let delay = freq
+            .iter()
+            .enumerate()
+            .map(|(i, &f)| {
+                if i == 0 {
+                    -(phase[1] - phase[0]) / (2.0 * PI * freq[1])
+                } else {
+                    -phase[i] / (2.0 * PI * f)
+                }
+            })
+            .collect();
+
+        FrequencyResponse {
+            freq,
+            magnitude,
+            magnitude_db,
+            phase,
+            delay,
+        }
+    }
+
+    /// filter แบบ causal ; state เริ่มที่ steady state ของค่าแรก (ไม่มี transient จาก 0)
+    pub fn filter(&self, x: &[f64]) -> Vec<f64> {
+        let mut stream = IirStream::new(self.clone());
+        x.iter().filter_map(|&v| stream.update(v)).collect()
+    }
+
+    /// zero-phase forward-backward (ใช้ข้อมูลอนาคต -> สำหรับวิเคราะห์ย้อนหลังเท่านั้น)
+    /// ขยายปลายทั้งสองข้างแบบ odd reflection เหมือน filtfilt ของ scipy
+    pub fn filtfilt(&self, x: &[f64]) -> Vec<f64> {
+        let n = x.len();
+        if n < 2 {
+            return x.to_vec();
+        }
+        let pad = (3 * (2 * self.sections.len() + 1)).min(n - 1);
+        let mut ext = Vec::with_capacity(n + 2 * pad);
+        ext.extend((1..=pad).rev().map(|i| 2.0 * x[0] - x[i]));
+        ext.extend_from_slice(x);
+        ext.extend((1..=pad).map(|i| 2.0 * x[n - 1] - x[n - 1 - i]));
+
+        let mut y = self.filter(&ext);
+        y.reverse();
+        let mut y = self.filter(&y);
+        y.reverse();
+        y[pad..pad + n].to_vec()
+    }
+}
+
+/// IIR แบบ streaming: update ทีละแท่งใน O(sections)
+#[derive(Clone, Debug)]
+pub struct IirStream {
+    filter: IirFilter,
+    state: Option<Vec<[f64; 2]>>,
+}
+
+impl IirStream {
+    pub fn new(filter: IirFilter) -> Self {
+        Self {
+            filter,
+            state: None,
+        }
+    }
+}
+
+impl Indicator for IirStream {
+    type Input = f64;
+
+    fn update(&mut self, x: f64) -> Option<f64> {
+        let filter = &self.filter;
+        let state = self.state.get_or_insert_with(|| {
+            filter
+                .steady_state()
+                .into_iter()
+                .map(|[z1, z2]| [z1 * x, z2 * x])
+                .collect()
+        });
+        let mut v = x;
+        for (s, z) in filter.sections.iter().zip(state.iter_mut()) {
+            let y = s.b[0] * v + z[0];
+            z[0] = s.b[1] * v - s.a[1] * y + z[1];
+            z[1] = s.b[2] * v - s.a[2] * y;
+            v = y;
+        }
+        Some(v)
+    }
+
+    fn reset(&mut self) {
+        self.state = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_steady_state_start_and_response() {
+        // 1-pole low-pass y = 0.2x + 0.8y[1]
+        let f = IirFilter::new(vec![Biquad::new([0.2, 0.0, 0.0], -0.8, 0.0)]);
+        assert!((f.dc_gain() - 1.0).abs() < 1e-12);
+        // เริ่มที่ค่าแรกเลย ไม่ไต่ขึ้นจาก 0
+        let y = f.filter(&[5.0, 5.0, 5.0]);
+        assert!(y.iter().all(|v| (v - 5.0).abs() < 1e-12));
+        let y = f.filter(&[1.0, 2.0]);
+        assert!((y[1] - (0.2 * 2.0 + 0.8 * 1.0)).abs() < 1e-12);
+
+        // |H| ที่ Nyquist = 0.2 / 1.8 ; phase ที่ DC = 0
+        let r = f.response(513);
+        assert!((r.magnitude[512] - 0.2 / 1.8).abs() < 1e-12);
+        assert!(r.phase[0].abs() < 1e-12);
+        // delay ที่ DC ของ 1-pole = a / (1 - a) = 4 bars
+        assert!((r.delay[0] - 4.0).abs() < 0.01);
+    }
+}
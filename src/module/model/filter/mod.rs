@@ -0,0 +1,4 @@
+pub mod butterworth;
+pub mod ehlers;
+pub mod iir;
+pub mod spec;
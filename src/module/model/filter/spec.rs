@@ -0,0 +1,164 @@
+// filter ที่เลือกได้สำหรับ Strategy (Copy ได้) และสร้าง series ตาม warm-up convention ของ ema_series
+
+use crate::module::model::{
+    filter::{
+        butterworth::{butterworth_band_pass, butterworth_high_pass, butterworth_low_pass},
+        ehlers::{decycler, roofing, super_smoother},
+        iir::IirFilter,
+    },
+    technical::moving_average::mask_warmup,
+};
+
+/// period ทั้งหมดเป็นจำนวนแท่งต่อรอบของความถี่ตัด
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FilterSpec {
+    ButterworthLowPass {
+        order: usize,
+        period: f64,
+    },
+    ButterworthHighPass {
+        order: usize,
+        period: f64,
+    },
+    ButterworthBandPass {
+        order: usize,
+        short_period: f64,
+        long_period: f64,
+    },
+    SuperSmoother {
+        period: f64,
+    },
+    Roofing {
+        hp_period: f64,
+        lp_period: f64,
+    },
+    Decycler {
+        period: f64,
+    },
+}
+
+impl FilterSpec {
+    /// None ถ้า parameter ใช้ไม่ได้ (period ≤ 2, order = 0, ช่วง band กลับด้าน)
+    pub fn build(&self) -> Option<IirFilter> {
+        match *self {
+            Self::ButterworthLowPass { order, period } => butterworth_low_pass(order, period),
+            Self::ButterworthHighPass { order, period } => butterworth_high_pass(order, period),
+            Self::ButterworthBandPass {
+                order,
+                short_period,
+                long_period,
+            } => butterworth_band_pass(order, short_period, long_period),
+            Self::SuperSmoother { period } => super_smoother(period),
+            Self::Roofing {
+                hp_period,
+                lp_period,
+            } => roofing(hp_period, lp_period),
+            Self::Decycler { period } => decycler(period),
+        }
+    }
+
+    /// true = output แกว่งรอบศูนย์ (high/band-pass) -> สัญญาณคือ y > 0
+    /// false = เส้นแนวโน้มบนราคา -> สัญญาณคือ y ชันขึ้น
+    pub fn is_oscillator(&self) -> bool {
+        matches!(
+            self,
+            Self::ButterworthHighPass { .. }
+                | Self::ButterworthBandPass { .. }
+                | Self::Roofing { .. }
+        )
+    }
+
+    /// ซ่อนแท่งแรก ๆ ราวหนึ่งรอบของ period ที่ยาวที่สุดให้ transient ผ่านไปก่อน
+    pub fn warmup(&self) -> usize {
+        let period = match *self {
+            Self::ButterworthLowPass { period, .. }
+            | Self::ButterworthHighPass { period, .. }
+            | Self::SuperSmoother { period }
+            | Self::Decycler { period } => period,
+            Self::ButterworthBandPass { long_period, .. } => long_period,
+            Self::Roofing { hp_period, .. } => hp_period,
+        };
+        period.max(0.0).ceil() as usize
+    }
+
+    pub fn label(&self) -> String {
+        match *self {
+            Self::ButterworthLowPass { order, period } => {
+                format!("Butterworth LP(order={order}, period={period})")
+            }
+            Self::ButterworthHighPass { order, period } => {
+                format!("Butterworth HP(order={order}, period={period})")
+            }
+            Self::ButterworthBandPass {
+                order,
+                short_period,
+                long_period,
+            } => format!("Butterworth BP(order={order}, {short_period}-{long_period})"),
+            Self::SuperSmoother { period } => format!("Super smoother({period})"),
+            Self::Roofing {
+                hp_period,
+                lp_period,
+            } => format!("Roofing({hp_period}, {lp_period})"),
+            Self::Decycler { period } => format!("Decycler({period})"),
+        }
+    }
+}
+
+/// filter แบบ causal แล้วคืน Vec<Option<f64>> (None ช่วง warm-up)
+pub fn filter_series(data: &[f64], spec: FilterSpec) -> Vec<Option<f64>> {
+    match spec.build() {
+        Some(f) => mask_warmup(
+            f.filter(data).into_iter().map(Some).collect(),
+            spec.warmup(),
+        ),
+        None => vec![None; data.len()],
+    }
+}
+
+/// สัญญาณซื้อจาก filter ตาม is_oscillator
+pub fn filter_signal(data: &[f64], spec: FilterSpec) -> Vec<Option<bool>> {
+    let y = filter_series(data, spec);
+    (0..y.len())
+        .map(|t| {
+            let v = y[t]?;
+            if spec.is_oscillator() {
+                Some(v > 0.0)
+            } else {
+                Some(v > y[t.checked_sub(1)?]?)
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_filter_signal_follows_trend_and_cycle() {
+        let n = 300;
+        let trend: Vec<f64> = (0..n).map(|t| 100.0 + 0.2 * t as f64).collect();
+        let spec = FilterSpec::SuperSmoother { period: 10.0 };
+        let s = filter_signal(&trend, spec);
+        // slope ต้องใช้ค่าก่อนหน้าด้วย -> เริ่มหลัง warm-up หนึ่งแท่ง
+        assert!(s[10].is_none() && s[11].is_some());
+        assert!(s[11..].iter().all(|v| *v == Some(true)));
+
+        // cycle period 20 ใน band 10-40: สัญญาณเปลี่ยนทิศตามรอบ
+        let cycle: Vec<f64> = (0..n)
+            .map(|t| 100.0 + (2.0 * std::f64::consts::PI * t as f64 / 20.0).sin())
+            .collect();
+        let spec = FilterSpec::ButterworthBandPass {
+            order: 2,
+            short_period: 10.0,
+            long_period: 40.0,
+        };
+        assert!(spec.is_oscillator());
+        let s = filter_signal(&cycle, spec);
+        let ups = s[100..].iter().filter(|v| **v == Some(true)).count();
+        assert!((80..=120).contains(&ups), "{ups}");
+
+        let bad = FilterSpec::Decycler { period: 1.0 };
+        assert!(filter_series(&trend, bad).iter().all(Option::is_none));
+    }
+}
@@ -0,0 +1,490 @@
+// GARCH-family สำหรับ conditional variance ของ residual / log return (ε_t = √h_t z_t, z ~ N(0,1))
+//   GARCH(1,1): h_t = ω + α ε²_{t-1} + β h_{t-1}
+//   GJR-GARCH : h_t = ω + (α + γ·1[ε_{t-1} < 0]) ε²_{t-1} + β h_{t-1}
+//   EGARCH    : ln h_t = ω + α(|z_{t-1}| - √(2/π)) + γ z_{t-1} + β ln h_{t-1}
+// ประมาณด้วย Gaussian MLE บนข้อมูลที่ standardize แล้ว (พารามิเตอร์สเกล O(1)) แล้วแปลงกลับ
+
+use crate::module::model::{
+    arma::{ArmaModel, ForecastStep, arma_residuals, fit_arma_with_ic, psi_weights},
+    util::{
+        numdiff::{covariance_from_hessian, numerical_hessian},
+        optimize::{NelderMead, Optimizer},
+    },
+};
+
+const SQRT_2_OVER_PI: f64 = 0.7978845608028654;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GarchKind {
+    Garch,
+    Gjr,
+    Egarch,
+}
+
+impl GarchKind {
+    fn n_params(self) -> usize {
+        match self {
+            GarchKind::Garch => 3,
+            GarchKind::Gjr | GarchKind::Egarch => 4,
+        }
+    }
+
+    fn initial(self) -> Vec<f64> {
+        match self {
+            GarchKind::Garch => vec![0.05, 0.05, 0.9],
+            GarchKind::Gjr => vec![0.05, 0.03, 0.9, 0.06],
+            GarchKind::Egarch => vec![0.001, 0.1, 0.95, -0.05],
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct GarchParams {
+    pub omega: f64,
+    pub alpha: f64,
+    pub beta: f64,
+    /// leverage term (0 สำหรับ GARCH ธรรมดา)
+    pub gamma: f64,
+}
+
+impl GarchParams {
+    fn from_vec(x: &[f64]) -> Self {
+        Self {
+            omega: x[0],
+            alpha: x[1],
+            beta: x[2],
+            gamma: x.get(3).copied().unwrap_or(0.0),
+        }
+    }
+
+    fn to_vec(self, kind: GarchKind) -> Vec<f64> {
+        let mut v = vec![self.omega, self.alpha, self.beta, self.gamma];
+        v.truncate(kind.n_params());
+        v
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct GarchModel {
+    pub kind: GarchKind,
+    pub params: GarchParams,
+    /// เรียงตาม [ω, α, β, γ]
+    pub std_errors: Vec<f64>,
+    pub loglik: f64,
+    pub aic: f64,
+    pub bic: f64,
+    /// variance[t] = Var(ε_t | ข้อมูลถึง t-1) ; ตัวสุดท้าย (index n) คือพยากรณ์ 1 ก้าว
+    pub variance: Vec<f64>,
+}
+
+fn feasible(kind: GarchKind, p: &GarchParams) -> bool {
+    match kind {
+        GarchKind::Garch => {
+            p.omega > 0.0 && p.alpha >= 0.0 && p.beta >= 0.0 && p.alpha + p.beta < 1.0
+        }
+        GarchKind::Gjr => {
+            p.omega > 0.0
+                && p.alpha >= 0.0
+                && p.alpha + p.gamma >= 0.0
+                && p.beta >= 0.0
+                && p.alpha + 0.5 * p.gamma + p.beta < 1.0
+        }
+        GarchKind::Egarch => p.beta.abs() < 1.0,
+    }
+}
+
+/// h_{t+1} จาก ε_t และ h_t
+fn next_variance(kind: GarchKind, p: &GarchParams, e: f64, prev: f64) -> f64 {
+    match kind {
+        GarchKind::Garch => p.omega + p.alpha * e * e + p.beta * prev,
+        GarchKind::Gjr => {
+            let lev = if e < 0.0 { p.gamma } else { 0.0 };
+            p.omega + (p.alpha + lev) * e * e + p.beta * prev
+        }
+        GarchKind::Egarch => {
+            let z = e / prev.sqrt();
+            (p.omega + p.alpha * (z.abs() - SQRT_2_OVER_PI) + p.gamma * z + p.beta * prev.ln())
+                .exp()
+        }
+    }
+}
+
+/// h_0..h_n โดยเริ่มจาก h_0 = ค่าเฉลี่ย ε²
+fn variance_path(eps: &[f64], kind: GarchKind, p: &GarchParams) -> Vec<f64> {
+    let n = eps.len();
+    let mut h = Vec::with_capacity(n + 1);
+    h.push(eps.iter().map(|e| e * e).sum::<f64>() / n.max(1) as f64);
+    for t in 0..n {
+        h.push(next_variance(kind, p, eps[t], h[t]));
+    }
+    h
+}
+
+fn gaussian_loglik(eps: &[f64], h: &[f64]) -> f64 {
+    let ln_2pi = (2.0 * std::f64::consts::PI).ln();
+    eps.iter()
+        .zip(h)
+        .map(|(e, v)| -0.5 * (ln_2pi + v.ln() + e * e / v))
+        .sum()
+}
+
+fn neg_loglik(eps: &[f64], kind: GarchKind, x: &[f64]) -> f64 {
+    let p = GarchParams::from_vec(x);
+    if !feasible(kind, &p) {
+        return f64::INFINITY;
+    }
+    let h = variance_path(eps, kind, &p);
+    if h.iter().any(|v| !(v.is_finite() && *v > 0.0)) {
+        return f64::INFINITY;
+    }
+    -gaussian_loglik(eps, &h)
+}
+
+impl GarchModel {
+    pub fn persistence(&self) -> f64 {
+        let p = &self.params;
+        match self.kind {
+            GarchKind::Garch => p.alpha + p.beta,
+            GarchKind::Gjr => p.alpha + 0.5 * p.gamma + p.beta,
+            GarchKind::Egarch => p.beta,
+        }
+    }
+
+    /// variance ระยะยาว (None ถ้า persistence >= 1)
+    pub fn unconditional_variance(&self) -> Option<f64> {
+        let pers = self.persistence();
+        if pers >= 1.0 {
+            return None;
+        }
+        Some(match self.kind {
+            GarchKind::Garch | GarchKind::Gjr => self.params.omega / (1.0 - pers),
+            GarchKind::Egarch => (self.params.omega / (1.0 - pers)).exp(),
+        })
+    }
+
+    /// √h_t ในตัวอย่าง (ยาว n + 1, ตัวสุดท้ายคือพยากรณ์แท่งถัดไป)
+    pub fn conditional_sd(&self) -> Vec<f64> {
+        self.variance.iter().map(|v| v.sqrt()).collect()
+    }
+
+    /// พยากรณ์ h_{n+1..n+k}
+    /// EGARCH ใช้ ln h_{n+k} = ω + β ln h_{n+k-1} (ไม่ปรับ Jensen)
+    pub fn forecast_variance(&self, k: usize) -> Vec<f64> {
+        let mut out = Vec::with_capacity(k);
+        let Some(&first) = self.variance.last() else {
+            return out;
+        };
+        let p = &self.params;
+        let pers = self.persistence();
+        let mut h = first;
+        for _ in 0..k {
+            out.push(h);
+            h = match self.kind {
+                GarchKind::Garch | GarchKind::Gjr => p.omega + pers * h,
+                GarchKind::Egarch => (p.omega + p.beta * h.ln()).exp(),
+            };
+        }
+        out
+    }
+}
+
+/// MLE ของ GARCH-family บน ε (ควรมี mean ≈ 0 เช่น residual ของ ARMA)
+pub fn fit_garch(eps: &[f64], kind: GarchKind) -> Option<GarchModel> {
+    fit_garch_with(eps, kind, &NelderMead::new(1500, 1e-9).with_restarts(1))
+}
+
+/// เหมือน fit_garch แต่เลือก optimizer เองได้ (objective เป็น ∞ นอกเงื่อนไข stationarity)
+pub fn fit_garch_with(
+    eps: &[f64],
+    kind: GarchKind,
+    optimizer: &dyn Optimizer,
+) -> Option<GarchModel> {
+    let n = eps.len();
+    if n < 20 {
+        return None;
+    }
+    let var = eps.iter().map(|e| e * e).sum::<f64>() / n as f64;
+    if !(var.is_finite() && var > 0.0) {
+        return None;
+    }
+    let scale = var.sqrt();
+    let z: Vec<f64> = eps.iter().map(|e| e / scale).collect();
+
+    let obj = |x: &[f64]| neg_loglik(&z, kind, x);
+    let xs = optimizer.minimize(&obj, &kind.initial()).x;
+    if !obj(&xs).is_finite() {
+        return None;
+    }
+
+    // แปลงกลับสเกลเดิม: GARCH/GJR ω·var, EGARCH ω + (1 - β) ln var
+    let ln_var = var.ln();
+    let mut params = GarchParams::from_vec(&xs);
+    match kind {
+        GarchKind::Garch | GarchKind::Gjr => params.omega *= var,
+        GarchKind::Egarch => params.omega += (1.0 - params.beta) * ln_var,
+    }
+
+    let hess = numerical_hessian(&obj, &xs);
+    let std_errors = match covariance_from_hessian(&hess) {
+        Some(cov) => (0..xs.len())
+            .map(|i| {
+                let v = match (kind, i) {
+                    (GarchKind::Garch | GarchKind::Gjr, 0) => cov[0][0] * var * var,
+                    // delta method ของ ω_s + (1 - β) L
+                    (GarchKind::Egarch, 0) => {
+                        cov[0][0] + ln_var * ln_var * cov[2][2] - 2.0 * ln_var * cov[0][2]
+                    }
+                    _ => cov[i][i],
+                };
+                v.max(0.0).sqrt()
+            })
+            .collect(),
+        None => vec![f64::NAN; xs.len()],
+    };
+
+    let variance = variance_path(eps, kind, &params);
+    let loglik = gaussian_loglik(eps, &variance);
+    let k = kind.n_params() as f64;
+    Some(GarchModel {
+        kind,
+        params,
+        std_errors,
+        loglik,
+        aic: -2.0 * loglik + 2.0 * k,
+        bic: -2.0 * loglik + k * (n as f64).ln(),
+        variance,
+    })
+}
+
+/// σ̂_{t|t-1} ของ returns[t] แบบ walk-forward: ฟิตใหม่ทุก refit_every แท่งบน window แท่งก่อนหน้า
+/// แล้วกรอง h ต่อด้วยพารามิเตอร์ล่าสุด -> ใช้เฉพาะข้อมูลถึง t-1 ; ก่อนฟิตได้ครั้งแรกเป็น NaN
+pub fn rolling_garch_sd(
+    returns: &[f64],
+    kind: GarchKind,
+    window: usize,
+    refit_every: usize,
+) -> Vec<f64> {
+    let refit_every = refit_every.max(1);
+    let mut out = vec![f64::NAN; returns.len()];
+    // (model, mean ของหน้าต่างที่ฟิต, แท่งที่ฟิต)
+    let mut fit: Option<(GarchModel, f64, usize)> = None;
+    let mut h = f64::NAN;
+    for t in window..returns.len() {
+        if fit.as_ref().is_none_or(|(_, _, at)| t - at >= refit_every) {
+            let xs = &returns[t - window..t];
+            if xs.iter().all(|x| x.is_finite()) {
+                let mean = xs.iter().sum::<f64>() / window as f64;
+                let eps: Vec<f64> = xs.iter().map(|x| x - mean).collect();
+                if let Some(m) = fit_garch(&eps, kind) {
+                    h = m.variance.last().copied().unwrap_or(f64::NAN);
+                    fit = Some((m, mean, t));
+                    out[t] = h.sqrt();
+                    continue;
+                }
+            }
+        }
+        let Some((m, mean, _)) = &fit else {
+            continue;
+        };
+        let e = returns[t - 1] - mean;
+        if e.is_finite() {
+            h = next_variance(kind, &m.params, e, h);
+        }
+        out[t] = h.sqrt();
+    }
+    out
+}
+
+/// se_k = √(Σ_{j<k} ψ_j² h_{n+k-j}) : forecast_std_errors ที่ variance ของ shock เปลี่ยนตาม GARCH
+pub fn garch_forecast_std_errors(psi: &[f64], h: &[f64]) -> Vec<f64> {
+    (0..psi.len().min(h.len()))
+        .map(|k| {
+            (0..=k)
+                .map(|j| psi[j] * psi[j] * h[k - j])
+                .sum::<f64>()
+                .sqrt()
+        })
+        .collect()
+}
+
+/// ARMA สำหรับ mean + GARCH บน residual (two-step)
+#[derive(Clone, Debug)]
+pub struct ArmaGarch {
+    pub arma: ArmaModel,
+    pub garch: GarchModel,
+}
+
+impl ArmaGarch {
+    pub fn fit(y: &[f64], p: usize, q: usize, kind: GarchKind) -> Option<Self> {
+        let arma = fit_arma_with_ic(y, p, q)?;
+        Self::from_arma(arma, y, kind)
+    }
+
+    /// ฟิต GARCH บน residual ของ ARMA ที่ฟิตไว้แล้วบน y ชุดเดียวกัน
+    pub fn from_arma(arma: ArmaModel, y: &[f64], kind: GarchKind) -> Option<Self> {
+        let resid = arma_residuals(y, &arma.params);
+        let garch = fit_garch(&resid, kind)?;
+        Some(Self { arma, garch })
+    }
+
+    /// พยากรณ์ k ก้าวถัดจากปลาย series ที่ใช้ฟิต: mean จาก ARMA,
+    /// variance = Σ_{j<k} ψ_j² h_{n+k-j} -> ช่วงกว้างขึ้นเมื่อ h ล่าสุดสูง
+    pub fn forecast(&self, k: usize) -> Vec<ForecastStep> {
+        let psi = psi_weights(&self.arma.params.phi, &self.arma.params.theta, k);
+        let se = garch_forecast_std_errors(&psi, &self.garch.forecast_variance(k));
+        self.arma
+            .forecast(k)
+            .iter()
+            .zip(se)
+            .map(|(f, se)| ForecastStep::new(f.mean, se))
+            .collect()
+    }
+}
+
+/// น้ำหนักสถานะแบบ volatility targeting: target_sd / σ̂_t จำกัดไม่เกิน max_leverage
+pub fn vol_target_scale(sd: &[f64], target_sd: f64, max_leverage: f64) -> Vec<f64> {
+    sd.iter()
+        .map(|s| {
+            if s.is_finite() && *s > 0.0 {
+                (target_sd / s).min(max_leverage)
+            } else {
+                0.0
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::module::util::math::rng::SplitMix64;
+
+    fn simulate(kind: GarchKind, p: GarchParams, n: usize, seed: u64) -> Vec<f64> {
+        let mut rng = SplitMix64::new(seed);
+        let burn = 500;
+        let mut out = Vec::with_capacity(n);
+        let mut h = match kind {
+            GarchKind::Egarch => 1.0,
+            _ => p.omega / (1.0 - p.alpha - 0.5 * p.gamma - p.beta),
+        };
+        for t in 0..n + burn {
+            let z = rng.next_normal();
+            let e = h.sqrt() * z;
+            if t >= burn {
+                out.push(e);
+            }
+            h = match kind {
+                GarchKind::Egarch => {
+                    (p.omega + p.alpha * (z.abs() - SQRT_2_OVER_PI) + p.gamma * z + p.beta * h.ln())
+                        .exp()
+                }
+                GarchKind::Garch | GarchKind::Gjr => {
+                    let lev = if e < 0.0 { p.gamma } else { 0.0 };
+                    p.omega + (p.alpha + lev) * e * e + p.beta * h
+                }
+            };
+        }
+        out
+    }
+
+    #[test]
+    fn test_garch_recovers_parameters_and_mean_reverts() {
+        let truth = GarchParams {
+            omega: 0.1,
+            alpha: 0.1,
+            beta: 0.8,
+            gamma: 0.0,
+        };
+        let eps = simulate(GarchKind::Garch, truth, 4000, 17);
+        let m = fit_garch(&eps, GarchKind::Garch).unwrap();
+        assert!((m.params.alpha - 0.1).abs() < 0.05, "{:?}", m.params);
+        assert!((m.params.beta - 0.8).abs() < 0.1, "{:?}", m.params);
+        assert!(m.std_errors.iter().all(|s| s.is_finite() && *s > 0.0));
+        assert_eq!(m.variance.len(), eps.len() + 1);
+
+        let f = m.forecast_variance(500);
+        let uncond = m.unconditional_variance().unwrap();
+        assert!((f[499] - uncond).abs() / uncond < 1e-3);
+    }
+
+    #[test]
+    fn test_gjr_and_egarch_detect_leverage() {
+        let gjr_truth = GarchParams {
+            omega: 0.05,
+            alpha: 0.02,
+            beta: 0.85,
+            gamma: 0.15,
+        };
+        let eps = simulate(GarchKind::Gjr, gjr_truth, 4000, 5);
+        let gjr = fit_garch(&eps, GarchKind::Gjr).unwrap();
+        assert!(gjr.params.gamma > 0.05, "{:?}", gjr.params);
+
+        let eg_truth = GarchParams {
+            omega: 0.0,
+            alpha: 0.2,
+            beta: 0.9,
+            gamma: -0.1,
+        };
+        let eps = simulate(GarchKind::Egarch, eg_truth, 4000, 9);
+        let eg = fit_garch(&eps, GarchKind::Egarch).unwrap();
+        assert!((eg.params.beta - 0.9).abs() < 0.07, "{:?}", eg.params);
+        assert!(eg.params.gamma < 0.0, "{:?}", eg.params);
+    }
+
+    #[test]
+    fn test_arma_garch_forecast_scales_psi_by_garch_variance() {
+        let truth = GarchParams {
+            omega: 0.05,
+            alpha: 0.1,
+            beta: 0.85,
+            gamma: 0.0,
+        };
+        let eps = simulate(GarchKind::Garch, truth, 2000, 23);
+        // AR(1) บน shock แบบ GARCH
+        let mut y = vec![0.0; eps.len()];
+        for t in 1..y.len() {
+            y[t] = 0.5 * y[t - 1] + eps[t];
+        }
+        let m = ArmaGarch::fit(&y, 1, 0, GarchKind::Garch).unwrap();
+        let k = 10;
+        let f = m.forecast(k);
+        let h = m.garch.forecast_variance(k);
+        assert!((f[0].se - m.garch.variance.last().unwrap().sqrt()).abs() < 1e-12);
+
+        let psi = psi_weights(&m.arma.params.phi, &m.arma.params.theta, k);
+        for step in 1..k {
+            let var: f64 = (0..=step).map(|j| psi[j] * psi[j] * h[step - j]).sum();
+            assert!((f[step].se.powi(2) - var).abs() < 1e-12);
+            assert!(f[step].se > f[step - 1].se);
+        }
+        let mean: Vec<f64> = m.arma.forecast(k).iter().map(|s| s.mean).collect();
+        assert_eq!(f.iter().map(|s| s.mean).collect::<Vec<_>>(), mean);
+    }
+
+    #[test]
+    fn test_rolling_garch_sd_uses_past_returns_only() {
+        let truth = GarchParams {
+            omega: 0.05,
+            alpha: 0.1,
+            beta: 0.85,
+            gamma: 0.0,
+        };
+        let r = simulate(GarchKind::Garch, truth, 400, 31);
+        let sd = rolling_garch_sd(&r, GarchKind::Garch, 200, 50);
+        assert!(sd[..200].iter().all(|s| s.is_nan()));
+        assert!(sd[200..].iter().all(|s| s.is_finite() && *s > 0.0));
+
+        // แก้ค่าอนาคตแล้ว σ̂ ก่อนหน้านั้นต้องไม่เปลี่ยน
+        let mut shocked = r.clone();
+        shocked[300] = 25.0;
+        let sd2 = rolling_garch_sd(&shocked, GarchKind::Garch, 200, 50);
+        assert_eq!(sd[200..=300], sd2[200..=300]);
+        assert!(sd2[301] > sd[301]);
+    }
+
+    #[test]
+    fn test_vol_target_scale_caps_leverage() {
+        let w = vol_target_scale(&[0.01, 0.02, 0.001, 0.0], 0.01, 3.0);
+        assert_eq!(w, vec![1.0, 0.5, 3.0, 0.0]);
+    }
+}
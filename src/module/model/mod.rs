@@ -1,6 +1,16 @@
+pub mod arima;
 pub mod arma;
+pub mod cycle;
+pub mod diagnostics;
 pub mod differencing;
 pub mod ema;
+pub mod filter;
+pub mod garch;
 pub mod pacf;
 pub mod sma;
+pub mod state_space;
+pub mod store;
+pub mod streaming;
+pub mod technical;
 pub mod util;
+pub mod var;
@@ -1,3 +1,7 @@
+use std::collections::VecDeque;
+
+use crate::module::model::streaming::Indicator;
+
 /// this function calculate value mean average kub
 pub fn calculate_sma(data: &[f64]) -> f64 {
     data.iter().sum::<f64>() / data.len() as f64
@@ -5,18 +9,48 @@ pub fn calculate_sma(data: &[f64]) -> f64 {
 // why change &Vec<f64> to &[f64]
 
 pub fn sma_series(data: &[f64], period: usize) -> Vec<Option<f64>> {
-    let n = data.len();
-    let mut out = vec![None; n];
-    if period == 0 || n < period {
-        return out;
+    Sma::new(period).run(data.iter().copied())
+}
+
+/// SMA แบบ streaming: เก็บแค่ period แท่งล่าสุดกับผลรวม
+#[derive(Clone, Debug)]
+pub struct Sma {
+    period: usize,
+    window: VecDeque<f64>,
+    sum: f64,
+}
+
+impl Sma {
+    pub fn new(period: usize) -> Self {
+        Self {
+            period,
+            window: VecDeque::with_capacity(period),
+            sum: 0.0,
+        }
     }
-    let mut sum: f64 = data[..period].iter().sum();
-    out[period - 1] = Some(sum / period as f64);
-    for t in period..n {
-        sum += data[t] - data[t - period];
-        out[t] = Some(sum / period as f64);
+}
+
+impl Indicator for Sma {
+    type Input = f64;
+
+    fn update(&mut self, x: f64) -> Option<f64> {
+        if self.period == 0 {
+            return None;
+        }
+        if self.window.len() < self.period {
+            self.sum += x;
+        } else {
+            let old = self.window.pop_front().unwrap_or(0.0);
+            self.sum += x - old;
+        }
+        self.window.push_back(x);
+        (self.window.len() == self.period).then(|| self.sum / self.period as f64)
+    }
+
+    fn reset(&mut self) {
+        self.window.clear();
+        self.sum = 0.0;
     }
-    out
 }
 
 #[cfg(test)]
@@ -0,0 +1,287 @@
+// Linear Gaussian state-space model (สังเกตทีละค่า)
+//   y_t = Z' α_t + ε_t,        ε_t ~ N(0, H)
+//   α_{t+1} = T α_t + η_t,     η_t ~ N(0, Q)   (Q = R Q R' รวมไว้แล้ว)
+// Kalman filter, RTS smoother และ log-likelihood ; y ที่เป็น NaN ถือว่าหาย (ข้าม update)
+
+use nalgebra::{DMatrix, DVector};
+
+use crate::module::model::arma::nelder_mead_min;
+
+/// variance เริ่มต้นของ state ที่ไม่รู้ค่า (approximate diffuse)
+const DIFFUSE_VARIANCE: f64 = 1e7;
+
+#[derive(Clone, Debug)]
+pub struct StateSpaceModel {
+    pub transition: DMatrix<f64>,
+    pub design: DVector<f64>,
+    pub obs_variance: f64,
+    pub state_cov: DMatrix<f64>,
+    pub a0: DVector<f64>,
+    pub p0: DMatrix<f64>,
+    /// จำนวน observation แรกที่ไม่นับใน likelihood (ช่วง diffuse)
+    pub n_diffuse: usize,
+}
+
+#[derive(Clone, Debug)]
+pub struct FilterOutput {
+    /// a_{t|t-1}, P_{t|t-1}
+    pub predicted_state: Vec<DVector<f64>>,
+    pub predicted_cov: Vec<DMatrix<f64>>,
+    /// a_{t|t}, P_{t|t}
+    pub filtered_state: Vec<DVector<f64>>,
+    pub filtered_cov: Vec<DMatrix<f64>>,
+    /// v_t = y_t - Z' a_{t|t-1} (NaN ถ้า y_t หาย), F_t
+    pub innovations: Vec<f64>,
+    pub innovation_var: Vec<f64>,
+    pub loglik: f64,
+}
+
+#[derive(Clone, Debug)]
+pub struct SmootherOutput {
+    pub state: Vec<DVector<f64>>,
+    pub cov: Vec<DMatrix<f64>>,
+}
+
+/// covariance แบบ stationary: P = T P T' + Q (แก้ผ่าน vec / Kronecker)
+pub fn stationary_cov(t: &DMatrix<f64>, q: &DMatrix<f64>) -> Option<DMatrix<f64>> {
+    let m = t.nrows();
+    let lhs = DMatrix::<f64>::identity(m * m, m * m) - t.kronecker(t);
+    let rhs = DVector::from_column_slice(q.as_slice());
+    let vec_p = lhs.lu().solve(&rhs)?;
+    Some(DMatrix::from_column_slice(m, m, vec_p.as_slice()))
+}
+
+impl StateSpaceModel {
+    /// local level: y_t = μ_t + ε_t, μ_{t+1} = μ_t + η_t
+    pub fn local_level(sigma2_eps: f64, sigma2_eta: f64) -> Self {
+        Self {
+            transition: DMatrix::identity(1, 1),
+            design: DVector::from_element(1, 1.0),
+            obs_variance: sigma2_eps,
+            state_cov: DMatrix::from_element(1, 1, sigma2_eta),
+            a0: DVector::zeros(1),
+            p0: DMatrix::from_element(1, 1, DIFFUSE_VARIANCE),
+            n_diffuse: 1,
+        }
+    }
+
+    /// local linear trend: μ_{t+1} = μ_t + ν_t + ξ_t, ν_{t+1} = ν_t + ζ_t
+    pub fn local_linear_trend(sigma2_eps: f64, sigma2_level: f64, sigma2_slope: f64) -> Self {
+        Self {
+            transition: DMatrix::from_row_slice(2, 2, &[1.0, 1.0, 0.0, 1.0]),
+            design: DVector::from_column_slice(&[1.0, 0.0]),
+            obs_variance: sigma2_eps,
+            state_cov: DMatrix::from_diagonal(&DVector::from_column_slice(&[
+                sigma2_level,
+                sigma2_slope,
+            ])),
+            a0: DVector::zeros(2),
+            p0: DMatrix::identity(2, 2) * DIFFUSE_VARIANCE,
+            n_diffuse: 2,
+        }
+    }
+
+    /// ARMA(p, q) แบบ Harvey (r = max(p, q + 1)) เริ่มจาก covariance แบบ stationary
+    pub fn arma(phi: &[f64], theta: &[f64], sigma2: f64) -> Option<Self> {
+        let p = phi.len();
+        let r = p.max(theta.len() + 1);
+        let transition = DMatrix::from_fn(r, r, |i, j| {
+            if j == 0 && i < p {
+                phi[i]
+            } else if j == i + 1 {
+                1.0
+            } else {
+                0.0
+            }
+        });
+        let rvec = DVector::from_fn(r, |i, _| {
+            if i == 0 {
+                1.0
+            } else {
+                theta.get(i - 1).copied().unwrap_or(0.0)
+            }
+        });
+        let state_cov = &rvec * rvec.transpose() * sigma2;
+        let p0 = stationary_cov(&transition, &state_cov)?;
+        let mut design = DVector::zeros(r);
+        design[0] = 1.0;
+        Some(Self {
+            transition,
+            design,
+            obs_variance: 0.0,
+            state_cov,
+            a0: DVector::zeros(r),
+            p0,
+            n_diffuse: 0,
+        })
+    }
+
+    pub fn state_dim(&self) -> usize {
+        self.transition.nrows()
+    }
+
+    pub fn filter(&self, y: &[f64]) -> FilterOutput {
+        let n = y.len();
+        let z = &self.design;
+        let tt = &self.transition;
+        let mut out = FilterOutput {
+            predicted_state: Vec::with_capacity(n),
+            predicted_cov: Vec::with_capacity(n),
+            filtered_state: Vec::with_capacity(n),
+            filtered_cov: Vec::with_capacity(n),
+            innovations: Vec::with_capacity(n),
+            innovation_var: Vec::with_capacity(n),
+            loglik: 0.0,
+        };
+        let ln_2pi = (2.0 * std::f64::consts::PI).ln();
+        let mut a = self.a0.clone();
+        let mut p = self.p0.clone();
+
+        for (t, &yt) in y.iter().enumerate() {
+            out.predicted_state.push(a.clone());
+            out.predicted_cov.push(p.clone());
+
+            let pz = &p * z;
+            let f = z.dot(&pz) + self.obs_variance;
+            let (af, pf, v) = if yt.is_finite() && f > 0.0 {
+                let v = yt - z.dot(&a);
+                if t >= self.n_diffuse {
+                    out.loglik += -0.5 * (ln_2pi + f.ln() + v * v / f);
+                }
+                let k = &pz / f;
+                (&a + &k * v, &p - &k * pz.transpose(), v)
+            } else {
+                (a.clone(), p.clone(), f64::NAN)
+            };
+            out.innovations.push(v);
+            out.innovation_var.push(f);
+
+            a = tt * &af;
+            p = tt * &pf * tt.transpose() + &self.state_cov;
+            out.filtered_state.push(af);
+            out.filtered_cov.push(pf);
+        }
+        out
+    }
+
+    /// Rauch–Tung–Striebel smoother บนผลของ filter
+    pub fn smooth(&self, filtered: &FilterOutput) -> SmootherOutput {
+        let n = filtered.filtered_state.len();
+        let mut state = filtered.filtered_state.clone();
+        let mut cov = filtered.filtered_cov.clone();
+        let tt = &self.transition;
+        for t in (0..n.saturating_sub(1)).rev() {
+            let p_pred = &filtered.predicted_cov[t + 1];
+            let inv = p_pred
+                .clone()
+                .try_inverse()
+                .or_else(|| p_pred.clone().pseudo_inverse(1e-12).ok());
+            let Some(inv) = inv else {
+                continue;
+            };
+            let j = &filtered.filtered_cov[t] * tt.transpose() * inv;
+            let ds = &state[t + 1] - &filtered.predicted_state[t + 1];
+            let dp = &cov[t + 1] - p_pred;
+            state[t] = &filtered.filtered_state[t] + &j * ds;
+            cov[t] = &filtered.filtered_cov[t] + &j * dp * j.transpose();
+        }
+        SmootherOutput { state, cov }
+    }
+
+    pub fn loglik(&self, y: &[f64]) -> f64 {
+        self.filter(y).loglik
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct LocalLevelFit {
+    pub model: StateSpaceModel,
+    pub sigma2_eps: f64,
+    pub sigma2_eta: f64,
+    pub loglik: f64,
+}
+
+/// MLE ของ local level (optimize บน log-variance)
+pub fn fit_local_level(y: &[f64]) -> Option<LocalLevelFit> {
+    let n = y.len();
+    if n < 10 {
+        return None;
+    }
+    let diffs: Vec<f64> = y.windows(2).map(|w| w[1] - w[0]).collect();
+    let var = diffs.iter().map(|d| d * d).sum::<f64>() / diffs.len() as f64;
+    if !(var.is_finite() && var > 0.0) {
+        return None;
+    }
+    let obj = |u: &[f64]| {
+        let ll = StateSpaceModel::local_level(u[0].exp(), u[1].exp()).loglik(y);
+        if ll.is_finite() { -ll } else { f64::INFINITY }
+    };
+    let start = vec![(0.5 * var).ln(), (0.5 * var).ln()];
+    let u = nelder_mead_min(start, obj, 600, 1e-9);
+    let (sigma2_eps, sigma2_eta) = (u[0].exp(), u[1].exp());
+    let model = StateSpaceModel::local_level(sigma2_eps, sigma2_eta);
+    let loglik = model.loglik(y);
+    Some(LocalLevelFit {
+        model,
+        sigma2_eps,
+        sigma2_eta,
+        loglik,
+    })
+}
+
+/// Kalman trend: local linear trend ที่ filter แล้ว คืน (level, slope) ณ เวลา t
+/// ใช้แค่ข้อมูลถึง t ; snr = variance ของ level/slope เทียบกับ noise ของราคา
+pub fn kalman_trend(data: &[f64], level_snr: f64, slope_snr: f64) -> Vec<(f64, f64)> {
+    let Some(&first) = data.first() else {
+        return Vec::new();
+    };
+    let mut model = StateSpaceModel::local_linear_trend(1.0, level_snr, slope_snr);
+    model.a0 = DVector::from_column_slice(&[first, 0.0]);
+    model
+        .filter(data)
+        .filtered_state
+        .iter()
+        .map(|a| (a[0], a[1]))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::module::model::arma::mle::arma_exact_loglik;
+
+    #[test]
+    fn test_arma_state_space_matches_exact_loglik() {
+        let y = [0.4, -0.1, 0.3, 0.8, -0.5, 0.2, 0.0, -0.3];
+        let (phi, theta) = ([0.5, -0.2], [0.3]);
+        let (ll_ref, sigma2) = arma_exact_loglik(&y, 0.0, &phi, &theta).unwrap();
+        let ll = StateSpaceModel::arma(&phi, &theta, sigma2)
+            .unwrap()
+            .loglik(&y);
+        assert!((ll - ll_ref).abs() < 1e-9, "{ll} vs {ll_ref}");
+    }
+
+    #[test]
+    fn test_local_level_smoother_and_missing() {
+        let y = [1.0, 1.2, f64::NAN, 1.1, 0.9, 1.3];
+        let model = StateSpaceModel::local_level(0.1, 0.01);
+        let f = model.filter(&y);
+        assert!(f.innovations[2].is_nan());
+        let s = model.smooth(&f);
+        // smoother ใช้ข้อมูลทั้งสองฝั่ง -> variance ไม่เกินของ filter
+        for t in 0..y.len() {
+            assert!(s.cov[t][(0, 0)] <= f.filtered_cov[t][(0, 0)] + 1e-12);
+        }
+        // จุดที่หายได้ค่าระหว่างเพื่อนบ้าน
+        assert!(s.state[2][0] > 0.9 && s.state[2][0] < 1.3);
+    }
+
+    #[test]
+    fn test_kalman_trend_slope_sign() {
+        let up: Vec<f64> = (0..50).map(|t| 100.0 + 0.5 * t as f64).collect();
+        let tr = kalman_trend(&up, 0.01, 0.001);
+        assert!(tr[49].1 > 0.4);
+        let down: Vec<f64> = up.iter().rev().copied().collect();
+        assert!(kalman_trend(&down, 0.01, 0.001)[49].1 < -0.4);
+    }
+}
@@ -0,0 +1,301 @@
+// บันทึก / โหลด model ที่ fit แล้วเป็น JSON พร้อม metadata
+// ใช้พยากรณ์ต่อจาก model เดิมได้โดยไม่ต้อง fit ใหม่ และตรวจได้ว่าข้อมูลที่ใช้ตอนนี้ตรงกับตอน fit หรือไม่
+
+use std::error::Error;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+
+use crate::module::model::{arima::Arima, arma::ArmaModel};
+
+/// เพิ่มเมื่อเปลี่ยน layout ของไฟล์จนไฟล์เก่าอ่านไม่ได้
+/// 2: ARIMA เก็บแค่ระดับท้าย d + p ตัว, ARMA เก็บ lag state และ n / k / AICc ของการฟิต
+pub const FORMAT_VERSION: u32 = 2;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TrainingRange {
+    /// unix timestamp ของแท่งแรก / แท่งสุดท้ายที่ใช้ fit
+    pub start: i64,
+    pub end: i64,
+}
+
+impl TrainingRange {
+    pub fn from_timestamps(ts: &[i64]) -> Option<Self> {
+        Some(Self {
+            start: *ts.first()?,
+            end: *ts.last()?,
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ModelMetadata {
+    pub kind: String,
+    /// (p, d, q)
+    pub order: [usize; 3],
+    /// จำนวนจุดที่ใช้ฟิต ARMA จริง (หลัง differencing) และ k ที่ใช้ใน information criteria
+    pub n_obs: usize,
+    pub n_params: usize,
+    pub training_range: Option<TrainingRange>,
+    /// FNV-1a 64 bit ของข้อมูลที่ใช้ fit (hex)
+    pub data_hash: String,
+    /// None เมื่อค่าไม่ finite เช่น AICc ที่ n <= k + 1
+    pub aic: Option<f64>,
+    pub aicc: Option<f64>,
+    pub bic: Option<f64>,
+    pub sigma2: Option<f64>,
+    pub created_at: i64,
+    pub crate_version: String,
+}
+
+/// model ที่บันทึกได้ ; KIND ใช้กันโหลดไฟล์ผิดชนิด
+pub trait StoredModel: Serialize + DeserializeOwned {
+    const KIND: &'static str;
+    fn order(&self) -> [usize; 3];
+    fn arma(&self) -> &ArmaModel;
+}
+
+impl StoredModel for ArmaModel {
+    const KIND: &'static str = "ARMA";
+
+    fn order(&self) -> [usize; 3] {
+        [self.p, 0, self.q]
+    }
+
+    fn arma(&self) -> &ArmaModel {
+        self
+    }
+}
+
+impl StoredModel for Arima {
+    const KIND: &'static str = "ARIMA";
+
+    fn order(&self) -> [usize; 3] {
+        [self.p(), self.d, self.q()]
+    }
+
+    fn arma(&self) -> &ArmaModel {
+        &self.arma
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SavedModel<M> {
+    pub format_version: u32,
+    pub metadata: ModelMetadata,
+    pub model: M,
+}
+
+fn finite(x: f64) -> Option<f64> {
+    x.is_finite().then_some(x)
+}
+
+/// f64 ที่อาจเป็น ±∞ / NaN: JSON ไม่มีค่าเหล่านี้ (serde_json เขียนเป็น null แล้วอ่านกลับเป็น f64 ไม่ได้)
+/// จึงเขียนค่าที่ไม่ finite เป็น string "inf" / "-inf" / "nan" แทน
+pub(crate) mod non_finite {
+    use serde::{Deserialize, Deserializer, Serializer, de::Error};
+
+    pub fn serialize<S: Serializer>(x: &f64, s: S) -> Result<S::Ok, S::Error> {
+        match *x {
+            x if x.is_finite() => s.serialize_f64(x),
+            x if x.is_nan() => s.serialize_str("nan"),
+            x if x > 0.0 => s.serialize_str("inf"),
+            _ => s.serialize_str("-inf"),
+        }
+    }
+
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Repr {
+        Number(f64),
+        Text(String),
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<f64, D::Error> {
+        match Repr::deserialize(d)? {
+            Repr::Number(x) => Ok(x),
+            Repr::Text(t) => match t.as_str() {
+                "inf" => Ok(f64::INFINITY),
+                "-inf" => Ok(f64::NEG_INFINITY),
+                "nan" => Ok(f64::NAN),
+                other => Err(D::Error::custom(format!("invalid float {other:?}"))),
+            },
+        }
+    }
+}
+
+/// hash ของค่าแบบ bit-exact (FNV-1a 64) ไว้เช็คว่าข้อมูลเปลี่ยนหรือไม่
+pub fn data_hash(data: &[f64]) -> String {
+    const OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+    let mut h = OFFSET;
+    for v in data {
+        for b in v.to_bits().to_le_bytes() {
+            h ^= u64::from(b);
+            h = h.wrapping_mul(PRIME);
+        }
+    }
+    format!("{h:016x}")
+}
+
+impl<M: StoredModel> SavedModel<M> {
+    /// data = series ที่ใช้ fit (ระดับราคาสำหรับ ARIMA, series ที่ stationary แล้วสำหรับ ARMA)
+    pub fn new(model: M, data: &[f64], training_range: Option<TrainingRange>) -> Self {
+        let arma = model.arma();
+        let metadata = ModelMetadata {
+            kind: M::KIND.to_string(),
+            order: model.order(),
+            n_obs: arma.n_obs,
+            n_params: arma.n_params,
+            training_range,
+            data_hash: data_hash(data),
+            aic: finite(arma.aic),
+            aicc: finite(arma.aicc),
+            bic: finite(arma.bic),
+            sigma2: finite(arma.sigma2),
+            created_at: chrono::Utc::now().timestamp(),
+            crate_version: env!("CARGO_PKG_VERSION").to_string(),
+        };
+        Self {
+            format_version: FORMAT_VERSION,
+            metadata,
+            model,
+        }
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), Box<dyn Error>> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, Box<dyn Error>> {
+        let saved: Self = serde_json::from_str(&std::fs::read_to_string(path)?)?;
+        if saved.format_version != FORMAT_VERSION {
+            return Err(format!(
+                "unsupported model format version {} (expected {})",
+                saved.format_version, FORMAT_VERSION
+            )
+            .into());
+        }
+        if saved.metadata.kind != M::KIND {
+            return Err(format!(
+                "model file holds {} but {} was requested",
+                saved.metadata.kind,
+                M::KIND
+            )
+            .into());
+        }
+        Ok(saved)
+    }
+
+    /// true ถ้า data เหมือนกับข้อมูลที่ใช้ fit ทุก bit (hash รวมความยาวอยู่แล้ว)
+    pub fn matches_data(&self, data: &[f64]) -> bool {
+        data_hash(data) == self.metadata.data_hash
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::module::model::arma::{arma_forecast_mean, fit_arma_with_ic};
+    use crate::module::model::differencing::{diff_n, integrate_n};
+    use crate::module::util::math::rng::SplitMix64;
+
+    fn temp_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("dsp_rust_{}_{}", std::process::id(), name))
+    }
+
+    #[test]
+    fn test_arma_round_trip_forecasts_identically() {
+        let mut rng = SplitMix64::new(8);
+        let mut y = vec![0.0; 400];
+        for t in 1..y.len() {
+            y[t] = 0.1 + 0.5 * y[t - 1] + (rng.next_f64() - 0.5);
+        }
+        let model = fit_arma_with_ic(&y, 1, 1).unwrap();
+        let saved = SavedModel::new(model.clone(), &y, TrainingRange::from_timestamps(&[10, 20]));
+        let path = temp_path("arma.json");
+        saved.save(&path).unwrap();
+
+        let loaded = SavedModel::<ArmaModel>::load(&path).unwrap();
+        assert_eq!(loaded.metadata, saved.metadata);
+        assert_eq!(loaded.metadata.order, [1, 0, 1]);
+        assert!(loaded.matches_data(&y));
+        assert!(!loaded.matches_data(&y[1..]));
+
+        let a = model.forecast(5);
+        let b = loaded.model.forecast(5);
+        for (x, z) in a.iter().zip(&b) {
+            assert_eq!(x.mean, z.mean);
+            assert_eq!(x.se, z.se);
+        }
+
+        // ไฟล์ ARMA โหลดเป็น ARIMA ไม่ได้
+        assert!(SavedModel::<Arima>::load(&path).is_err());
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_arima_round_trip_and_hash() {
+        let mut rng = SplitMix64::new(4);
+        let mut x = 100.0;
+        let levels: Vec<f64> = (0..300)
+            .map(|_| {
+                x += 0.05 + (rng.next_f64() - 0.5);
+                x
+            })
+            .collect();
+        let model = Arima::fit(&levels, 1, 1, 0, true).unwrap();
+        let path = temp_path("arima.json");
+        SavedModel::new(model.clone(), &levels, None)
+            .save(&path)
+            .unwrap();
+        let loaded = SavedModel::<Arima>::load(&path).unwrap();
+        assert_eq!(loaded.model.forecast(10), model.forecast(10));
+        assert_eq!(loaded.metadata.order, [1, 1, 0]);
+        // metadata มาจากการฟิตบน ∇x (299 จุด, k = drift + φ + σ²)
+        assert_eq!(loaded.metadata.n_obs, 299);
+        assert_eq!(loaded.metadata.n_params, 3);
+        assert_eq!(loaded.metadata.aicc, Some(model.arma.aicc));
+
+        // ไฟล์เก็บแค่ระดับท้าย d + p ตัว แต่พยากรณ์ได้เท่ากับการใช้ข้อมูลทั้งหมด
+        let json: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(json["model"]["tail"].as_array().unwrap().len(), 2);
+        let w = diff_n(&levels, 1);
+        let full = integrate_n(&levels, 1, &arma_forecast_mean(&w, &model.arma.params, 10));
+        for (a, b) in loaded.model.forecast(10).iter().zip(&full) {
+            assert!((a - b).abs() < 1e-9);
+        }
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(data_hash(&[]), "cbf29ce484222325");
+    }
+
+    #[test]
+    fn test_round_trip_with_undefined_aicc() {
+        // n = 4, k = 4 (c, φ, θ, σ²) -> AICc = ∞
+        let y = [0.3, -0.1, 0.4, 0.2];
+        let model = fit_arma_with_ic(&y, 1, 1).unwrap();
+        assert_eq!(model.n_obs, 4);
+        assert_eq!(model.aicc, f64::INFINITY);
+
+        let path = temp_path("arma_small.json");
+        SavedModel::new(model.clone(), &y, None)
+            .save(&path)
+            .unwrap();
+        let loaded = SavedModel::<ArmaModel>::load(&path).unwrap();
+        assert_eq!(loaded.metadata.aicc, None);
+        assert_eq!(loaded.metadata.aic, Some(model.aic));
+        assert_eq!(loaded.model.aicc, f64::INFINITY);
+        for (a, b) in loaded.model.forecast(3).iter().zip(model.forecast(3)) {
+            assert_eq!((a.mean, a.se), (b.mean, b.se));
+        }
+        std::fs::remove_file(&path).unwrap();
+        assert_ne!(data_hash(&[0.0]), data_hash(&[-0.0]));
+    }
+}
@@ -0,0 +1,22 @@
+// indicator แบบ stateful: ป้อนทีละแท่งแล้วได้ค่าล่าสุดใน O(1)
+// ใช้สร้างสัญญาณ live โดยไม่ต้องคำนวณทั้ง series ใหม่ทุกครั้ง
+
+pub trait Indicator {
+    /// ข้อมูลต่อแท่ง (เช่น close เป็น f64)
+    type Input;
+
+    /// ป้อนแท่งใหม่ ; None ระหว่าง warm-up
+    fn update(&mut self, input: Self::Input) -> Option<f64>;
+
+    /// กลับไปสถานะเริ่มต้น
+    fn reset(&mut self);
+
+    /// ป้อนทั้ง series ตามลำดับ (ต่อจากสถานะปัจจุบัน)
+    fn run<I>(&mut self, inputs: I) -> Vec<Option<f64>>
+    where
+        I: IntoIterator<Item = Self::Input>,
+        Self: Sized,
+    {
+        inputs.into_iter().map(|x| self.update(x)).collect()
+    }
+}
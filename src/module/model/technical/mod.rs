@@ -0,0 +1,4 @@
+pub mod moving_average;
+pub mod oscillator;
+pub mod volatility;
+pub mod volume;
@@ -0,0 +1,177 @@
+// moving average เพิ่มเติมจาก ema / sma
+// ทุกฟังก์ชันคืน Vec<Option<f64>> ยาวเท่า input ; None = ยังอยู่ช่วง warm-up เหมือน ema_series
+
+use crate::module::model::ema::{EmaConvention, ema_series_with};
+
+/// ใช้ f กับช่วงที่มีค่าของ series (ตั้งแต่ Some ตัวแรก) แล้ววางกลับตำแหน่งเดิม
+pub(crate) fn map_defined(
+    series: &[Option<f64>],
+    f: impl FnOnce(&[f64]) -> Vec<Option<f64>>,
+) -> Vec<Option<f64>> {
+    let n = series.len();
+    let Some(start) = series.iter().position(Option::is_some) else {
+        return vec![None; n];
+    };
+    let values: Vec<f64> = series[start..]
+        .iter()
+        .map(|v| v.unwrap_or(f64::NAN))
+        .collect();
+    let mut out = vec![None; start];
+    out.extend(f(&values));
+    out
+}
+
+/// ตั้งค่าก่อน index warmup เป็น None (ค่ายังไม่นิ่ง)
+pub(crate) fn mask_warmup(mut series: Vec<Option<f64>>, warmup: usize) -> Vec<Option<f64>> {
+    for v in series.iter_mut().take(warmup) {
+        *v = None;
+    }
+    series
+}
+
+/// EMA แบบ Pine (alpha = 2/(n+1), seed ค่าแรก) มีค่าทุกแท่งเมื่อ period > 0
+pub(crate) fn pine_ema_values(data: &[f64], period: usize) -> Vec<f64> {
+    ema_series_with(data, period, EmaConvention::Pine)
+        .into_iter()
+        .map(|v| v.unwrap_or(f64::NAN))
+        .collect()
+}
+
+/// WMA น้ำหนัก 1..n (แท่งล่าสุดหนักสุด) เหมือน ta.wma
+pub fn wma_series(data: &[f64], period: usize) -> Vec<Option<f64>> {
+    let n = data.len();
+    let mut out = vec![None; n];
+    if period == 0 || n < period {
+        return out;
+    }
+    let denom = (period * (period + 1)) as f64 / 2.0;
+    for t in period - 1..n {
+        let w: f64 = data[t + 1 - period..=t]
+            .iter()
+            .enumerate()
+            .map(|(i, x)| (i + 1) as f64 * x)
+            .sum();
+        out[t] = Some(w / denom);
+    }
+    out
+}
+
+/// DEMA = 2·EMA - EMA(EMA) ; ใช้ EMA แบบ Pine แล้วซ่อน 2(n-1) แท่งแรก
+pub fn dema_series(data: &[f64], period: usize) -> Vec<Option<f64>> {
+    if period == 0 {
+        return vec![None; data.len()];
+    }
+    let e1 = pine_ema_values(data, period);
+    let e2 = pine_ema_values(&e1, period);
+    let dema = e1.iter().zip(&e2).map(|(a, b)| Some(2.0 * a - b)).collect();
+    mask_warmup(dema, 2 * (period - 1))
+}
+
+/// TEMA = 3·EMA - 3·EMA² + EMA³ ; ซ่อน 3(n-1) แท่งแรก
+pub fn tema_series(data: &[f64], period: usize) -> Vec<Option<f64>> {
+    if period == 0 {
+        return vec![None; data.len()];
+    }
+    let e1 = pine_ema_values(data, period);
+    let e2 = pine_ema_values(&e1, period);
+    let e3 = pine_ema_values(&e2, period);
+    let tema = (0..data.len())
+        .map(|t| Some(3.0 * e1[t] - 3.0 * e2[t] + e3[t]))
+        .collect();
+    mask_warmup(tema, 3 * (period - 1))
+}
+
+/// Hull MA = WMA(2·WMA(n/2) - WMA(n), ⌊√n⌋) เหมือน ta.hma
+pub fn hma_series(data: &[f64], period: usize) -> Vec<Option<f64>> {
+    if period == 0 {
+        return vec![None; data.len()];
+    }
+    let half = wma_series(data, (period / 2).max(1));
+    let full = wma_series(data, period);
+    let raw: Vec<Option<f64>> = half
+        .iter()
+        .zip(&full)
+        .map(|(h, f)| Some(2.0 * (*h)? - (*f)?))
+        .collect();
+    let sq = ((period as f64).sqrt().floor() as usize).max(1);
+    map_defined(&raw, |v| wma_series(v, sq))
+}
+
+/// Kaufman adaptive MA ; efficiency ratio จาก period แท่ง, smoothing อยู่ระหว่าง EMA(fast) กับ EMA(slow)
+pub fn kama_series(data: &[f64], period: usize, fast: usize, slow: usize) -> Vec<Option<f64>> {
+    let n = data.len();
+    let mut out = vec![None; n];
+    if period == 0 || n <= period {
+        return out;
+    }
+    let fast_sc = 2.0 / (fast as f64 + 1.0);
+    let slow_sc = 2.0 / (slow as f64 + 1.0);
+    let mut kama = data[period - 1];
+    for t in period..n {
+        let change = (data[t] - data[t - period]).abs();
+        let volatility: f64 = data[t + 1 - period..=t]
+            .iter()
+            .zip(&data[t - period..t])
+            .map(|(a, b)| (a - b).abs())
+            .sum();
+        let er = if volatility > 0.0 {
+            change / volatility
+        } else {
+            0.0
+        };
+        let sc = (er * (fast_sc - slow_sc) + slow_sc).powi(2);
+        kama += sc * (data[t] - kama);
+        out[t] = Some(kama);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DATA: [f64; 8] = [1.0, 2.0, 4.0, 3.0, 5.0, 8.0, 7.0, 9.0];
+
+    #[test]
+    fn test_wma_and_hma() {
+        let w = wma_series(&DATA, 3);
+        assert_eq!(&w[..2], &[None, None]);
+        // (1·1 + 2·2 + 3·4) / 6
+        assert!((w[2].unwrap() - 17.0 / 6.0).abs() < 1e-12);
+        assert!((w[7].unwrap() - (8.0 + 14.0 + 27.0) / 6.0).abs() < 1e-12);
+
+        // HMA(4): WMA(2) กับ WMA(4) มีค่าตั้งแต่ t=3 แล้ว WMA(2) ของผลต่าง -> t=4
+        let h = hma_series(&DATA, 4);
+        assert!(h[3].is_none() && h[4].is_some());
+        let raw =
+            |t: usize| 2.0 * wma_series(&DATA, 2)[t].unwrap() - wma_series(&DATA, 4)[t].unwrap();
+        assert!((h[4].unwrap() - (raw(3) + 2.0 * raw(4)) / 3.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_dema_tema_track_linear_trend() {
+        // DEMA/TEMA ไม่มี lag กับเส้นตรงเมื่อผ่าน warm-up ไปนาน ๆ
+        let line: Vec<f64> = (0..400).map(|t| 2.0 + 0.5 * t as f64).collect();
+        let d = dema_series(&line, 10);
+        let t3 = tema_series(&line, 10);
+        assert!(d[17].is_none() && d[18].is_some());
+        assert!(t3[26].is_none() && t3[27].is_some());
+        let ema_lag = line[399] - pine_ema_values(&line, 10)[399];
+        assert!((ema_lag - 0.5 * 4.5).abs() < 1e-6);
+        assert!((d[399].unwrap() - line[399]).abs() < 1e-6);
+        assert!((t3[399].unwrap() - line[399]).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_kama_efficiency_ratio_extremes() {
+        // แนวโน้มตรง -> ER = 1 -> sc = fast_sc²
+        let k = kama_series(&DATA[..4], 2, 2, 30);
+        assert!(k[1].is_none());
+        // t=2: change=3, volatility=1+2 -> ER=1, sc=(2/3)²
+        let sc = (2.0f64 / 3.0).powi(2);
+        assert!((k[2].unwrap() - (2.0 + sc * (4.0 - 2.0))).abs() < 1e-12);
+        // ราคาแบน -> KAMA คงที่
+        let flat = kama_series(&[5.0; 20], 10, 2, 30);
+        assert_eq!(flat[19], Some(5.0));
+    }
+}
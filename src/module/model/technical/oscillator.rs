@@ -0,0 +1,238 @@
+// oscillator / momentum: RSI, MACD, Stochastic, CCI, ADX
+// Wilder smoothing ใช้ Ema แบบ EmaConvention::Wilder (seed SMA เหมือน ta.rma)
+
+use crate::module::model::{
+    ema::{Ema, EmaConvention},
+    sma::sma_series,
+    streaming::Indicator,
+    technical::{
+        moving_average::{map_defined, mask_warmup, pine_ema_values},
+        volatility::{highest_series, lowest_series, true_range_series},
+    },
+};
+
+#[derive(Clone, Debug)]
+pub struct Macd {
+    pub line: Vec<Option<f64>>,
+    pub signal: Vec<Option<f64>>,
+    pub histogram: Vec<Option<f64>>,
+}
+
+#[derive(Clone, Debug)]
+pub struct Stochastic {
+    /// %K หลัง smooth
+    pub k: Vec<Option<f64>>,
+    /// SMA ของ %K
+    pub d: Vec<Option<f64>>,
+}
+
+#[derive(Clone, Debug)]
+pub struct Adx {
+    pub plus_di: Vec<Option<f64>>,
+    pub minus_di: Vec<Option<f64>>,
+    pub adx: Vec<Option<f64>>,
+}
+
+/// RSI แบบ Wilder (ta.rsi) ; มีค่าตั้งแต่ index period
+pub fn rsi_series(data: &[f64], period: usize) -> Vec<Option<f64>> {
+    let n = data.len();
+    let mut out = vec![None; n];
+    if period == 0 {
+        return out;
+    }
+    let mut up = Ema::new(period, EmaConvention::Wilder);
+    let mut down = Ema::new(period, EmaConvention::Wilder);
+    for t in 1..n {
+        let d = data[t] - data[t - 1];
+        let (Some(u), Some(l)) = (up.update(d.max(0.0)), down.update((-d).max(0.0))) else {
+            continue;
+        };
+        out[t] = Some(if l == 0.0 {
+            100.0
+        } else if u == 0.0 {
+            0.0
+        } else {
+            100.0 - 100.0 / (1.0 + u / l)
+        });
+    }
+    out
+}
+
+/// MACD (ta.macd) ด้วย EMA แบบ Pine ; line ซ่อน max(fast, slow)-1 แท่งแรก, signal/histogram ซ่อนเพิ่มอีก signal-1
+pub fn macd_series(data: &[f64], fast: usize, slow: usize, signal: usize) -> Macd {
+    let n = data.len();
+    if fast == 0 || slow == 0 || signal == 0 {
+        return Macd {
+            line: vec![None; n],
+            signal: vec![None; n],
+            histogram: vec![None; n],
+        };
+    }
+    let f = pine_ema_values(data, fast);
+    let s = pine_ema_values(data, slow);
+    let line: Vec<f64> = f.iter().zip(&s).map(|(a, b)| a - b).collect();
+    let sig = pine_ema_values(&line, signal);
+    let warm_line = fast.max(slow) - 1;
+    let warm_signal = warm_line + signal - 1;
+    Macd {
+        histogram: mask_warmup(
+            line.iter().zip(&sig).map(|(l, s)| Some(l - s)).collect(),
+            warm_signal,
+        ),
+        signal: mask_warmup(sig.into_iter().map(Some).collect(), warm_signal),
+        line: mask_warmup(line.into_iter().map(Some).collect(), warm_line),
+    }
+}
+
+/// Stochastic %K(k_period) smooth ด้วย SMA(k_smooth) และ %D = SMA(%K, d_period)
+/// ช่วงราคาแบน (high = low ทั้งหน้าต่าง) ให้ %K = 50
+pub fn stochastic_series(
+    high: &[f64],
+    low: &[f64],
+    close: &[f64],
+    k_period: usize,
+    k_smooth: usize,
+    d_period: usize,
+) -> Stochastic {
+    let hh = highest_series(high, k_period);
+    let ll = lowest_series(low, k_period);
+    let raw: Vec<Option<f64>> = (0..close.len())
+        .map(|t| {
+            let (h, l) = (hh[t]?, ll[t]?);
+            Some(if h > l {
+                100.0 * (close[t] - l) / (h - l)
+            } else {
+                50.0
+            })
+        })
+        .collect();
+    let k = map_defined(&raw, |v| sma_series(v, k_smooth));
+    let d = map_defined(&k, |v| sma_series(v, d_period));
+    Stochastic { k, d }
+}
+
+/// CCI บน typical price (h+l+c)/3 ; mean deviation เป็น 0 ให้ค่า 0
+pub fn cci_series(high: &[f64], low: &[f64], close: &[f64], period: usize) -> Vec<Option<f64>> {
+    let tp: Vec<f64> = (0..close.len())
+        .map(|t| (high[t] + low[t] + close[t]) / 3.0)
+        .collect();
+    sma_series(&tp, period)
+        .iter()
+        .enumerate()
+        .map(|(t, m)| {
+            let m = (*m)?;
+            let md = tp[t + 1 - period..=t]
+                .iter()
+                .map(|x| (x - m).abs())
+                .sum::<f64>()
+                / period as f64;
+            Some(if md > 0.0 {
+                (tp[t] - m) / (0.015 * md)
+            } else {
+                0.0
+            })
+        })
+        .collect()
+}
+
+/// +DI / -DI / ADX แบบ Wilder ; DI มีค่าตั้งแต่ index period, ADX ตั้งแต่ 2·period - 1
+pub fn adx_series(high: &[f64], low: &[f64], close: &[f64], period: usize) -> Adx {
+    let n = close.len();
+    let mut out = Adx {
+        plus_di: vec![None; n],
+        minus_di: vec![None; n],
+        adx: vec![None; n],
+    };
+    if period == 0 {
+        return out;
+    }
+    let tr = true_range_series(high, low, close);
+    let wilder = || Ema::new(period, EmaConvention::Wilder);
+    let (mut tr_s, mut plus_s, mut minus_s, mut dx_s) = (wilder(), wilder(), wilder(), wilder());
+    for t in 1..n {
+        let up = high[t] - high[t - 1];
+        let down = low[t - 1] - low[t];
+        let plus_dm = if up > down && up > 0.0 { up } else { 0.0 };
+        let minus_dm = if down > up && down > 0.0 { down } else { 0.0 };
+        let (Some(atr), Some(p), Some(m)) = (
+            tr_s.update(tr[t]),
+            plus_s.update(plus_dm),
+            minus_s.update(minus_dm),
+        ) else {
+            continue;
+        };
+        let (pdi, mdi) = if atr > 0.0 {
+            (100.0 * p / atr, 100.0 * m / atr)
+        } else {
+            (0.0, 0.0)
+        };
+        out.plus_di[t] = Some(pdi);
+        out.minus_di[t] = Some(mdi);
+        let sum = pdi + mdi;
+        let dx = if sum > 0.0 {
+            100.0 * (pdi - mdi).abs() / sum
+        } else {
+            0.0
+        };
+        out.adx[t] = dx_s.update(dx);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rsi_golden_and_extremes() {
+        let data = [1.0, 2.0, 1.5, 2.5, 2.0];
+        let r = rsi_series(&data, 2);
+        assert_eq!(&r[..2], &[None, None]);
+        // seed: up = (1 + 0)/2, down = (0 + 0.5)/2 -> RS = 2
+        assert!((r[2].unwrap() - (100.0 - 100.0 / 3.0)).abs() < 1e-12);
+        // t=3: up = (0.5 + 1)/2 = 0.75, down = 0.25/2 = 0.125
+        assert!((r[3].unwrap() - (100.0 - 100.0 / 7.0)).abs() < 1e-12);
+
+        let rising: Vec<f64> = (0..20).map(f64::from).collect();
+        assert_eq!(rsi_series(&rising, 14)[19], Some(100.0));
+        let falling: Vec<f64> = rising.iter().rev().copied().collect();
+        assert_eq!(rsi_series(&falling, 14)[19], Some(0.0));
+    }
+
+    #[test]
+    fn test_macd_and_stochastic() {
+        let flat = [3.0; 40];
+        let m = macd_series(&flat, 12, 26, 9);
+        assert!(m.line[24].is_none() && m.line[25] == Some(0.0));
+        assert!(m.signal[32].is_none() && m.signal[33] == Some(0.0));
+        assert_eq!(m.histogram[39], Some(0.0));
+
+        let high = [10.0, 12.0, 11.0, 13.0, 14.0];
+        let low = [8.0, 9.0, 9.0, 10.0, 12.0];
+        let close = [9.0, 11.0, 10.0, 12.0, 13.0];
+        let s = stochastic_series(&high, &low, &close, 3, 1, 2);
+        // t=2: HH=12, LL=8 -> 100·(10-8)/4
+        assert_eq!(s.k[2], Some(50.0));
+        assert_eq!(s.k[3], Some(75.0));
+        assert_eq!(s.d[2], None);
+        assert_eq!(s.d[3], Some(62.5));
+    }
+
+    #[test]
+    fn test_cci_and_adx_on_trend() {
+        let high: Vec<f64> = (0..60).map(|t| 10.0 + t as f64).collect();
+        let low: Vec<f64> = high.iter().map(|h| h - 2.0).collect();
+        let close: Vec<f64> = high.iter().map(|h| h - 0.5).collect();
+
+        let c = cci_series(&high, &low, &close, 20);
+        assert!(c[18].is_none());
+        // typical price เพิ่มเป็นเส้นตรง -> CCI คงที่ = 9.5 / (0.015 · 5)
+        assert!((c[59].unwrap() - 9.5 / 0.075).abs() < 1e-9);
+
+        let a = adx_series(&high, &low, &close, 14);
+        assert!(a.plus_di[13].is_none() && a.plus_di[14].is_some());
+        assert!(a.adx[26].is_none() && a.adx[27].is_some());
+        assert_eq!(a.minus_di[59], Some(0.0));
+        assert!((a.adx[59].unwrap() - 100.0).abs() < 1e-9);
+    }
+}
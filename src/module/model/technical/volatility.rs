@@ -0,0 +1,166 @@
+// ความผันผวนและ channel: ATR, Bollinger, Donchian, Keltner
+// high / low / close ต้องยาวเท่ากัน (ถ้ามีแค่ close ให้ส่ง close ทั้งสามช่องได้)
+
+use crate::module::model::{
+    ema::{Ema, EmaConvention},
+    sma::sma_series,
+    streaming::Indicator,
+    technical::moving_average::{mask_warmup, pine_ema_values},
+};
+
+#[derive(Clone, Debug)]
+pub struct Bands {
+    pub upper: Vec<Option<f64>>,
+    pub middle: Vec<Option<f64>>,
+    pub lower: Vec<Option<f64>>,
+}
+
+impl Bands {
+    fn empty(n: usize) -> Self {
+        Self {
+            upper: vec![None; n],
+            middle: vec![None; n],
+            lower: vec![None; n],
+        }
+    }
+}
+
+/// true range ; แท่งแรกไม่มี close ก่อนหน้าจึงใช้ high - low (เหมือน ta.tr(true))
+pub fn true_range_series(high: &[f64], low: &[f64], close: &[f64]) -> Vec<f64> {
+    (0..close.len())
+        .map(|t| {
+            let hl = high[t] - low[t];
+            match t.checked_sub(1).map(|p| close[p]) {
+                Some(pc) => hl.max((high[t] - pc).abs()).max((low[t] - pc).abs()),
+                None => hl,
+            }
+        })
+        .collect()
+}
+
+/// ATR = Wilder RMA ของ true range (ta.atr) ; มีค่าตั้งแต่ index period-1
+pub fn atr_series(high: &[f64], low: &[f64], close: &[f64], period: usize) -> Vec<Option<f64>> {
+    Ema::new(period, EmaConvention::Wilder).run(true_range_series(high, low, close))
+}
+
+fn rolling(data: &[f64], period: usize, pick: fn(f64, f64) -> f64) -> Vec<Option<f64>> {
+    let n = data.len();
+    let mut out = vec![None; n];
+    if period == 0 || n < period {
+        return out;
+    }
+    for t in period - 1..n {
+        out[t] = data[t + 1 - period..=t].iter().copied().reduce(pick);
+    }
+    out
+}
+
+/// ค่าสูงสุดใน period แท่งล่าสุด (ta.highest)
+pub fn highest_series(data: &[f64], period: usize) -> Vec<Option<f64>> {
+    rolling(data, period, f64::max)
+}
+
+/// ค่าต่ำสุดใน period แท่งล่าสุด (ta.lowest)
+pub fn lowest_series(data: &[f64], period: usize) -> Vec<Option<f64>> {
+    rolling(data, period, f64::min)
+}
+
+/// Bollinger: SMA ± k·σ โดย σ เป็น population stdev (เหมือน ta.stdev ค่า default)
+pub fn bollinger_series(data: &[f64], period: usize, k: f64) -> Bands {
+    let n = data.len();
+    let mut bands = Bands::empty(n);
+    let middle = sma_series(data, period);
+    for (t, m) in middle.iter().enumerate() {
+        let Some(m) = *m else { continue };
+        let window = &data[t + 1 - period..=t];
+        let var = window.iter().map(|x| (x - m).powi(2)).sum::<f64>() / period as f64;
+        let sd = var.sqrt();
+        bands.upper[t] = Some(m + k * sd);
+        bands.lower[t] = Some(m - k * sd);
+    }
+    bands.middle = middle;
+    bands
+}
+
+/// Donchian: highest high / lowest low และเส้นกลาง
+pub fn donchian_series(high: &[f64], low: &[f64], period: usize) -> Bands {
+    let upper = highest_series(high, period);
+    let lower = lowest_series(low, period);
+    let middle = upper
+        .iter()
+        .zip(&lower)
+        .map(|(u, l)| Some(((*u)? + (*l)?) / 2.0))
+        .collect();
+    Bands {
+        upper,
+        middle,
+        lower,
+    }
+}
+
+/// Keltner (ta.kc แบบ useTrueRange): EMA(close) ± mult·EMA(true range) ; EMA แบบ Pine ซ่อน period-1 แท่งแรก
+pub fn keltner_series(high: &[f64], low: &[f64], close: &[f64], period: usize, mult: f64) -> Bands {
+    let n = close.len();
+    if period == 0 {
+        return Bands::empty(n);
+    }
+    let basis = pine_ema_values(close, period);
+    let range = pine_ema_values(&true_range_series(high, low, close), period);
+    let band = |sign: f64| {
+        let v = basis
+            .iter()
+            .zip(&range)
+            .map(|(b, r)| Some(b + sign * mult * r))
+            .collect();
+        mask_warmup(v, period - 1)
+    };
+    Bands {
+        upper: band(1.0),
+        middle: band(0.0),
+        lower: band(-1.0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HIGH: [f64; 5] = [10.0, 11.0, 12.0, 11.5, 13.0];
+    const LOW: [f64; 5] = [9.0, 9.5, 10.5, 10.0, 11.0];
+    const CLOSE: [f64; 5] = [9.5, 10.5, 11.0, 10.2, 12.5];
+
+    #[test]
+    fn test_true_range_and_atr() {
+        let tr = true_range_series(&HIGH, &LOW, &CLOSE);
+        for (a, b) in tr.iter().zip([1.0, 1.5, 1.5, 1.5, 2.8]) {
+            assert!((a - b).abs() < 1e-12);
+        }
+        let atr = atr_series(&HIGH, &LOW, &CLOSE, 3);
+        assert_eq!(&atr[..2], &[None, None]);
+        // seed = SMA(1, 1.5, 1.5) แล้ว Wilder alpha = 1/3
+        assert!((atr[2].unwrap() - 4.0 / 3.0).abs() < 1e-12);
+        assert!((atr[4].unwrap() - (atr[3].unwrap() * 2.0 + 2.8) / 3.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_channels() {
+        let d = donchian_series(&HIGH, &LOW, 3);
+        assert_eq!(d.upper[3], Some(12.0));
+        assert_eq!(d.lower[4], Some(10.0));
+        assert_eq!(d.middle[2], Some(10.5));
+
+        let b = bollinger_series(&[1.0, 3.0, 1.0, 3.0], 2, 2.0);
+        assert_eq!(b.middle[1], Some(2.0));
+        assert_eq!(b.upper[1], Some(4.0));
+        assert_eq!(b.lower[3], Some(0.0));
+        assert!(b.upper[0].is_none());
+
+        let k = keltner_series(&HIGH, &LOW, &CLOSE, 3, 2.0);
+        assert!(k.middle[1].is_none());
+        let width = k.upper[4].unwrap() - k.lower[4].unwrap();
+        assert!(
+            (width - 4.0 * pine_ema_values(&true_range_series(&HIGH, &LOW, &CLOSE), 3)[4]).abs()
+                < 1e-12
+        );
+    }
+}
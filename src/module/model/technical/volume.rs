@@ -0,0 +1,80 @@
+// indicator ที่ใช้ volume: OBV และ VWAP
+
+/// On-balance volume (ta.obv): สะสม volume ตามทิศทางของ close ; แท่งแรกเป็น 0
+pub fn obv_series(close: &[f64], volume: &[f64]) -> Vec<Option<f64>> {
+    let mut obv = 0.0;
+    (0..close.len())
+        .map(|t| {
+            if t > 0 {
+                let d = close[t] - close[t - 1];
+                if d > 0.0 {
+                    obv += volume[t];
+                } else if d < 0.0 {
+                    obv -= volume[t];
+                }
+            }
+            Some(obv)
+        })
+        .collect()
+}
+
+fn typical_price(high: f64, low: f64, close: f64) -> f64 {
+    (high + low + close) / 3.0
+}
+
+/// VWAP สะสมตั้งแต่แท่งแรก (anchor ที่ต้น series) ราคาใช้ hlc3 ; None จนกว่าจะมี volume
+pub fn vwap_series(high: &[f64], low: &[f64], close: &[f64], volume: &[f64]) -> Vec<Option<f64>> {
+    let (mut pv, mut vol) = (0.0, 0.0);
+    (0..close.len())
+        .map(|t| {
+            pv += typical_price(high[t], low[t], close[t]) * volume[t];
+            vol += volume[t];
+            (vol > 0.0).then(|| pv / vol)
+        })
+        .collect()
+}
+
+/// VWAP ของ period แท่งล่าสุด (ใช้กับข้อมูลรายวันที่ session VWAP ไม่มีความหมาย)
+pub fn rolling_vwap_series(
+    high: &[f64],
+    low: &[f64],
+    close: &[f64],
+    volume: &[f64],
+    period: usize,
+) -> Vec<Option<f64>> {
+    let n = close.len();
+    let mut out = vec![None; n];
+    if period == 0 || n < period {
+        return out;
+    }
+    let pv: Vec<f64> = (0..n)
+        .map(|i| typical_price(high[i], low[i], close[i]) * volume[i])
+        .collect();
+    for (t, (pv, vol)) in pv.windows(period).zip(volume.windows(period)).enumerate() {
+        let vol: f64 = vol.iter().sum();
+        out[t + period - 1] = (vol > 0.0).then(|| pv.iter().sum::<f64>() / vol);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_obv_and_vwap() {
+        let close = [10.0, 11.0, 11.0, 10.0, 12.0];
+        let volume = [100.0, 200.0, 50.0, 300.0, 100.0];
+        let obv: Vec<f64> = obv_series(&close, &volume).into_iter().flatten().collect();
+        assert_eq!(obv, vec![0.0, 200.0, 200.0, -100.0, 0.0]);
+
+        // ใช้ close เป็น high/low ด้วย -> typical price = close
+        let v = vwap_series(&close, &close, &close, &volume);
+        assert_eq!(v[0], Some(10.0));
+        assert!((v[1].unwrap() - (1000.0 + 2200.0) / 300.0).abs() < 1e-12);
+        let r = rolling_vwap_series(&close, &close, &close, &volume, 2);
+        assert!(r[0].is_none());
+        assert!((r[4].unwrap() - (3000.0 + 1200.0) / 400.0).abs() < 1e-12);
+        assert_eq!(vwap_series(&[1.0], &[1.0], &[1.0], &[0.0]), vec![None]);
+    }
+}
@@ -1 +1,4 @@
-
+pub mod numdiff;
+pub mod optimize;
+pub mod polynomial;
+pub mod unpack_params;
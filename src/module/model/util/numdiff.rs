@@ -0,0 +1,102 @@
+// อนุพันธ์เชิงตัวเลข (central difference) สำหรับ gradient / Hessian ของ objective
+
+use nalgebra::DMatrix;
+
+/// ขนาดก้าวแบบ relative: h_i = eps · max(|x_i|, 1)
+fn step(x: f64, eps: f64) -> f64 {
+    eps * x.abs().max(1.0)
+}
+
+pub fn numerical_gradient<F>(f: &F, x: &[f64]) -> Vec<f64>
+where
+    F: Fn(&[f64]) -> f64,
+{
+    let mut g = vec![0.0; x.len()];
+    let mut xp = x.to_vec();
+    for i in 0..x.len() {
+        let h = step(x[i], 1e-6);
+        xp[i] = x[i] + h;
+        let fp = f(&xp);
+        xp[i] = x[i] - h;
+        let fm = f(&xp);
+        xp[i] = x[i];
+        g[i] = (fp - fm) / (2.0 * h);
+    }
+    g
+}
+
+/// Hessian แบบ central difference (สมมาตร)
+pub fn numerical_hessian<F>(f: &F, x: &[f64]) -> Vec<Vec<f64>>
+where
+    F: Fn(&[f64]) -> f64,
+{
+    let n = x.len();
+    let mut hess = vec![vec![0.0; n]; n];
+    let f0 = f(x);
+    let mut xp = x.to_vec();
+    let h: Vec<f64> = x.iter().map(|&v| step(v, 1e-4)).collect();
+    for i in 0..n {
+        xp[i] = x[i] + h[i];
+        let fp = f(&xp);
+        xp[i] = x[i] - h[i];
+        let fm = f(&xp);
+        xp[i] = x[i];
+        hess[i][i] = (fp - 2.0 * f0 + fm) / (h[i] * h[i]);
+        for j in 0..i {
+            let mut eval = |si: f64, sj: f64| {
+                xp[i] = x[i] + si * h[i];
+                xp[j] = x[j] + sj * h[j];
+                let v = f(&xp);
+                xp[i] = x[i];
+                xp[j] = x[j];
+                v
+            };
+            let v = (eval(1.0, 1.0) - eval(1.0, -1.0) - eval(-1.0, 1.0) + eval(-1.0, -1.0))
+                / (4.0 * h[i] * h[j]);
+            hess[i][j] = v;
+            hess[j][i] = v;
+        }
+    }
+    hess
+}
+
+/// covariance = inverse Hessian ของ -loglik (None ถ้าไม่ positive definite)
+pub fn covariance_from_hessian(hess: &[Vec<f64>]) -> Option<Vec<Vec<f64>>> {
+    let k = hess.len();
+    let h = DMatrix::from_fn(k, k, |i, j| hess[i][j]);
+    if h.iter().any(|v| !v.is_finite()) {
+        return None;
+    }
+    let cov = h.cholesky()?.inverse();
+    Some(
+        (0..k)
+            .map(|i| (0..k).map(|j| cov[(i, j)]).collect())
+            .collect(),
+    )
+}
+
+/// standard error จาก inverse Hessian (NaN ถ้า Hessian ไม่ positive definite)
+pub fn hessian_std_errors(hess: &[Vec<f64>]) -> Vec<f64> {
+    match covariance_from_hessian(hess) {
+        Some(cov) => (0..cov.len()).map(|i| cov[i][i].max(0.0).sqrt()).collect(),
+        None => vec![f64::NAN; hess.len()],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quadratic_derivatives() {
+        // f = x² + 3xy + 2y²
+        let f = |v: &[f64]| v[0] * v[0] + 3.0 * v[0] * v[1] + 2.0 * v[1] * v[1];
+        let g = numerical_gradient(&f, &[1.0, 2.0]);
+        assert!((g[0] - 8.0).abs() < 1e-6);
+        assert!((g[1] - 11.0).abs() < 1e-6);
+        let h = numerical_hessian(&f, &[1.0, 2.0]);
+        assert!((h[0][0] - 2.0).abs() < 1e-4);
+        assert!((h[0][1] - 3.0).abs() < 1e-4);
+        assert!((h[1][1] - 4.0).abs() < 1e-4);
+    }
+}
@@ -0,0 +1,658 @@
+// ตัว optimizer สำหรับ fit model (minimize f: ℝⁿ -> ℝ)
+// - NelderMead: ไม่ใช้อนุพันธ์ ทนต่อ objective ที่ไม่เรียบ / มีขอบเขต (คืน ∞ นอกขอบเขต)
+// - Bfgs / Lbfgs: quasi-Newton + Armijo backtracking ใช้ gradient เชิงตัวเลข
+// - LevenbergMarquardt: least squares จาก residual vector หรือ damped Newton บน objective ทั่วไป
+// ค่า NaN ของ objective ถือเป็น +∞ ทุกตัว (ไม่ panic)
+
+use nalgebra::{DMatrix, DVector};
+
+use crate::module::model::util::numdiff::{numerical_gradient, numerical_hessian};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Convergence {
+    Converged,
+    MaxIterations,
+    /// หา step ที่ทำให้ f ลดลงไม่ได้แล้ว (มักเกิดเมื่ออยู่ใกล้จุดต่ำสุดมาก ๆ หรือ gradient ไม่แม่น)
+    Stalled,
+    /// f หรือ gradient ที่จุดเริ่มไม่เป็นตัวเลขจำกัด
+    NonFinite,
+}
+
+#[derive(Clone, Debug)]
+pub struct OptimResult {
+    pub x: Vec<f64>,
+    pub fx: f64,
+    pub iterations: usize,
+    /// ‖∇f(x)‖₂ ณ จุดสุดท้าย (เชิงตัวเลข)
+    pub grad_norm: f64,
+    pub status: Convergence,
+}
+
+impl OptimResult {
+    pub fn converged(&self) -> bool {
+        self.status == Convergence::Converged
+    }
+}
+
+pub trait Optimizer: Sync {
+    fn minimize(&self, f: &dyn Fn(&[f64]) -> f64, x0: &[f64]) -> OptimResult;
+}
+
+fn finite_or_inf(v: f64) -> f64 {
+    if v.is_nan() { f64::INFINITY } else { v }
+}
+
+fn norm(v: &[f64]) -> f64 {
+    v.iter().map(|x| x * x).sum::<f64>().sqrt()
+}
+
+fn dot(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b).map(|(x, y)| x * y).sum()
+}
+
+fn gradient(f: &dyn Fn(&[f64]) -> f64, x: &[f64]) -> Vec<f64> {
+    numerical_gradient(&|v: &[f64]| f(v), x)
+}
+
+fn result(
+    f: &dyn Fn(&[f64]) -> f64,
+    x: Vec<f64>,
+    fx: f64,
+    iterations: usize,
+    status: Convergence,
+) -> OptimResult {
+    let grad_norm = if x.is_empty() {
+        0.0
+    } else {
+        norm(&gradient(f, &x))
+    };
+    OptimResult {
+        x,
+        fx,
+        iterations,
+        grad_norm,
+        status,
+    }
+}
+
+// ---------------- Nelder–Mead ----------------
+
+#[derive(Clone, Copy, Debug)]
+pub struct NelderMead {
+    pub max_iter: usize,
+    /// หยุดเมื่อ f(แย่สุด) - f(ดีสุด) ใน simplex < tol
+    pub tol: f64,
+    /// เริ่ม simplex ใหม่รอบจุดที่ได้อีกกี่ครั้ง (แต่ละรอบ tol เล็กลง 10 เท่า)
+    pub restarts: usize,
+}
+
+impl Default for NelderMead {
+    fn default() -> Self {
+        Self::new(800, 1e-7)
+    }
+}
+
+impl NelderMead {
+    pub fn new(max_iter: usize, tol: f64) -> Self {
+        Self {
+            max_iter,
+            tol,
+            restarts: 0,
+        }
+    }
+
+    pub fn with_restarts(mut self, restarts: usize) -> Self {
+        self.restarts = restarts;
+        self
+    }
+
+    fn run(
+        &self,
+        f: &dyn Fn(&[f64]) -> f64,
+        x0: Vec<f64>,
+        tol: f64,
+    ) -> (Vec<f64>, f64, usize, bool) {
+        let f = |x: &[f64]| finite_or_inf(f(x));
+        let n = x0.len();
+        let mut sp: Vec<(Vec<f64>, f64)> = Vec::with_capacity(n + 1);
+        let f0 = f(&x0);
+        sp.push((x0.clone(), f0));
+        if n == 0 {
+            return (x0, f0, 0, true);
+        }
+        for i in 0..n {
+            let mut xi = x0.clone();
+            xi[i] = if xi[i].abs() > 1e-6 {
+                xi[i] * 1.05
+            } else {
+                0.001
+            };
+            let fi = f(&xi);
+            sp.push((xi, fi));
+        }
+        let (alpha, gamma, rho, sigma) = (1.0, 2.0, 0.5, 0.5);
+        let mut converged = false;
+        let mut iterations = 0;
+        for _ in 0..self.max_iter {
+            sp.sort_by(|a, b| a.1.total_cmp(&b.1));
+            // ∞ - ∞ = NaN จึงเทียบแบบนี้แทน abs() < tol
+            if sp[n].1 - sp[0].1 < tol {
+                converged = true;
+                break;
+            }
+            iterations += 1;
+            let mut cen = vec![0.0; n];
+            for (x, _) in &sp[..n] {
+                for j in 0..n {
+                    cen[j] += x[j];
+                }
+            }
+            for c in cen.iter_mut() {
+                *c /= n as f64;
+            }
+            let xr: Vec<f64> = (0..n)
+                .map(|j| cen[j] + alpha * (cen[j] - sp[n].0[j]))
+                .collect();
+            let fr = f(&xr);
+            if fr < sp[0].1 {
+                let xe: Vec<f64> = (0..n).map(|j| cen[j] + gamma * (xr[j] - cen[j])).collect();
+                let fe = f(&xe);
+                sp[n] = if fe < fr { (xe, fe) } else { (xr, fr) };
+            } else if fr < sp[n - 1].1 {
+                sp[n] = (xr, fr);
+            } else {
+                let xc: Vec<f64> = if fr < sp[n].1 {
+                    (0..n).map(|j| cen[j] + rho * (xr[j] - cen[j])).collect()
+                } else {
+                    (0..n)
+                        .map(|j| cen[j] + rho * (sp[n].0[j] - cen[j]))
+                        .collect()
+                };
+                let fc = f(&xc);
+                if fc < sp[n].1 {
+                    sp[n] = (xc, fc);
+                } else {
+                    let best = sp[0].0.clone();
+                    for item in sp.iter_mut().skip(1) {
+                        let xi: Vec<f64> = (0..n)
+                            .map(|j| best[j] + sigma * (item.0[j] - best[j]))
+                            .collect();
+                        let fi = f(&xi);
+                        *item = (xi, fi);
+                    }
+                }
+            }
+        }
+        sp.sort_by(|a, b| a.1.total_cmp(&b.1));
+        let (x, fx) = sp.swap_remove(0);
+        (x, fx, iterations, converged)
+    }
+}
+
+impl Optimizer for NelderMead {
+    fn minimize(&self, f: &dyn Fn(&[f64]) -> f64, x0: &[f64]) -> OptimResult {
+        let mut x = x0.to_vec();
+        let mut fx = f64::INFINITY;
+        let mut iterations = 0;
+        let mut converged = false;
+        let mut tol = self.tol;
+        for _ in 0..=self.restarts {
+            let (xn, fxn, it, conv) = self.run(f, x, tol);
+            x = xn;
+            fx = fxn;
+            iterations += it;
+            converged = conv;
+            tol *= 0.1;
+        }
+        let status = if !fx.is_finite() {
+            Convergence::NonFinite
+        } else if converged {
+            Convergence::Converged
+        } else {
+            Convergence::MaxIterations
+        };
+        result(f, x, fx, iterations, status)
+    }
+}
+
+// ---------------- BFGS / L-BFGS ----------------
+
+/// วิธีประมาณ inverse Hessian ของ quasi-Newton
+trait InverseHessian {
+    /// ทิศทาง d = -H g
+    fn direction(&self, g: &[f64]) -> Vec<f64>;
+    fn update(&mut self, s: &[f64], y: &[f64]);
+    fn reset(&mut self);
+}
+
+struct DenseInverse(DMatrix<f64>);
+
+impl InverseHessian for DenseInverse {
+    fn direction(&self, g: &[f64]) -> Vec<f64> {
+        (-(&self.0 * DVector::from_column_slice(g)))
+            .iter()
+            .copied()
+            .collect()
+    }
+
+    fn update(&mut self, s: &[f64], y: &[f64]) {
+        let rho = 1.0 / dot(s, y);
+        let n = s.len();
+        let s = DVector::from_column_slice(s);
+        let y = DVector::from_column_slice(y);
+        let left = DMatrix::identity(n, n) - rho * &s * y.transpose();
+        self.0 = &left * &self.0 * left.transpose() + rho * &s * s.transpose();
+    }
+
+    fn reset(&mut self) {
+        self.0.fill_with_identity();
+    }
+}
+
+struct LimitedInverse {
+    m: usize,
+    pairs: Vec<(Vec<f64>, Vec<f64>)>,
+}
+
+impl InverseHessian for LimitedInverse {
+    /// two-loop recursion (Nocedal & Wright, Algorithm 7.4)
+    fn direction(&self, g: &[f64]) -> Vec<f64> {
+        let mut q = g.to_vec();
+        let mut alphas = Vec::with_capacity(self.pairs.len());
+        for (s, y) in self.pairs.iter().rev() {
+            let a = dot(s, &q) / dot(s, y);
+            for (qi, yi) in q.iter_mut().zip(y) {
+                *qi -= a * yi;
+            }
+            alphas.push(a);
+        }
+        let gamma = self
+            .pairs
+            .last()
+            .map_or(1.0, |(s, y)| dot(s, y) / dot(y, y));
+        for qi in q.iter_mut() {
+            *qi *= gamma;
+        }
+        for ((s, y), a) in self.pairs.iter().zip(alphas.iter().rev()) {
+            let b = dot(y, &q) / dot(s, y);
+            for (qi, si) in q.iter_mut().zip(s) {
+                *qi += (a - b) * si;
+            }
+        }
+        q.iter().map(|v| -v).collect()
+    }
+
+    fn update(&mut self, s: &[f64], y: &[f64]) {
+        if self.pairs.len() == self.m {
+            self.pairs.remove(0);
+        }
+        self.pairs.push((s.to_vec(), y.to_vec()));
+    }
+
+    fn reset(&mut self) {
+        self.pairs.clear();
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct QuasiNewtonConfig {
+    pub max_iter: usize,
+    /// หยุดเมื่อ ‖g‖ < grad_tol
+    pub grad_tol: f64,
+    /// หยุดเมื่อ f ลดลงน้อยกว่า f_tol · (1 + |f|)
+    pub f_tol: f64,
+}
+
+impl Default for QuasiNewtonConfig {
+    fn default() -> Self {
+        Self {
+            max_iter: 500,
+            grad_tol: 1e-6,
+            f_tol: 1e-12,
+        }
+    }
+}
+
+/// weak Wolfe line search แบบ bisection / expansion (Lewis & Overton 2013)
+/// คืน (x + t·d, f, ∇f) ที่ f ลดลงพอ (Armijo) ; ถ้าทำได้จะได้ curvature condition ด้วย
+fn wolfe_search(
+    f: &dyn Fn(&[f64]) -> f64,
+    x: &[f64],
+    fx: f64,
+    d: &[f64],
+    slope: f64,
+) -> Option<(Vec<f64>, f64, Vec<f64>)> {
+    const C1: f64 = 1e-4;
+    const C2: f64 = 0.9;
+    let (mut lo, mut hi) = (0.0, f64::INFINITY);
+    let mut t = 1.0;
+    let mut armijo_ok = None;
+    for _ in 0..60 {
+        let xn: Vec<f64> = x.iter().zip(d).map(|(a, b)| a + t * b).collect();
+        let fxn = f(&xn);
+        if fxn > fx + C1 * t * slope {
+            hi = t;
+        } else {
+            let gn = gradient(f, &xn);
+            if gn.iter().any(|v| !v.is_finite()) {
+                return None;
+            }
+            if dot(&gn, d) < C2 * slope {
+                lo = t;
+                armijo_ok = Some((xn, fxn, gn));
+            } else {
+                return Some((xn, fxn, gn));
+            }
+        }
+        t = if hi.is_finite() {
+            0.5 * (lo + hi)
+        } else {
+            2.0 * lo
+        };
+    }
+    armijo_ok
+}
+
+fn quasi_newton(
+    f: &dyn Fn(&[f64]) -> f64,
+    x0: &[f64],
+    config: &QuasiNewtonConfig,
+    h: &mut dyn InverseHessian,
+) -> OptimResult {
+    let f = |x: &[f64]| finite_or_inf(f(x));
+    let mut x = x0.to_vec();
+    let mut fx = f(&x);
+    let mut g = gradient(&f, &x);
+    if !fx.is_finite() || g.iter().any(|v| !v.is_finite()) {
+        return result(&f, x, fx, 0, Convergence::NonFinite);
+    }
+    let mut fresh = true;
+    for it in 0..config.max_iter {
+        if norm(&g) < config.grad_tol {
+            return result(&f, x, fx, it, Convergence::Converged);
+        }
+        let mut d = h.direction(&g);
+        let mut slope = dot(&g, &d);
+        if slope.is_nan() || slope >= 0.0 {
+            h.reset();
+            fresh = true;
+            d = g.iter().map(|v| -v).collect();
+            slope = -dot(&g, &g);
+        }
+
+        let Some((xn, fxn, gn)) = wolfe_search(&f, &x, fx, &d, slope) else {
+            if fresh {
+                return result(&f, x, fx, it, Convergence::Stalled);
+            }
+            h.reset();
+            fresh = true;
+            continue;
+        };
+        let s: Vec<f64> = xn.iter().zip(&x).map(|(a, b)| a - b).collect();
+        let y: Vec<f64> = gn.iter().zip(&g).map(|(a, b)| a - b).collect();
+        if dot(&s, &y) > 1e-12 * norm(&s) * norm(&y) {
+            h.update(&s, &y);
+            fresh = false;
+        }
+        let decrease = fx - fxn;
+        x = xn;
+        fx = fxn;
+        g = gn;
+        if decrease < config.f_tol * (1.0 + fx.abs()) {
+            return result(&f, x, fx, it + 1, Convergence::Converged);
+        }
+    }
+    result(&f, x, fx, config.max_iter, Convergence::MaxIterations)
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Bfgs {
+    pub config: QuasiNewtonConfig,
+}
+
+impl Bfgs {
+    pub fn new(config: QuasiNewtonConfig) -> Self {
+        Self { config }
+    }
+}
+
+impl Optimizer for Bfgs {
+    fn minimize(&self, f: &dyn Fn(&[f64]) -> f64, x0: &[f64]) -> OptimResult {
+        let n = x0.len();
+        let mut h = DenseInverse(DMatrix::identity(n, n));
+        quasi_newton(f, x0, &self.config, &mut h)
+    }
+}
+
+/// L-BFGS เก็บแค่ m คู่ (s, y) ล่าสุด เหมาะกับพารามิเตอร์จำนวนมาก
+#[derive(Clone, Copy, Debug)]
+pub struct Lbfgs {
+    pub memory: usize,
+    pub config: QuasiNewtonConfig,
+}
+
+impl Default for Lbfgs {
+    fn default() -> Self {
+        Self::new(10, QuasiNewtonConfig::default())
+    }
+}
+
+impl Lbfgs {
+    pub fn new(memory: usize, config: QuasiNewtonConfig) -> Self {
+        Self {
+            memory: memory.max(1),
+            config,
+        }
+    }
+}
+
+impl Optimizer for Lbfgs {
+    fn minimize(&self, f: &dyn Fn(&[f64]) -> f64, x0: &[f64]) -> OptimResult {
+        let mut h = LimitedInverse {
+            m: self.memory,
+            pairs: Vec::new(),
+        };
+        quasi_newton(f, x0, &self.config, &mut h)
+    }
+}
+
+// ---------------- Levenberg–Marquardt ----------------
+
+#[derive(Clone, Copy, Debug)]
+pub struct LevenbergMarquardt {
+    pub max_iter: usize,
+    pub grad_tol: f64,
+    /// หยุดเมื่อ f ลดลงสัมพัทธ์น้อยกว่า f_tol
+    pub f_tol: f64,
+    pub lambda0: f64,
+}
+
+impl Default for LevenbergMarquardt {
+    fn default() -> Self {
+        Self {
+            max_iter: 200,
+            grad_tol: 1e-8,
+            f_tol: 1e-12,
+            lambda0: 1e-3,
+        }
+    }
+}
+
+/// Jacobian เชิงตัวเลข (forward difference) ของ r: ℝⁿ -> ℝᵐ
+fn jacobian(r: &dyn Fn(&[f64]) -> Vec<f64>, x: &[f64], r0: &[f64]) -> DMatrix<f64> {
+    let mut jac = DMatrix::zeros(r0.len(), x.len());
+    let mut xp = x.to_vec();
+    for j in 0..x.len() {
+        let h = 1e-7 * x[j].abs().max(1.0);
+        xp[j] = x[j] + h;
+        let rp = r(&xp);
+        xp[j] = x[j];
+        for i in 0..r0.len() {
+            jac[(i, j)] = (rp[i] - r0[i]) / h;
+        }
+    }
+    jac
+}
+
+/// x -> (gradient, A) ของ quadratic model รอบ x
+type LocalModel<'a> = dyn Fn(&[f64]) -> Option<(Vec<f64>, DMatrix<f64>)> + 'a;
+
+impl LevenbergMarquardt {
+    /// แก้ (A + λ·diag(A)) δ = -g แล้วปรับ λ ตามว่า f ลดลงหรือไม่
+    /// model(x) คืน (f, g, A) ; A = JᵀJ สำหรับ least squares หรือ Hessian สำหรับ objective ทั่วไป
+    fn run(
+        &self,
+        f: &dyn Fn(&[f64]) -> f64,
+        model: &LocalModel,
+        x0: &[f64],
+    ) -> (Vec<f64>, f64, usize, Convergence) {
+        let f = |x: &[f64]| finite_or_inf(f(x));
+        let mut x = x0.to_vec();
+        let mut fx = f(&x);
+        let Some((mut g, mut a)) = model(&x).filter(|_| fx.is_finite()) else {
+            return (x, fx, 0, Convergence::NonFinite);
+        };
+        let n = x.len();
+        let mut lambda = self.lambda0;
+        for it in 0..self.max_iter {
+            if norm(&g) < self.grad_tol {
+                return (x, fx, it, Convergence::Converged);
+            }
+            let mut stepped = false;
+            while lambda < 1e16 {
+                let mut damped = a.clone();
+                for i in 0..n {
+                    damped[(i, i)] += lambda * (a[(i, i)].abs() + 1e-12);
+                }
+                let rhs = -DVector::from_column_slice(&g);
+                let delta = damped.cholesky().map(|c| c.solve(&rhs));
+                if let Some(delta) = delta {
+                    let xn: Vec<f64> = x.iter().zip(delta.iter()).map(|(a, b)| a + b).collect();
+                    let fxn = f(&xn);
+                    if fxn < fx {
+                        let rel = (fx - fxn) / (1.0 + fx.abs());
+                        x = xn;
+                        fx = fxn;
+                        lambda = (lambda * 0.1).max(1e-12);
+                        stepped = true;
+                        if rel < self.f_tol {
+                            return (x, fx, it + 1, Convergence::Converged);
+                        }
+                        break;
+                    }
+                }
+                lambda *= 10.0;
+            }
+            if !stepped {
+                return (x, fx, it, Convergence::Stalled);
+            }
+            match model(&x) {
+                Some((gn, an)) => {
+                    g = gn;
+                    a = an;
+                }
+                None => return (x, fx, it + 1, Convergence::Stalled),
+            }
+        }
+        (x, fx, self.max_iter, Convergence::MaxIterations)
+    }
+
+    /// minimize ½‖r(x)‖² (Gauss–Newton ที่มี damping) ; fx ที่คืนคือ ‖r‖²
+    pub fn least_squares(&self, residuals: &dyn Fn(&[f64]) -> Vec<f64>, x0: &[f64]) -> OptimResult {
+        let sse = |x: &[f64]| residuals(x).iter().map(|v| v * v).sum::<f64>();
+        let model = |x: &[f64]| {
+            let r = residuals(x);
+            if r.iter().any(|v| !v.is_finite()) {
+                return None;
+            }
+            let jac = jacobian(residuals, x, &r);
+            let g = jac.transpose() * DVector::from_column_slice(&r) * 2.0;
+            let a = jac.transpose() * &jac * 2.0;
+            Some((g.iter().copied().collect(), a))
+        };
+        let (x, fx, iterations, status) = self.run(&sse, &model, x0);
+        result(&sse, x, fx, iterations, status)
+    }
+}
+
+impl Optimizer for LevenbergMarquardt {
+    /// damped Newton: A = Hessian เชิงตัวเลข
+    fn minimize(&self, f: &dyn Fn(&[f64]) -> f64, x0: &[f64]) -> OptimResult {
+        let model = |x: &[f64]| {
+            let g = gradient(f, x);
+            let hess = numerical_hessian(&|v: &[f64]| f(v), x);
+            let n = x.len();
+            let a = DMatrix::from_fn(n, n, |i, j| hess[i][j]);
+            if g.iter().any(|v| !v.is_finite()) || a.iter().any(|v| !v.is_finite()) {
+                return None;
+            }
+            Some((g, a))
+        };
+        let (x, fx, iterations, status) = self.run(f, &model, x0);
+        result(f, x, fx, iterations, status)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rosenbrock(x: &[f64]) -> f64 {
+        (1.0 - x[0]).powi(2) + 100.0 * (x[1] - x[0] * x[0]).powi(2)
+    }
+
+    #[test]
+    fn test_all_optimizers_minimize_rosenbrock() {
+        let optimizers: Vec<(&str, Box<dyn Optimizer>)> = vec![
+            (
+                "nm",
+                Box::new(NelderMead::new(5000, 1e-14).with_restarts(2)),
+            ),
+            ("bfgs", Box::new(Bfgs::default())),
+            ("lbfgs", Box::new(Lbfgs::default())),
+            ("lm", Box::new(LevenbergMarquardt::default())),
+        ];
+        for (name, opt) in optimizers {
+            let r = opt.minimize(&rosenbrock, &[-1.2, 1.0]);
+            assert!(r.converged(), "{name}: {:?}", r.status);
+            assert!((r.x[0] - 1.0).abs() < 1e-3, "{name}: {:?}", r.x);
+            assert!((r.x[1] - 1.0).abs() < 1e-3, "{name}: {:?}", r.x);
+            assert!(r.iterations > 0 && r.grad_norm < 1e-2, "{name}: {r:?}");
+        }
+    }
+
+    #[test]
+    fn test_nan_objective_does_not_panic() {
+        // NaN นอก x > 0 ; จุดต่ำสุดที่ x = 2
+        let f = |x: &[f64]| {
+            if x[0] <= 0.0 {
+                f64::NAN
+            } else {
+                (x[0] - 2.0).powi(2)
+            }
+        };
+        let r = NelderMead::default().minimize(&f, &[0.0005]);
+        assert!((r.x[0] - 2.0).abs() < 1e-3, "{r:?}");
+        let r = Bfgs::default().minimize(&f, &[1.0]);
+        assert!((r.x[0] - 2.0).abs() < 1e-4, "{r:?}");
+        let r = Bfgs::default().minimize(&f, &[-1.0]);
+        assert_eq!(r.status, Convergence::NonFinite);
+    }
+
+    #[test]
+    fn test_least_squares_exponential_fit() {
+        // y = 2 e^{-0.5 t}
+        let t: Vec<f64> = (0..20).map(|i| i as f64 * 0.25).collect();
+        let y: Vec<f64> = t.iter().map(|t| 2.0 * (-0.5 * t).exp()).collect();
+        let r = |p: &[f64]| -> Vec<f64> {
+            t.iter()
+                .zip(&y)
+                .map(|(t, y)| p[0] * (p[1] * t).exp() - y)
+                .collect()
+        };
+        let res = LevenbergMarquardt::default().least_squares(&r, &[1.0, 0.0]);
+        assert!(res.converged(), "{res:?}");
+        assert!((res.x[0] - 2.0).abs() < 1e-6 && (res.x[1] + 0.5).abs() < 1e-6);
+        assert!(res.fx < 1e-12);
+    }
+}
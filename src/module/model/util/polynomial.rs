@@ -0,0 +1,74 @@
+// พหุนามใน lag operator B เก็บเป็นสัมประสิทธิ์ [a_0, a_1, ...] ของ a_0 + a_1 B + ...
+
+pub fn poly_mul(a: &[f64], b: &[f64]) -> Vec<f64> {
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+    let mut out = vec![0.0; a.len() + b.len() - 1];
+    for (i, x) in a.iter().enumerate() {
+        for (j, y) in b.iter().enumerate() {
+            out[i + j] += x * y;
+        }
+    }
+    out
+}
+
+/// 1 - φ_1 B^s - φ_2 B^{2s} - ... (s = 1 คือ AR ปกติ)
+pub fn ar_poly(phi: &[f64], s: usize) -> Vec<f64> {
+    let mut poly = vec![0.0; 1 + phi.len() * s];
+    poly[0] = 1.0;
+    for (i, v) in phi.iter().enumerate() {
+        poly[(i + 1) * s] = -v;
+    }
+    poly
+}
+
+/// 1 + θ_1 B^s + θ_2 B^{2s} + ...
+pub fn ma_poly(theta: &[f64], s: usize) -> Vec<f64> {
+    let mut poly = vec![0.0; 1 + theta.len() * s];
+    poly[0] = 1.0;
+    for (j, v) in theta.iter().enumerate() {
+        poly[(j + 1) * s] = *v;
+    }
+    poly
+}
+
+/// (1 - B)^d (1 - B^s)^D
+pub fn diff_poly(d: usize, seasonal_d: usize, s: usize) -> Vec<f64> {
+    let mut poly = vec![1.0];
+    for _ in 0..d {
+        poly = poly_mul(&poly, &[1.0, -1.0]);
+    }
+    for _ in 0..seasonal_d {
+        poly = poly_mul(&poly, &ar_poly(&[1.0], s));
+    }
+    poly
+}
+
+/// ย้อน ar_poly: 1 - Σ φ_i B^i -> φ
+pub fn poly_to_ar(poly: &[f64]) -> Vec<f64> {
+    poly.iter().skip(1).map(|v| -v).collect()
+}
+
+/// ย้อน ma_poly: 1 + Σ θ_j B^j -> θ
+pub fn poly_to_ma(poly: &[f64]) -> Vec<f64> {
+    poly.iter().skip(1).copied().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff_poly_and_multiplicative_ar() {
+        assert_eq!(diff_poly(2, 0, 1), vec![1.0, -2.0, 1.0]);
+        // (1 - B)(1 - B^4) = 1 - B - B^4 + B^5
+        assert_eq!(diff_poly(1, 1, 4), vec![1.0, -1.0, 0.0, 0.0, -1.0, 1.0]);
+        // (1 - 0.5B)(1 - 0.3B^2) -> φ = [0.5, 0.3, -0.15]
+        let phi = poly_to_ar(&poly_mul(&ar_poly(&[0.5], 1), &ar_poly(&[0.3], 2)));
+        let expected = [0.5, 0.3, -0.15];
+        for (a, b) in phi.iter().zip(expected) {
+            assert!((a - b).abs() < 1e-12);
+        }
+    }
+}
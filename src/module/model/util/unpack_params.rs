@@ -0,0 +1,126 @@
+// แปลงพารามิเตอร์อิสระ (ℝ) เป็นสัมประสิทธิ์ ARMA ที่ stationary / invertible เสมอ
+// ผ่าน partial autocorrelation (Jones 1980, Monahan 1984):
+//   u_k --tanh--> r_k ∈ (-1, 1) --Durbin–Levinson--> φ
+// ทำให้ AR(p) stationary สำหรับทุก p ไม่ใช่แค่บังคับ |φ_i| < 1 ทีละตัว
+
+use crate::module::model::arma::ArmaParams;
+
+/// function convert parameters to arma parameter
+/// u = [c, u_ar(p), u_ma(q)] -> ArmaParams ที่ stationary และ invertible
+pub fn unpack_params(u: &[f64], p: usize, q: usize) -> ArmaParams {
+    ArmaParams {
+        c: u[0],
+        phi: unconstrained_to_ar(&u[1..1 + p]),
+        theta: unconstrained_to_ma(&u[1 + p..1 + p + q]),
+    }
+}
+
+/// ย้อนกลับของ unpack_params (ใช้ warm start จากพารามิเตอร์ที่ fit ไว้แล้ว)
+pub fn pack_params(par: &ArmaParams) -> Vec<f64> {
+    let mut u = Vec::with_capacity(1 + par.phi.len() + par.theta.len());
+    u.push(par.c);
+    u.extend(ar_to_unconstrained(&par.phi));
+    u.extend(ma_to_unconstrained(&par.theta));
+    u
+}
+
+/// partial autocorrelations r_1..r_k (|r| < 1) -> สัมประสิทธิ์ AR φ_1..φ_k
+/// ของพหุนาม 1 - φ_1 z - ... - φ_k z^k ซึ่งรากอยู่นอกวงกลมหนึ่งหน่วยเสมอ
+pub fn pacf_to_ar(r: &[f64]) -> Vec<f64> {
+    let k = r.len();
+    let mut phi = vec![0.0; k];
+    let mut prev = vec![0.0; k];
+    for j in 0..k {
+        phi[j] = r[j];
+        for i in 0..j {
+            phi[i] = prev[i] - r[j] * prev[j - 1 - i];
+        }
+        prev[..=j].copy_from_slice(&phi[..=j]);
+    }
+    phi
+}
+
+/// ย้อนกลับของ pacf_to_ar; คืน None ถ้า φ ไม่ stationary (มี |r_k| >= 1)
+pub fn ar_to_pacf(phi: &[f64]) -> Option<Vec<f64>> {
+    let k = phi.len();
+    let mut a = phi.to_vec();
+    let mut r = vec![0.0; k];
+    for j in (0..k).rev() {
+        let rj = a[j];
+        if !rj.is_finite() || rj.abs() >= 1.0 {
+            return None;
+        }
+        r[j] = rj;
+        let denom = 1.0 - rj * rj;
+        let mut next = vec![0.0; j];
+        for i in 0..j {
+            next[i] = (a[i] + rj * a[j - 1 - i]) / denom;
+        }
+        a = next;
+    }
+    Some(r)
+}
+
+/// u (อิสระ) -> φ ที่ stationary
+pub fn unconstrained_to_ar(u: &[f64]) -> Vec<f64> {
+    let r: Vec<f64> = u.iter().map(|v| v.tanh()).collect();
+    pacf_to_ar(&r)
+}
+
+/// u (อิสระ) -> θ ที่ invertible สำหรับพหุนาม MA แบบบวก 1 + θ_1 z + ... + θ_q z^q
+pub fn unconstrained_to_ma(u: &[f64]) -> Vec<f64> {
+    unconstrained_to_ar(u).into_iter().map(|a| -a).collect()
+}
+
+/// ย้อนกลับของ unconstrained_to_ar; ค่าที่ไม่ stationary ถูกหดเข้ามาก่อน (ใช้เป็นจุดเริ่มต้น)
+pub fn ar_to_unconstrained(phi: &[f64]) -> Vec<f64> {
+    let mut shrink: f64 = 1.0;
+    for _ in 0..50 {
+        let scaled: Vec<f64> = phi
+            .iter()
+            .enumerate()
+            .map(|(i, v)| v * shrink.powi(i as i32 + 1))
+            .collect();
+        if let Some(r) = ar_to_pacf(&scaled) {
+            return r
+                .into_iter()
+                .map(|x| x.clamp(-0.99, 0.99).atanh())
+                .collect();
+        }
+        shrink *= 0.9;
+    }
+    vec![0.0; phi.len()]
+}
+
+pub fn ma_to_unconstrained(theta: &[f64]) -> Vec<f64> {
+    let neg: Vec<f64> = theta.iter().map(|v| -v).collect();
+    ar_to_unconstrained(&neg)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pacf_roundtrip() {
+        let r = [0.5, -0.3, 0.2];
+        let phi = pacf_to_ar(&r);
+        let back = ar_to_pacf(&phi).unwrap();
+        for (a, b) in r.iter().zip(back.iter()) {
+            assert!((a - b).abs() < 1e-12);
+        }
+        // AR(2): r = (0.5, -0.3) -> φ1 = 0.5 - (-0.3)(0.5) = 0.65, φ2 = -0.3
+        let phi2 = pacf_to_ar(&[0.5, -0.3]);
+        assert!((phi2[0] - 0.65).abs() < 1e-12);
+        assert!((phi2[1] + 0.3).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_nonstationary_ar_is_rejected() {
+        // AR ใน arma.trading_view (φ = -1.0, -0.41) ยัง stationary, φ = (1.2, -0.1) ไม่ใช่
+        assert!(ar_to_pacf(&[-1.0, -0.41]).is_some());
+        assert!(ar_to_pacf(&[1.2, -0.1]).is_none());
+        let u = ar_to_unconstrained(&[1.2, -0.1]);
+        assert!(ar_to_pacf(&unconstrained_to_ar(&u)).is_some());
+    }
+}
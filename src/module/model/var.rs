@@ -0,0 +1,405 @@
+// VAR(p): y_t = c + A_1 y_{t-1} + ... + A_p y_{t-p} + u_t, u_t ~ (0, Σ)
+// ประมาณค่าด้วย OLS ทีละสมการ (เท่ากับ MLE แบบมีเงื่อนไขเมื่อ regressor เหมือนกันทุกสมการ)
+// data เป็นแถวตามเวลา: data[t][j] = ตัวแปร j ณ เวลา t
+
+use std::path::PathBuf;
+
+use nalgebra::{DMatrix, DVector};
+
+use crate::module::{
+    data::{align::log_volume, read_csv::read_bars},
+    eval::{TargetKind, ZeroRule, evaluator::Evaluator, skill::TestResult},
+    util::math::distribution::f_sf,
+};
+
+#[derive(Clone, Debug)]
+pub struct VarModel {
+    pub p: usize,
+    pub k: usize,
+    pub intercept: DVector<f64>,
+    /// A_1..A_p (k×k)
+    pub coefs: Vec<DMatrix<f64>>,
+    /// covariance ของ residual แบบ ML (U'U / T)
+    pub sigma: DMatrix<f64>,
+    pub n_obs: usize,
+    pub aic: f64,
+    pub bic: f64,
+    pub hqic: f64,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct VarIcRow {
+    pub p: usize,
+    pub aic: f64,
+    pub bic: f64,
+    pub hqic: f64,
+}
+
+#[derive(Clone, Debug)]
+pub struct VarOrderSelection {
+    pub table: Vec<VarIcRow>,
+    pub aic: usize,
+    pub bic: usize,
+    pub hqic: usize,
+}
+
+/// regressor ของเวลา t: [1, y_{t-1}', ..., y_{t-p}']
+fn lagged_design(data: &[Vec<f64>], p: usize, start: usize) -> DMatrix<f64> {
+    let k = data[0].len();
+    DMatrix::from_fn(data.len() - start, 1 + k * p, |r, c| {
+        if c == 0 {
+            1.0
+        } else {
+            let lag = (c - 1) / k + 1;
+            data[start + r - lag][(c - 1) % k]
+        }
+    })
+}
+
+fn targets(data: &[Vec<f64>], start: usize) -> DMatrix<f64> {
+    let k = data[0].len();
+    DMatrix::from_fn(data.len() - start, k, |r, c| data[start + r][c])
+}
+
+fn ols(x: &DMatrix<f64>, y: &DMatrix<f64>) -> Option<DMatrix<f64>> {
+    x.clone().svd(true, true).solve(y, 1e-12).ok()
+}
+
+fn is_valid(data: &[Vec<f64>]) -> bool {
+    let Some(k) = data.first().map(|r| r.len()) else {
+        return false;
+    };
+    k > 0
+        && data
+            .iter()
+            .all(|r| r.len() == k && r.iter().all(|v| v.is_finite()))
+}
+
+/// fit โดยใช้ t = start..n เป็น target (start ≥ p) เพื่อเทียบ IC บน sample เดียวกัน
+fn fit_var_sample(data: &[Vec<f64>], p: usize, start: usize) -> Option<VarModel> {
+    if !is_valid(data) || p == 0 || start < p {
+        return None;
+    }
+    let k = data[0].len();
+    let n_obs = data.len().checked_sub(start)?;
+    if n_obs <= 1 + k * p {
+        return None;
+    }
+    let x = lagged_design(data, p, start);
+    let y = targets(data, start);
+    let b = ols(&x, &y)?;
+    let resid = &y - &x * &b;
+    let sigma = resid.transpose() * &resid / n_obs as f64;
+
+    let intercept = DVector::from_fn(k, |i, _| b[(0, i)]);
+    let coefs = (0..p)
+        .map(|lag| DMatrix::from_fn(k, k, |i, j| b[(1 + lag * k + j, i)]))
+        .collect();
+
+    let det = sigma.determinant();
+    if det <= 0.0 || !det.is_finite() {
+        return None;
+    }
+    let t = n_obs as f64;
+    let n_par = (p * k * k) as f64;
+    let ln_det = det.ln();
+    Some(VarModel {
+        p,
+        k,
+        intercept,
+        coefs,
+        sigma,
+        n_obs,
+        aic: ln_det + 2.0 * n_par / t,
+        bic: ln_det + t.ln() * n_par / t,
+        hqic: ln_det + 2.0 * t.ln().ln() * n_par / t,
+    })
+}
+
+pub fn fit_var(data: &[Vec<f64>], p: usize) -> Option<VarModel> {
+    fit_var_sample(data, p, p)
+}
+
+/// เลือก p = 1..=max_p ด้วย AIC / BIC / HQIC (ตัด max_p แถวแรกทุก order ให้ sample เท่ากัน)
+pub fn select_var_order(data: &[Vec<f64>], max_p: usize) -> Option<VarOrderSelection> {
+    let table: Vec<VarIcRow> = (1..=max_p)
+        .filter_map(|p| fit_var_sample(data, p, max_p))
+        .map(|m| VarIcRow {
+            p: m.p,
+            aic: m.aic,
+            bic: m.bic,
+            hqic: m.hqic,
+        })
+        .collect();
+    let best = |ic: fn(&VarIcRow) -> f64| {
+        table
+            .iter()
+            .min_by(|a, b| ic(a).total_cmp(&ic(b)))
+            .map(|r| r.p)
+    };
+    Some(VarOrderSelection {
+        aic: best(|r| r.aic)?,
+        bic: best(|r| r.bic)?,
+        hqic: best(|r| r.hqic)?,
+        table,
+    })
+}
+
+/// Granger causality: H0 คือ lag ทั้ง p ตัวของตัวแปร cause ไม่ช่วยอธิบาย effect
+/// F = ((RSS_r - RSS_u) / p) / (RSS_u / (T - 1 - kp))
+pub fn granger_causality(data: &[Vec<f64>], p: usize, cause: usize, effect: usize) -> TestResult {
+    let nan = |n| TestResult {
+        statistic: f64::NAN,
+        p_value: f64::NAN,
+        n,
+    };
+    if !is_valid(data) || p == 0 || cause == effect {
+        return nan(0);
+    }
+    let k = data[0].len();
+    let n_obs = data.len().saturating_sub(p);
+    if cause >= k || effect >= k || n_obs <= 1 + k * p {
+        return nan(n_obs);
+    }
+    let x = lagged_design(data, p, p);
+    let y = targets(data, p).columns(effect, 1).into_owned();
+    let keep: Vec<usize> = (0..x.ncols())
+        .filter(|&c| c == 0 || (c - 1) % k != cause)
+        .collect();
+    let x_r = x.select_columns(keep.iter());
+
+    let rss = |x: &DMatrix<f64>| -> Option<f64> {
+        let b = ols(x, &y)?;
+        Some((&y - x * b).norm_squared())
+    };
+    let (Some(rss_u), Some(rss_r)) = (rss(&x), rss(&x_r)) else {
+        return nan(n_obs);
+    };
+    let d1 = p as f64;
+    let d2 = (n_obs - 1 - k * p) as f64;
+    let f = ((rss_r - rss_u) / d1) / (rss_u / d2);
+    TestResult {
+        statistic: f,
+        p_value: f_sf(f, d1, d2),
+        n: n_obs,
+    }
+}
+
+impl VarModel {
+    /// impulse response Ψ_0..Ψ_horizon ; Ψ_h[i][j] = ผลต่อตัวแปร i เมื่อ shock ตัวแปร j
+    /// orthogonalized = true ใช้ Cholesky ของ Σ (ลำดับตัวแปรมีผล)
+    pub fn impulse_response(&self, horizon: usize, orthogonalized: bool) -> Vec<DMatrix<f64>> {
+        let k = self.k;
+        let mut psi: Vec<DMatrix<f64>> = vec![DMatrix::identity(k, k)];
+        for h in 1..=horizon {
+            let mut m = DMatrix::zeros(k, k);
+            for (i, a) in self.coefs.iter().enumerate().take(h) {
+                m += a * &psi[h - 1 - i];
+            }
+            psi.push(m);
+        }
+        if orthogonalized {
+            let chol = self
+                .sigma
+                .clone()
+                .cholesky()
+                .map(|c| c.l())
+                .unwrap_or_else(|| DMatrix::identity(k, k));
+            psi.iter().map(|m| m * &chol).collect()
+        } else {
+            psi
+        }
+    }
+
+    /// พยากรณ์ h ก้าวต่อจาก history (ใช้ p แถวสุดท้าย)
+    pub fn forecast(&self, history: &[Vec<f64>], h: usize) -> Vec<Vec<f64>> {
+        let mut path: Vec<DVector<f64>> = history
+            .iter()
+            .rev()
+            .take(self.p)
+            .rev()
+            .map(|r| DVector::from_column_slice(r))
+            .collect();
+        if path.len() < self.p {
+            return Vec::new();
+        }
+        let mut out = Vec::with_capacity(h);
+        for _ in 0..h {
+            let n = path.len();
+            let mut next = self.intercept.clone();
+            for (i, a) in self.coefs.iter().enumerate() {
+                next += a * &path[n - 1 - i];
+            }
+            out.push(next.iter().copied().collect());
+            path.push(next);
+        }
+        out
+    }
+}
+
+/// สัญญาณทิศทางของตัวแปร target แบบ walk-forward:
+/// out[t] = ทายว่า data[t + 1][target] > 0 จาก VAR ที่ fit บน window แถวล่าสุด (refit ทุก refit_every แถว)
+pub fn var_signal_series(
+    data: &[Vec<f64>],
+    target: usize,
+    window: usize,
+    p: usize,
+    refit_every: usize,
+) -> Vec<Option<bool>> {
+    let n = data.len();
+    let mut out = vec![None; n];
+    let mut model: Option<VarModel> = None;
+    let mut since_fit = 0;
+    for t in window.saturating_sub(1)..n {
+        let hist = &data[t + 1 - window..=t];
+        if model.is_none() || since_fit >= refit_every.max(1) {
+            model = fit_var(hist, p).or(model);
+            since_fit = 0;
+        }
+        since_fit += 1;
+        if let Some(m) = &model {
+            out[t] = m
+                .forecast(hist, 1)
+                .first()
+                .and_then(|f| f.get(target))
+                .map(|v| *v > 0.0);
+        }
+    }
+    out
+}
+
+pub fn var_model() {
+    let data_path = PathBuf::from("data/SPX.csv");
+    let bars = match read_bars(&data_path) {
+        Ok(b) => b,
+        Err(e) => {
+            eprintln!("unable to read {}: {}", data_path.display(), e);
+            return;
+        }
+    };
+    let close: Vec<f64> = bars.iter().map(|b| b.close).collect();
+    let lv = log_volume(&bars);
+
+    // แถว = (log return, Δ log volume) ; index[i] คือ bar ของแถว i
+    let mut rows = Vec::new();
+    let mut index = Vec::new();
+    for t in 1..bars.len() {
+        if let (Some(a), Some(b)) = (lv[t - 1], lv[t])
+            && close[t - 1] > 0.0
+            && close[t] > 0.0
+        {
+            rows.push(vec![(close[t] / close[t - 1]).ln(), b - a]);
+            index.push(t);
+        }
+    }
+    if rows.len() < 100 {
+        eprintln!("VAR: not enough bars with volume ({})", rows.len());
+        return;
+    }
+
+    let Some(sel) = select_var_order(&rows, 8) else {
+        eprintln!("VAR order selection failed");
+        return;
+    };
+    println!("VAR lag selection (return, Δlog volume):");
+    for r in &sel.table {
+        println!(
+            "  p={} AIC={:.4} BIC={:.4} HQIC={:.4}",
+            r.p, r.aic, r.bic, r.hqic
+        );
+    }
+    println!("  best: AIC={} BIC={} HQIC={}", sel.aic, sel.bic, sel.hqic);
+
+    let p = sel.bic;
+    let vol_to_ret = granger_causality(&rows, p, 1, 0);
+    let ret_to_vol = granger_causality(&rows, p, 0, 1);
+    println!(
+        "  Granger volume -> return: F={:.3} p={:.4}",
+        vol_to_ret.statistic, vol_to_ret.p_value
+    );
+    println!(
+        "  Granger return -> volume: F={:.3} p={:.4}",
+        ret_to_vol.statistic, ret_to_vol.p_value
+    );
+
+    if let Some(model) = fit_var(&rows, p) {
+        let irf = model.impulse_response(5, true);
+        println!("  orthogonalized IRF of return to a volume shock:");
+        for (h, m) in irf.iter().enumerate() {
+            println!("    h={h}: {:.6e}", m[(0, 1)]);
+        }
+    }
+
+    // สัญญาณ: แถว i ทายทิศ return ของแถว i+1 -> ใช้ได้เฉพาะเมื่อเป็น bar ติดกัน
+    let sig_rows = var_signal_series(&rows, 0, 252, p, 21);
+    let mut signal = vec![None; close.len()];
+    for i in 0..rows.len().saturating_sub(1) {
+        if index[i + 1] == index[i] + 1 {
+            signal[index[i]] = sig_rows[i];
+        }
+    }
+    let evaluation =
+        Evaluator::new(TargetKind::Diff, ZeroRule::Ignore).evaluate_signals(&close, &signal);
+    println!(
+        "  VAR({p}) signal accuracy = {:.2}% (n={})",
+        evaluation.metrics.accuracy * 100.0,
+        evaluation.report.total
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::module::util::math::rng::SplitMix64;
+
+    /// x_t = 0.5 x_{t-1} + e1 ; y_t = 0.4 x_{t-1} + 0.2 y_{t-1} + e2 (x Granger-cause y)
+    fn simulate(n: usize) -> Vec<Vec<f64>> {
+        let mut rng = SplitMix64::new(21);
+        let mut draw = || rng.next_normal();
+        let mut data = vec![vec![0.0, 0.0]];
+        for t in 1..n {
+            let (x, y) = (data[t - 1][0], data[t - 1][1]);
+            data.push(vec![0.5 * x + draw(), 0.4 * x + 0.2 * y + draw()]);
+        }
+        data
+    }
+
+    #[test]
+    fn test_fit_var_recovers_coefficients() {
+        let data = simulate(3000);
+        let m = fit_var(&data, 1).unwrap();
+        let a = &m.coefs[0];
+        assert!((a[(0, 0)] - 0.5).abs() < 0.05, "{a}");
+        assert!(a[(0, 1)].abs() < 0.05, "{a}");
+        assert!((a[(1, 0)] - 0.4).abs() < 0.05, "{a}");
+        assert!((a[(1, 1)] - 0.2).abs() < 0.05, "{a}");
+
+        let sel = select_var_order(&data, 4).unwrap();
+        assert_eq!(sel.bic, 1);
+
+        let f = m.forecast(&data, 2);
+        let last = &data[data.len() - 1];
+        let step1 = m.intercept[0] + a[(0, 0)] * last[0] + a[(0, 1)] * last[1];
+        assert!((f[0][0] - step1).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_granger_direction() {
+        let data = simulate(2000);
+        assert!(granger_causality(&data, 2, 0, 1).p_value < 1e-6);
+        assert!(granger_causality(&data, 2, 1, 0).p_value > 0.01);
+    }
+
+    #[test]
+    fn test_impulse_response() {
+        let data = simulate(2000);
+        let m = fit_var(&data, 1).unwrap();
+        let irf = m.impulse_response(3, false);
+        assert_eq!(irf[0], DMatrix::identity(2, 2));
+        let a2 = &m.coefs[0] * &m.coefs[0];
+        assert!((&irf[2] - a2).norm() < 1e-12);
+        // orthogonalized h=0 = Cholesky factor: P P' = Σ
+        let o = m.impulse_response(0, true);
+        assert!((&o[0] * o[0].transpose() - &m.sigma).norm() < 1e-10);
+    }
+}
@@ -0,0 +1,48 @@
+// ของที่ทุกกราฟใช้ร่วมกัน: font, การแปลง timestamp และความถี่การสุ่มตัวอย่าง
+
+use chrono::{DateTime, TimeZone, Utc};
+
+/// plotters panic ถ้าหา font family ไม่เจอ -> export PLOT_FONT_FAMILY บนเครื่องที่ไม่มี DejaVu Sans
+pub(crate) fn font_family() -> String {
+    std::env::var("PLOT_FONT_FAMILY")
+        .ok()
+        .filter(|s| !s.is_empty())
+        .unwrap_or_else(|| "DejaVu Sans".to_string())
+}
+
+pub(crate) fn ts_to_datetime(ts: i64) -> Option<DateTime<Utc>> {
+    // รองรับทั้งวินาทีและมิลลิวินาที
+    let (secs, nanos) = if ts.abs() >= 1_000_000_000_000 {
+        let secs = ts.div_euclid(1000);
+        let rem_ms = ts.rem_euclid(1000) as u32;
+        (secs, rem_ms * 1_000_000)
+    } else {
+        (ts, 0u32)
+    };
+    Utc.timestamp_opt(secs, nanos).single()
+}
+
+/// จำนวนแท่งต่อวันจากช่วงห่างที่สั้นที่สุดระหว่าง timestamp (วินาที) ; ถ้าไม่มีช่วงห่างถือเป็นรายวัน
+pub(crate) fn samples_per_day(timestamps: &[i64]) -> f64 {
+    let dt_sec = timestamps
+        .windows(2)
+        .map(|w| (w[1] - w[0]).abs())
+        .filter(|&d| d > 0)
+        .min()
+        .unwrap_or(86400);
+    86400.0 / dt_sec as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_samples_per_day_uses_smallest_gap() {
+        // มีวันหยุดสุดสัปดาห์และ timestamp ซ้ำ -> ยังเป็นรายวัน
+        let daily = [0, 86400, 86400, 2 * 86400, 5 * 86400];
+        assert_eq!(samples_per_day(&daily), 1.0);
+        assert_eq!(samples_per_day(&[0, 3600, 7200]), 24.0);
+        assert_eq!(samples_per_day(&[42]), 1.0);
+    }
+}
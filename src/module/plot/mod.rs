@@ -1,2 +1,7 @@
+pub mod common;
 pub mod plot_fft;
+pub mod plot_forecast;
+pub mod plot_diagnostics;
 pub mod plot_graph;
+pub mod plot_reliability;
+pub mod plot_spectrogram;
@@ -0,0 +1,138 @@
+use crate::module::util::math::distribution::normal_quantile;
+use nalgebra::Complex;
+use plotters::prelude::*;
+use std::error::Error;
+
+fn ensure_parent(output_path: &str) -> Result<(), Box<dyn Error>> {
+    if let Some(parent) = std::path::Path::new(output_path).parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    Ok(())
+}
+
+/// Normal QQ plot ของ residual ที่ standardize แล้ว (Blom plotting positions)
+/// จุดควรอยู่บนเส้นทแยงถ้า residual เป็น normal ; หางที่ออกนอกเส้นคือ fat tail
+pub fn plot_qq(resid: &[f64], output_path: &str) -> Result<(), Box<dyn Error>> {
+    let n = resid.len();
+    if n < 3 {
+        return Err("need at least 3 residuals for a QQ plot".into());
+    }
+    ensure_parent(output_path)?;
+
+    let mean = resid.iter().sum::<f64>() / n as f64;
+    let sd = (resid.iter().map(|e| (e - mean).powi(2)).sum::<f64>() / (n - 1) as f64).sqrt();
+    let mut z: Vec<f64> = resid.iter().map(|e| (e - mean) / sd.max(1e-300)).collect();
+    z.sort_by(f64::total_cmp);
+    let points: Vec<(f64, f64)> = z
+        .iter()
+        .enumerate()
+        .map(|(i, &v)| {
+            let p = (i as f64 + 1.0 - 0.375) / (n as f64 + 0.25);
+            (normal_quantile(p), v)
+        })
+        .collect();
+
+    let lim = points
+        .iter()
+        .flat_map(|&(x, y)| [x.abs(), y.abs()])
+        .filter(|v| v.is_finite())
+        .fold(1.0, f64::max)
+        * 1.05;
+
+    let root = BitMapBackend::new(output_path, (800, 600)).into_drawing_area();
+    root.fill(&WHITE)?;
+
+    let mut chart = ChartBuilder::on(&root)
+        .caption("Normal Q-Q Plot (residuals)", ("sans-serif", 30))
+        .margin(10)
+        .x_label_area_size(40)
+        .y_label_area_size(50)
+        .build_cartesian_2d(-lim..lim, -lim..lim)?;
+
+    chart
+        .configure_mesh()
+        .x_desc("Theoretical quantile")
+        .y_desc("Sample quantile")
+        .draw()?;
+
+    chart.draw_series(LineSeries::new(
+        vec![(-lim, -lim), (lim, lim)],
+        BLACK.stroke_width(1),
+    ))?;
+    chart.draw_series(
+        points
+            .iter()
+            .map(|&(x, y)| Circle::new((x, y), 2, BLUE.filled())),
+    )?;
+
+    root.present()?;
+    println!("QQ plot saved to: {}", output_path);
+    Ok(())
+}
+
+/// inverse roots ของ AR (สีแดง) และ MA (สีน้ำเงิน) เทียบกับวงกลมหนึ่งหน่วย
+pub fn plot_unit_circle(
+    ar_roots: &[Complex<f64>],
+    ma_roots: &[Complex<f64>],
+    output_path: &str,
+) -> Result<(), Box<dyn Error>> {
+    ensure_parent(output_path)?;
+
+    let lim = ar_roots
+        .iter()
+        .chain(ma_roots)
+        .flat_map(|r| [r.re.abs(), r.im.abs()])
+        .fold(1.0, f64::max)
+        * 1.1;
+
+    let root = BitMapBackend::new(output_path, (640, 640)).into_drawing_area();
+    root.fill(&WHITE)?;
+
+    let mut chart = ChartBuilder::on(&root)
+        .caption("Inverse AR/MA roots", ("sans-serif", 30))
+        .margin(10)
+        .x_label_area_size(40)
+        .y_label_area_size(50)
+        .build_cartesian_2d(-lim..lim, -lim..lim)?;
+
+    chart
+        .configure_mesh()
+        .x_desc("Real")
+        .y_desc("Imaginary")
+        .draw()?;
+
+    chart.draw_series(LineSeries::new(
+        (0..=360).map(|d| {
+            let a = (d as f64).to_radians();
+            (a.cos(), a.sin())
+        }),
+        BLACK.stroke_width(1),
+    ))?;
+
+    chart
+        .draw_series(
+            ar_roots
+                .iter()
+                .map(|r| Circle::new((r.re, r.im), 6, RED.filled())),
+        )?
+        .label("AR")
+        .legend(|(x, y)| Circle::new((x, y), 5, RED.filled()));
+    chart
+        .draw_series(
+            ma_roots
+                .iter()
+                .map(|r| Cross::new((r.re, r.im), 6, BLUE.stroke_width(2))),
+        )?
+        .label("MA")
+        .legend(|(x, y)| Cross::new((x, y), 5, BLUE.stroke_width(2)));
+
+    chart
+        .configure_series_labels()
+        .background_style(WHITE.mix(0.8))
+        .border_style(BLACK)
+        .draw()?;
+
+    root.present()?;
+    println!("Unit circle plot saved to: {}", output_path);
+    Ok(())
+}
@@ -1,4 +1,5 @@
 use crate::module::data::read_csv::read_close_series;
+use crate::module::plot::common::{font_family, samples_per_day};
 use crate::module::util::function::fft_spectrum::fft_spectrum;
 use plotters::backend::BitMapBackend;
 use plotters::chart::ChartBuilder;
@@ -21,18 +22,12 @@ pub fn plot_fft(data_path: &PathBuf) -> Result<(), Box<dyn Error>> {
 
     //     extract closes and estimate sample
     let closes: Vec<f64> = pairs.iter().map(|(_, close)| *close).collect();
-    let mut dt_vec: Vec<i64> = pairs
-        .windows(2)
-        .map(|w| (w[1].0 - w[0].0).abs())
-        .filter(|&d| d > 0)
-        .collect();
-    dt_vec.sort();
-
-    let dt_sec = if !dt_vec.is_empty() { dt_vec[0] } else { 86400 };
-    let fs_per_day = 86400.0 / (dt_sec as f64);
+    let timestamps: Vec<i64> = pairs.iter().map(|(ts, _)| *ts).collect();
+    let fs_per_day = samples_per_day(&timestamps);
     println!(
         "[FFT] Sample interval: {} sec, fs_per_day: {:.2}",
-        fs_per_day, fs_per_day
+        86400.0 / fs_per_day,
+        fs_per_day
     );
 
     // Call util function to get spectrum
@@ -66,10 +61,7 @@ pub fn plot_fft(data_path: &PathBuf) -> Result<(), Box<dyn Error>> {
 
     let root = BitMapBackend::new(&out_path, (1280, 720)).into_drawing_area();
     root.fill(&WHITE)?;
-    let font_family = std::env::var("PLOT_FONT_FAMILY")
-        .ok()
-        .filter(|s| !s.is_empty())
-        .unwrap_or_else(|| "DejaVu Sans".to_string());
+    let font_family = font_family();
 
     let mut chart = ChartBuilder::on(&root)
         .margin(10)
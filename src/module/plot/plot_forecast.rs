@@ -0,0 +1,55 @@
+use plotters::prelude::*;
+use std::{error::Error, path::PathBuf};
+
+use crate::module::{
+    model::arma::ForecastStep,
+    plot::plot_graph::{Overlay, plot_graph_from_points_with},
+};
+
+/// history (เส้นน้ำเงิน) ต่อด้วย forecast (เส้นแดง) และแถบช่วงพยากรณ์ 95% / 80%
+pub fn plot_forecast(
+    history: &[(i64, f64)],
+    forecast: &[(i64, ForecastStep)],
+    title: &str,
+    output_path: &str,
+) -> Result<PathBuf, Box<dyn Error>> {
+    let Some(&last) = history.last() else {
+        return Err("No points provided".into());
+    };
+    if forecast.is_empty() {
+        return Err("No points provided".into());
+    }
+
+    // เส้นและแถบเริ่มจากจุดสุดท้ายของ history ให้ต่อเนื่อง
+    let band = |lo: fn(&ForecastStep) -> f64, hi: fn(&ForecastStep) -> f64| {
+        std::iter::once((last.0, last.1, last.1))
+            .chain(forecast.iter().map(|(ts, f)| (*ts, lo(f), hi(f))))
+            .collect()
+    };
+    let overlays = [
+        Overlay::Band {
+            label: "95%",
+            color: RED.mix(0.15),
+            points: band(|f| f.lo95, |f| f.hi95),
+        },
+        Overlay::Band {
+            label: "80%",
+            color: RED.mix(0.3),
+            points: band(|f| f.lo80, |f| f.hi80),
+        },
+        Overlay::Line {
+            label: "forecast",
+            color: RED,
+            points: std::iter::once(last)
+                .chain(forecast.iter().map(|(ts, f)| (*ts, f.mean)))
+                .collect(),
+        },
+    ];
+    plot_graph_from_points_with(
+        history,
+        "history",
+        title,
+        &overlays,
+        &PathBuf::from(output_path),
+    )
+}
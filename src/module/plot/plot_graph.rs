@@ -1,8 +1,13 @@
-use chrono::{DateTime, TimeZone, Utc};
+use chrono::{DateTime, Utc};
 use csv::{ReaderBuilder, StringRecord};
 use plotters::prelude::*;
 use serde::Deserialize;
-use std::{error::Error, path::PathBuf};
+
+use crate::module::plot::common::{font_family, ts_to_datetime};
+use std::{
+    error::Error,
+    path::{Path, PathBuf},
+};
 
 #[derive(Debug, Deserialize)]
 struct Quote {
@@ -11,18 +16,6 @@ struct Quote {
     close: f64,
 }
 
-fn ts_to_datetime(ts: i64) -> Option<DateTime<Utc>> {
-    // รองรับทั้งวินาทีและมิลลิวินาที
-    let (secs, nanos) = if ts.abs() >= 1_000_000_000_000 {
-        let secs = ts.div_euclid(1000);
-        let rem_ms = ts.rem_euclid(1000) as u32;
-        (secs, rem_ms * 1_000_000)
-    } else {
-        (ts, 0u32)
-    };
-    Utc.timestamp_opt(secs, nanos).single()
-}
-
 fn parse_points_headered(
     csv_path: &PathBuf,
 ) -> Result<(Vec<(DateTime<Utc>, f64)>, usize), Box<dyn Error>> {
@@ -316,25 +309,8 @@ pub fn plot_graph(csv_path: &PathBuf) -> Result<(), Box<dyn Error>> {
 
     // --- Font fallback handling -------------------------------------------------
     // plotters panics if the requested font family cannot be resolved on the system.
-    // Allow overriding via env var PLOT_FONT_FAMILY and provide several common fallbacks.
-    let font_family = std::env::var("PLOT_FONT_FAMILY")
-        .ok()
-        .filter(|s| !s.is_empty())
-        .unwrap_or_else(|| {
-            // Ordered fallback list (first assumed available will be used for all text)
-            // You can export PLOT_FONT_FAMILY to force a specific one.
-            for cand in [
-                "DejaVu Sans",     // very common on most Linux distros
-                "Liberation Sans", // fallback family
-                "Arial",           // common on many systems
-                "Noto Sans",       // Google Noto
-                "sans-serif",      // generic family (may still fail on minimal images)
-            ] {
-                return cand.to_string();
-            }
-            // Unreachable, but required by type inference
-            "sans-serif".to_string()
-        });
+    // Allow overriding via env var PLOT_FONT_FAMILY.
+    let font_family = font_family();
     println!("Using font family: {font_family}");
 
     println!("Building chart...");
@@ -396,9 +372,38 @@ pub fn plot_graph(csv_path: &PathBuf) -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
+/// เส้น / แถบที่วาดเพิ่มบน plot_graph_from_points_with (แกนเวลาเดียวกับ series หลัก)
+pub enum Overlay<'a> {
+    Line {
+        label: &'a str,
+        color: RGBColor,
+        points: Vec<(i64, f64)>,
+    },
+    /// แถบระหว่าง lo กับ hi: (timestamp, lo, hi)
+    Band {
+        label: &'a str,
+        color: RGBAColor,
+        points: Vec<(i64, f64, f64)>,
+    },
+}
+
+type DatedLine<'a> = (&'a str, RGBColor, Vec<(DateTime<Utc>, f64)>);
+type DatedBand<'a> = (&'a str, RGBAColor, Vec<(DateTime<Utc>, f64, f64)>);
+
 pub fn plot_graph_from_points(
     points_in: &[(i64, f64)],
     title: &str,
+) -> Result<PathBuf, Box<dyn Error>> {
+    let out_path = std::env::current_dir()?.join("data/diff_plot.png");
+    plot_graph_from_points_with(points_in, "diff", title, &[], &out_path)
+}
+
+pub fn plot_graph_from_points_with(
+    points_in: &[(i64, f64)],
+    label: &str,
+    title: &str,
+    overlays: &[Overlay],
+    out_path: &Path,
 ) -> Result<PathBuf, Box<dyn Error>> {
     if points_in.is_empty() {
         return Err("No points provided".into());
@@ -417,20 +422,65 @@ pub fn plot_graph_from_points(
     // sort by datetime
     pts.sort_by_key(|(d, _)| *d);
 
-    // x-range
-    let mut x0 = pts.first().unwrap().0;
-    let mut x1 = pts.last().unwrap().0;
+    let lines: Vec<DatedLine> = overlays
+        .iter()
+        .filter_map(|o| match o {
+            Overlay::Line {
+                label,
+                color,
+                points,
+            } => Some((
+                *label,
+                *color,
+                points
+                    .iter()
+                    .filter_map(|(ts, v)| ts_to_datetime(*ts).map(|d| (d, *v)))
+                    .collect(),
+            )),
+            Overlay::Band { .. } => None,
+        })
+        .collect();
+    let bands: Vec<DatedBand> = overlays
+        .iter()
+        .filter_map(|o| match o {
+            Overlay::Band {
+                label,
+                color,
+                points,
+            } => Some((
+                *label,
+                *color,
+                points
+                    .iter()
+                    .filter_map(|(ts, lo, hi)| ts_to_datetime(*ts).map(|d| (d, *lo, *hi)))
+                    .collect(),
+            )),
+            Overlay::Line { .. } => None,
+        })
+        .collect();
+
+    // x-range / y-range ครอบทั้ง series หลักและ overlay
+    let extent = pts
+        .iter()
+        .map(|&(d, v)| (d, v, v))
+        .chain(
+            lines
+                .iter()
+                .flat_map(|(_, _, l)| l.iter().map(|&(d, v)| (d, v, v))),
+        )
+        .chain(bands.iter().flat_map(|(_, _, b)| b.iter().copied()));
+    let (mut x0, mut x1) = (pts[0].0, pts[0].0);
+    let (mut y_min, mut y_max) = (f64::INFINITY, f64::NEG_INFINITY);
+    for (d, lo, hi) in extent {
+        x0 = x0.min(d);
+        x1 = x1.max(d);
+        y_min = y_min.min(lo);
+        y_max = y_max.max(hi);
+    }
     if x0 == x1 {
         x0 = x0 - chrono::Duration::minutes(1);
         x1 = x1 + chrono::Duration::minutes(1);
     }
-
-    // y-range with padding
-    let (mut y_min, mut y_max) = pts
-        .iter()
-        .fold((f64::INFINITY, f64::NEG_INFINITY), |(mn, mx), &(_, v)| {
-            (mn.min(v), mx.max(v))
-        });
     if (y_max - y_min).abs() < std::f64::EPSILON {
         y_min -= 1.0;
         y_max += 1.0;
@@ -441,19 +491,16 @@ pub fn plot_graph_from_points(
     }
 
     // prepare output path
-    let cwd = std::env::current_dir()?;
-    let out_path = cwd.join("data/diff_plot.png");
     if let Some(parent) = out_path.parent() {
         std::fs::create_dir_all(parent)?;
     }
 
     // plotting scope so backend is dropped before we return out_path
     {
-        let root = BitMapBackend::new(&out_path, (1200, 700)).into_drawing_area();
+        let root = BitMapBackend::new(out_path, (1200, 700)).into_drawing_area();
         root.fill(&WHITE)?;
 
-        let font_family =
-            std::env::var("PLOT_FONT_FAMILY").unwrap_or_else(|_| "DejaVu Sans".into());
+        let font_family = font_family();
 
         let mut chart = ChartBuilder::on(&root)
             .caption(title, (font_family.as_str(), 24))
@@ -482,10 +529,24 @@ pub fn plot_graph_from_points(
             .axis_desc_style((font_family.as_str(), 16))
             .draw()?;
 
+        // แถบวาดก่อนให้เส้นอยู่ด้านบน
+        for (band_label, color, band) in &bands {
+            let mut poly: Vec<(DateTime<Utc>, f64)> =
+                band.iter().map(|&(d, _, hi)| (d, hi)).collect();
+            poly.extend(band.iter().rev().map(|&(d, lo, _)| (d, lo)));
+            let color = *color;
+            chart
+                .draw_series(std::iter::once(Polygon::new(poly, color.filled())))?
+                .label(*band_label)
+                .legend(move |(x, y)| {
+                    Rectangle::new([(x, y - 5), (x + 20, y + 5)], color.filled())
+                });
+        }
+
         // draw line
         chart
             .draw_series(LineSeries::new(pts.iter().map(|(d, v)| (*d, *v)), &BLUE))?
-            .label("diff")
+            .label(label)
             .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 40, y)], &BLUE));
 
         // optional: sample markers (avoid clutter)
@@ -497,9 +558,18 @@ pub fn plot_graph_from_points(
                 .map(|(_, (d, v))| Circle::new((*d, *v), 2, BLUE.filled())),
         )?;
 
+        for (line_label, color, line) in &lines {
+            let color = *color;
+            chart
+                .draw_series(LineSeries::new(line.iter().copied(), &color))?
+                .label(*line_label)
+                .legend(move |(x, y)| PathElement::new(vec![(x, y), (x + 40, y)], color));
+        }
+
         chart
             .configure_series_labels()
             .border_style(&BLACK)
+            .background_style(WHITE.mix(0.8))
             .draw()?;
 
         drop(chart);
@@ -513,5 +583,5 @@ pub fn plot_graph_from_points(
         return Err("Plot file was not created".into());
     }
 
-    Ok(out_path)
+    Ok(out_path.to_path_buf())
 }
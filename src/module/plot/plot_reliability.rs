@@ -0,0 +1,51 @@
+use crate::module::eval::probabilistic::ReliabilityBin;
+use plotters::prelude::*;
+use std::error::Error;
+
+/// Reliability diagram: ความน่าจะเป็นที่พยากรณ์ (แกน x) เทียบความถี่ที่ขึ้นจริง (แกน y)
+/// จุดที่อยู่บนเส้นทแยงแปลว่า calibrate ดี ขนาดจุดตามจำนวนข้อมูลในช่วง
+pub fn plot_reliability(bins: &[ReliabilityBin], output_path: &str) -> Result<(), Box<dyn Error>> {
+    if bins.is_empty() {
+        return Err("no reliability bins to plot".into());
+    }
+    if let Some(parent) = std::path::Path::new(output_path).parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    let root = BitMapBackend::new(output_path, (800, 600)).into_drawing_area();
+    root.fill(&WHITE)?;
+
+    let mut chart = ChartBuilder::on(&root)
+        .caption("Reliability Diagram (P(up))", ("sans-serif", 30))
+        .margin(10)
+        .x_label_area_size(40)
+        .y_label_area_size(50)
+        .build_cartesian_2d(0f64..1f64, 0f64..1f64)?;
+
+    chart
+        .configure_mesh()
+        .x_desc("Forecast probability")
+        .y_desc("Observed frequency")
+        .draw()?;
+
+    // เส้น calibration สมบูรณ์
+    chart.draw_series(LineSeries::new(
+        vec![(0.0, 0.0), (1.0, 1.0)],
+        BLACK.stroke_width(1),
+    ))?;
+
+    chart.draw_series(LineSeries::new(
+        bins.iter().map(|b| (b.mean_pred, b.observed)),
+        &BLUE,
+    ))?;
+
+    let max_count = bins.iter().map(|b| b.count).max().unwrap_or(1).max(1) as f64;
+    chart.draw_series(bins.iter().map(|b| {
+        let r = 3.0 + 7.0 * (b.count as f64 / max_count).sqrt();
+        Circle::new((b.mean_pred, b.observed), r as i32, RED.filled())
+    }))?;
+
+    root.present()?;
+    println!("Reliability plot saved to: {}", output_path);
+    Ok(())
+}
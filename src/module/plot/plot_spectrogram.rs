@@ -0,0 +1,127 @@
+use crate::module::data::read_csv::read_close_series;
+use crate::module::plot::common::{font_family, samples_per_day, ts_to_datetime};
+use crate::module::util::function::stft::{Spectrogram, stft};
+use crate::module::util::windows::window_function::WindowFunction;
+use plotters::prelude::*;
+use std::error::Error;
+use std::path::{Path, PathBuf};
+
+// หน้าต่าง 2 ปี (resolution 0.5 cycles/year) เลื่อนทีละเดือน
+const WINDOW_LEN: usize = 504;
+const HOP: usize = 21;
+const FOCUS_MAX_CPY: f64 = 20.0;
+const FLOOR_DB: f64 = -40.0;
+
+/// spectrogram ของ log return จากไฟล์ close -> data/spectrogram.png
+/// ช่วงความถี่เดียวกับ plot_fft (0..20 cycles/year) ไว้ดูว่า cycle ที่เห็นใน FFT อยู่ตลอดหรือเฉพาะบางช่วง
+pub fn plot_spectrogram(data_path: &PathBuf) -> Result<(), Box<dyn Error>> {
+    println!("[STFT] Plotting spectrogram...");
+    if !data_path.exists() {
+        return Err(format!("data file not found: {:?}", data_path).into());
+    }
+    let pairs = read_close_series(data_path)?;
+    if pairs.len() <= WINDOW_LEN {
+        return Err("not enough data points".into());
+    }
+
+    let returns: Vec<f64> = pairs.windows(2).map(|w| (w[1].1 / w[0].1).ln()).collect();
+    // return ที่ t คือ bar t+1
+    let timestamps: Vec<i64> = pairs[1..].iter().map(|(ts, _)| *ts).collect();
+
+    let fs_per_day = samples_per_day(&pairs.iter().map(|(ts, _)| *ts).collect::<Vec<_>>());
+
+    let spec = stft(&returns, WINDOW_LEN, HOP, WindowFunction::Hann, fs_per_day)?;
+    let out_path = std::env::current_dir()?.join("data/spectrogram.png");
+    render_spectrogram(&spec, &timestamps, FOCUS_MAX_CPY, &out_path)?;
+    println!("[STFT] ✓ Saved {}", out_path.display());
+    Ok(())
+}
+
+/// วาด spectrogram เป็น heatmap: แกน x = เวลา (กึ่งกลาง frame), แกน y = cycles/year
+/// สีเป็น dB เทียบค่าสูงสุดในช่วงที่แสดง (น้ำเงิน = ต่ำ, แดง = สูง) ตัดที่ -40 dB
+pub fn render_spectrogram(
+    spec: &Spectrogram,
+    timestamps: &[i64],
+    max_cpy: f64,
+    out_path: &Path,
+) -> Result<(), Box<dyn Error>> {
+    if spec.magnitude.is_empty() || spec.freqs_cpy.len() < 2 {
+        return Err("empty spectrogram".into());
+    }
+    if let Some(parent) = out_path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    let df = spec.freqs_cpy[1] - spec.freqs_cpy[0];
+    let y1 = max_cpy
+        .min(*spec.freqs_cpy.last().unwrap_or(&max_cpy))
+        .max(df);
+    let bins: Vec<usize> = (1..spec.freqs_cpy.len())
+        .filter(|&k| spec.freqs_cpy[k] <= y1)
+        .collect();
+
+    let peak = spec
+        .magnitude
+        .iter()
+        .flat_map(|row| bins.iter().map(move |&k| row[k]))
+        .fold(0.0_f64, f64::max);
+    if peak <= 0.0 || !peak.is_finite() {
+        return Err("spectrogram has no energy in the focus range".into());
+    }
+
+    let centers = spec.centers();
+    let half_hop = spec.hop as f64 / 2.0;
+    let x0 = centers[0] - half_hop;
+    let x1 = centers[centers.len() - 1] + half_hop;
+
+    let root = BitMapBackend::new(out_path, (1280, 720)).into_drawing_area();
+    root.fill(&WHITE)?;
+    let font_family = font_family();
+
+    let caption = format!(
+        "Spectrogram of log returns (window {}, hop {})",
+        spec.window_len, spec.hop
+    );
+    let mut chart = ChartBuilder::on(&root)
+        .margin(10)
+        .caption(caption, (font_family.as_str(), 28))
+        .x_label_area_size(50)
+        .y_label_area_size(60)
+        .build_cartesian_2d(x0..x1, 0.0..y1 + df / 2.0)?;
+
+    let date_label = |x: &f64| {
+        timestamps
+            .get(x.round().max(0.0) as usize)
+            .and_then(|ts| ts_to_datetime(*ts))
+            .map(|dt| dt.format("%Y-%m").to_string())
+            .unwrap_or_default()
+    };
+    chart
+        .configure_mesh()
+        .disable_mesh()
+        .x_labels(10)
+        .y_labels(10)
+        .x_label_formatter(&date_label)
+        .label_style((font_family.as_str(), 14))
+        .axis_desc_style((font_family.as_str(), 16))
+        .x_desc("Time (window centre)")
+        .y_desc("Frequency (cycles/year)")
+        .draw()?;
+
+    let cells = spec.magnitude.iter().zip(&centers).flat_map(|(row, &c)| {
+        bins.iter().map(move |&k| {
+            let db = (20.0 * (row[k] / peak).log10()).max(FLOOR_DB);
+            let t = 1.0 - db / FLOOR_DB;
+            let f = spec.freqs_cpy[k];
+            Rectangle::new(
+                [(c - half_hop, f - df / 2.0), (c + half_hop, f + df / 2.0)],
+                HSLColor(0.66 * (1.0 - t), 1.0, 0.5).filled(),
+            )
+        })
+    });
+    chart.draw_series(cells)?;
+
+    drop(chart);
+    root.present()?;
+    Ok(())
+}
@@ -2,7 +2,7 @@ use std::path::PathBuf;
 
 use crate::module::{
     data::read_csv::read_close_series,
-    eval::{TargetKind, ZeroRule, calculate, evaluate_directional_accuracy},
+    eval::{TargetKind, ZeroRule, evaluator::Evaluator},
     model::{
         arma::{ArmaParams, fit_arma_with_ic},
         differencing::differencing,
@@ -90,13 +90,9 @@ fn evaluate_arma_percent(p: usize, q: usize) -> Option<ArmaMetricsPercent> {
     let levels: Vec<f64> = levels_pairs.iter().map(|(_, value)| *value).collect();
     let levels_for_eval = &levels[1..];
 
-    let report = evaluate_directional_accuracy(
-        levels_for_eval,
-        &predicted_diff,
-        TargetKind::Diff,
-        ZeroRule::Ignore,
-    );
-    let metrics = calculate(&report);
+    let metrics = Evaluator::new(TargetKind::Diff, ZeroRule::Ignore)
+        .evaluate_forecasts(levels_for_eval, &predicted_diff)
+        .metrics;
 
     Some(ArmaMetricsPercent {
         accuracy_pct: metrics.accuracy * 100.0,
@@ -2,11 +2,15 @@ use std::path::PathBuf;
 use std::time::Instant;
 
 use crate::module::data::read_csv::read_close_series;
-use crate::module::indicator::eval::{
-    EvalMetrics, eval_percent_ema_fast_slow, eval_percent_ema_sma,
-};
+use crate::module::eval::{ClassificationMetrics, DEFAULT_DEAD_BAND};
+use crate::module::indicator::decide::Strategy;
+use crate::module::indicator::eval::{eval_percent_ema_fast_slow, eval_percent_ema_sma};
+use crate::module::indicator::pine::{PineScriptKind, arma_script, strategy_script, write_script};
 use crate::module::model::{
-    arma::{ArmaModel, fit_arma_with_ic},
+    arma::{
+        ArmaModel,
+        select::{AutoArmaConfig, select_arma},
+    },
     ema::ema_series,
     sma::sma_series,
 };
@@ -28,30 +32,6 @@ fn format_percent_auto(v: f64) -> String {
 }
 
 // Helper functions for ARIMA (from arima.rs)
-fn diff_n(levels: &[f64], d: usize) -> Vec<f64> {
-    if d == 0 {
-        return levels.to_vec();
-    }
-    let mut out = levels.to_vec();
-    for _ in 0..d {
-        if out.len() < 2 {
-            return Vec::new();
-        }
-        let mut tmp = Vec::with_capacity(out.len().saturating_sub(1));
-        for t in 1..out.len() {
-            tmp.push(out[t] - out[t - 1]);
-        }
-        out = tmp;
-    }
-    out
-}
-
-fn invert_diff_1(levels: &[f64], diff_pred_next: &[f64]) -> Vec<f64> {
-    (0..diff_pred_next.len())
-        .map(|i| levels[i] + diff_pred_next[i])
-        .collect()
-}
-
 fn arma_residuals(y: &[f64], c: f64, phi: &[f64], theta: &[f64]) -> Vec<f64> {
     let n = y.len();
     let p = phi.len();
@@ -105,7 +85,7 @@ fn arma_predict_rolling(y: &[f64], model: &ArmaModel) -> Vec<f64> {
 
 #[derive(Debug)]
 pub struct EvalSnapshot {
-    pub(crate) metrics: EvalMetrics,
+    pub(crate) metrics: ClassificationMetrics,
 }
 
 fn load_close_prices(path: &PathBuf) -> Vec<f64> {
@@ -118,6 +98,13 @@ fn load_close_prices(path: &PathBuf) -> Vec<f64> {
     }
 }
 
+fn export_pine(path: &str, script: &str) {
+    match write_script(path, script) {
+        Ok(()) => println!("Pine script saved to: {}", path),
+        Err(e) => eprintln!("Error writing {}: {}", path, e),
+    }
+}
+
 pub fn run_search(data_path: PathBuf) {
     // ได้ datapath มาแล้วจะได้รู้ว่าเริ่มจากไฟล์ไหน
     let max_period = 100usize; // epoch
@@ -198,13 +185,25 @@ pub fn run_search(data_path: PathBuf) {
     println!("\n========== ARMA GRID SEARCH ==========");
     let arima_start = Instant::now();
 
-    // Load data for ARMA
-    let levels: Vec<f64> = close_prices.clone();
+    // ARMA ต้องใช้ series ที่ stationary -> fit บน log return แทนราคา
+    let returns: Vec<f64> = close_prices
+        .windows(2)
+        .filter(|w| w[0] > 0.0 && w[1] > 0.0)
+        .map(|w| (w[1] / w[0]).ln())
+        .collect();
 
-    for p in 1..10 {
-        for q in 1..=10 {
-            let _model = fit_arma_with_ic(&levels, p, q);
+    let config = AutoArmaConfig::new(9, 10).with_stepwise(false);
+    match select_arma(&returns, &config) {
+        Some(selection) => {
+            selection.print_table(10);
+            if let Some(best) = selection.best() {
+                export_pine(
+                    "output/pine/arma.pine",
+                    &arma_script(&best.model, PineScriptKind::Strategy),
+                );
+            }
         }
+        None => println!("ARMA search: not enough data"),
     }
 
     let arima_elapsed = arima_start.elapsed();
@@ -214,6 +213,7 @@ pub fn run_search(data_path: PathBuf) {
             &close_prices,
             ema_series(&close_prices, best_params_ema_sma.0),
             sma_series(&close_prices, best_params_ema_sma.1),
+            DEFAULT_DEAD_BAND,
         )
     } else {
         None
@@ -223,6 +223,7 @@ pub fn run_search(data_path: PathBuf) {
             &close_prices,
             ema_series(&close_prices, best_params_fast_slow.0),
             ema_series(&close_prices, best_params_fast_slow.1),
+            DEFAULT_DEAD_BAND,
         )
     } else {
         None
@@ -241,9 +242,9 @@ pub fn run_search(data_path: PathBuf) {
             println!(
                 "    metrics: accuracy={} precision={} recall={} f1={}\n",
                 format_percent_auto(snapshot.metrics.accuracy),
-                format_percent_auto(snapshot.metrics.precision_up),
-                format_percent_auto(snapshot.metrics.recall_up),
-                format_percent_auto(snapshot.metrics.f1_up)
+                format_percent_auto(snapshot.metrics.precision),
+                format_percent_auto(snapshot.metrics.recall),
+                format_percent_auto(snapshot.metrics.f1)
             );
         }
         None => {
@@ -264,13 +265,38 @@ pub fn run_search(data_path: PathBuf) {
             println!(
                 "    metrics: accuracy={} precision={} recall={} f1={}\n",
                 format_percent_auto(snapshot.metrics.accuracy),
-                format_percent_auto(snapshot.metrics.precision_up),
-                format_percent_auto(snapshot.metrics.recall_up),
-                format_percent_auto(snapshot.metrics.f1_up)
+                format_percent_auto(snapshot.metrics.precision),
+                format_percent_auto(snapshot.metrics.recall),
+                format_percent_auto(snapshot.metrics.f1)
             );
         }
         None => {
             println!("    metrics: unavailable (insufficient data)\n");
         }
     }
+
+    // Pine script ที่เติมค่าที่ดีที่สุดไว้แล้ว ใช้วางบน TradingView ได้ทันที
+    let best = [
+        (
+            best_val_ema_sma.is_finite(),
+            "output/pine/ema_sma.pine",
+            Strategy::EmaGtSma {
+                ema: best_params_ema_sma.0,
+                sma: best_params_ema_sma.1,
+            },
+        ),
+        (
+            best_val_fast_slow.is_finite(),
+            "output/pine/ema_fast_slow.pine",
+            Strategy::EmaFastGtEmaSlow {
+                fast: best_params_fast_slow.0,
+                slow: best_params_fast_slow.1,
+            },
+        ),
+    ];
+    for (_, path, strategy) in best.into_iter().filter(|(found, ..)| *found) {
+        if let Some(script) = strategy_script(strategy, PineScriptKind::Strategy) {
+            export_pine(path, &script);
+        }
+    }
 }
@@ -1,12 +1,13 @@
 use crate::module::{
-    indicator::eval::compute_metrics,
-    util::{debug::train::EvalSnapshot, function::find_real::eval_with_signals},
+    eval::{DeadBand, evaluator::Evaluator},
+    util::debug::train::EvalSnapshot,
 };
 
 pub fn evaluate_crossover(
     close: &[f64],
     lhs: Vec<Option<f64>>,
     rhs: Vec<Option<f64>>,
+    dead_band: DeadBand,
 ) -> Option<EvalSnapshot> {
     if close.is_empty() || lhs.len() != close.len() || rhs.len() != close.len() {
         return None;
@@ -19,7 +20,8 @@ pub fn evaluate_crossover(
             _ => None,
         })
         .collect();
-    let report = eval_with_signals(close, &signals);
-    let metrics = compute_metrics(&report);
+    let metrics = Evaluator::for_signals(dead_band)
+        .evaluate_signals(close, &signals)
+        .metrics;
     Some(EvalSnapshot { metrics })
 }
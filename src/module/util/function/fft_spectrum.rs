@@ -3,6 +3,9 @@ use rustfft::{FftPlanner, num_complex::Complex};
 use std::error::Error;
 use std::thread;
 
+/// วันเทรดต่อปี ใช้แปลงความถี่ของสเปกตรัมเป็น cycles/year
+pub const TRADING_DAYS_PER_YEAR: f64 = 252.0;
+
 /// Compute one-sided FFT amplitude spectrum (frequencies in cycles/year).
 /// returns (freqs_cpy, mags)
 pub fn fft_spectrum(
@@ -54,9 +57,9 @@ pub fn fft_spectrum(
         } else {
             mag *= 2.0 / (n as f64);
         }
-        // frequency in cycles/year (≈252 trading days)
+        // frequency in cycles/year (TRADING_DAYS_PER_YEAR trading days)
         let f_cpd = (fs_per_day / n as f64) * (k as f64);
-        let f_cpy = f_cpd * 252.0;
+        let f_cpy = f_cpd * TRADING_DAYS_PER_YEAR;
         freqs_cpy.push(f_cpy);
         mags.push(mag);
     }
@@ -1,4 +1,4 @@
 pub mod evaluate_cross_over;
 pub mod fft_spectrum;
-pub mod find_real;
 pub mod smooth_ma;
+pub mod stft;
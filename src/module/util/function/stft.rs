@@ -0,0 +1,139 @@
+use crate::module::util::function::fft_spectrum::TRADING_DAYS_PER_YEAR;
+use crate::module::util::windows::window_function::WindowFunction;
+use rustfft::{FftPlanner, num_complex::Complex};
+use std::error::Error;
+
+/// ผลของ STFT: magnitude[frame][bin] โดย frame ที่ i ครอบ data[starts[i]..starts[i] + window_len]
+#[derive(Debug, Clone)]
+pub struct Spectrogram {
+    pub window_len: usize,
+    pub hop: usize,
+    pub starts: Vec<usize>,
+    /// ความถี่ของแต่ละ bin เป็น cycles/year (TRADING_DAYS_PER_YEAR วัน/ปี) เหมือน fft_spectrum
+    pub freqs_cpy: Vec<f64>,
+    pub magnitude: Vec<Vec<f64>>,
+}
+
+impl Spectrogram {
+    /// index กึ่งกลางของแต่ละ frame ใน series เดิม
+    pub fn centers(&self) -> Vec<f64> {
+        self.starts
+            .iter()
+            .map(|&s| s as f64 + (self.window_len - 1) as f64 / 2.0)
+            .collect()
+    }
+
+    /// ความถี่ที่ magnitude สูงสุดในแต่ละ frame (ไม่นับ DC)
+    pub fn peak_frequencies(&self) -> Vec<f64> {
+        self.magnitude
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .enumerate()
+                    .skip(1)
+                    .max_by(|a, b| a.1.total_cmp(b.1))
+                    .map_or(0.0, |(k, _)| self.freqs_cpy[k])
+            })
+            .collect()
+    }
+}
+
+/// Short-time Fourier transform: ตัด data เป็นช่วงยาว window_len เลื่อนทีละ hop
+/// แต่ละช่วงลบ mean, คูณ window แล้วทำ one-sided amplitude spectrum
+/// (หารด้วยผลรวมของ window -> sine แอมพลิจูด A ให้ค่า ≈ A ไม่ขึ้นกับชนิด window)
+pub fn stft(
+    data: &[f64],
+    window_len: usize,
+    hop: usize,
+    window: WindowFunction,
+    fs_per_day: f64,
+) -> Result<Spectrogram, Box<dyn Error>> {
+    if window_len < 2 || hop == 0 {
+        return Err("window_len must be >= 2 and hop >= 1".into());
+    }
+    if data.len() < window_len {
+        return Err("not enough samples for one window".into());
+    }
+
+    let w = window.values(window_len);
+    let gain: f64 = w.iter().sum();
+    let fft = FftPlanner::<f64>::new().plan_fft_forward(window_len);
+    let half = window_len / 2;
+
+    let freqs_cpy: Vec<f64> = (0..=half)
+        .map(|k| fs_per_day / window_len as f64 * k as f64 * TRADING_DAYS_PER_YEAR)
+        .collect();
+
+    let starts: Vec<usize> = (0..=data.len() - window_len).step_by(hop).collect();
+    let magnitude = starts
+        .iter()
+        .map(|&s| {
+            let seg = &data[s..s + window_len];
+            let mean = seg.iter().sum::<f64>() / window_len as f64;
+            let mut buf: Vec<Complex<f64>> = seg
+                .iter()
+                .zip(&w)
+                .map(|(x, wi)| Complex::new((x - mean) * wi, 0.0))
+                .collect();
+            fft.process(&mut buf);
+            (0..=half)
+                .map(|k| {
+                    let scale = if k == 0 || (window_len.is_multiple_of(2) && k == half) {
+                        1.0
+                    } else {
+                        2.0
+                    };
+                    buf[k].norm() * scale / gain
+                })
+                .collect()
+        })
+        .collect();
+
+    Ok(Spectrogram {
+        window_len,
+        hop,
+        starts,
+        freqs_cpy,
+        magnitude,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::PI;
+
+    #[test]
+    fn test_stft_tracks_regime_change() {
+        // ครึ่งแรกคาบ 21 แท่ง (12 cycles/year) ครึ่งหลังคาบ 63 แท่ง (4 cycles/year)
+        let x: Vec<f64> = (0..1008)
+            .map(|t| {
+                let period = if t < 504 { 21.0 } else { 63.0 };
+                (2.0 * PI * t as f64 / period).sin()
+            })
+            .collect();
+        let spec = stft(&x, 252, 63, WindowFunction::Hann, 1.0).unwrap();
+
+        assert_eq!(
+            spec.starts,
+            vec![0, 63, 126, 189, 252, 315, 378, 441, 504, 567, 630, 693, 756]
+        );
+        assert_eq!(spec.freqs_cpy.len(), 127);
+        assert!((spec.freqs_cpy[1] - 1.0).abs() < 1e-12);
+
+        let peaks = spec.peak_frequencies();
+        assert!((peaks[0] - 12.0).abs() < 1e-9);
+        assert!((peaks[12] - 4.0).abs() < 1e-9);
+        // bin ตรงความถี่ -> amplitude ≈ 1
+        assert!((spec.magnitude[0][12] - 1.0).abs() < 0.02);
+        assert!((spec.centers()[0] - 125.5).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_stft_rejects_short_input() {
+        assert!(stft(&[1.0; 10], 20, 5, WindowFunction::Hann, 1.0).is_err());
+        assert!(stft(&[1.0; 10], 4, 0, WindowFunction::Hann, 1.0).is_err());
+        let w = WindowFunction::Blackman.values(5);
+        assert!(w[0].abs() < 1e-12 && (w[2] - 1.0).abs() < 1e-12);
+    }
+}
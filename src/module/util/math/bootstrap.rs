@@ -0,0 +1,227 @@
+// ช่วงความเชื่อมั่น (confidence interval) ของตัวชี้วัด
+// - Wilson score สำหรับสัดส่วน (accuracy / precision / recall)
+// - block / stationary bootstrap สำหรับข้อมูลอนุกรมเวลาที่มี autocorrelation
+
+use std::fmt;
+
+use crate::module::util::math::{distribution::two_sided_z, rng::SplitMix64};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Interval {
+    pub lo: f64,
+    pub hi: f64,
+}
+
+impl Interval {
+    pub fn nan() -> Self {
+        Self {
+            lo: f64::NAN,
+            hi: f64::NAN,
+        }
+    }
+
+    pub fn contains(&self, x: f64) -> bool {
+        self.lo <= x && x <= self.hi
+    }
+
+    /// แสดงผลเป็นเปอร์เซ็นต์ เช่น `[51.20%, 55.35%]`
+    pub fn to_percent_string(self) -> String {
+        format!("[{:.2}%, {:.2}%]", self.lo * 100.0, self.hi * 100.0)
+    }
+}
+
+impl fmt::Display for Interval {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{:.6}, {:.6}]", self.lo, self.hi)
+    }
+}
+
+/// Wilson score interval ของสัดส่วน successes / trials
+pub fn wilson_interval(successes: usize, trials: usize, level: f64) -> Interval {
+    if trials == 0 {
+        return Interval::nan();
+    }
+    let n = trials as f64;
+    let p = successes as f64 / n;
+    let z = two_sided_z(level);
+    let z2 = z * z;
+    let denom = 1.0 + z2 / n;
+    let center = (p + z2 / (2.0 * n)) / denom;
+    let half = z * (p * (1.0 - p) / n + z2 / (4.0 * n * n)).sqrt() / denom;
+    Interval {
+        lo: (center - half).max(0.0),
+        hi: (center + half).min(1.0),
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum BootstrapScheme {
+    /// Moving block bootstrap ความยาว block คงที่
+    Block { len: usize },
+    /// Stationary bootstrap (Politis–Romano) ความยาว block สุ่มแบบ geometric
+    Stationary { mean_len: f64 },
+}
+
+impl BootstrapScheme {
+    /// ความยาว block แบบ rule of thumb ~ n^(1/3)
+    pub fn default_for(n: usize) -> Self {
+        let mean_len = (n as f64).cbrt().max(1.0);
+        BootstrapScheme::Stationary { mean_len }
+    }
+}
+
+/// ระดับความเชื่อมั่นตั้งต้นของ interval ทั้ง Wilson และ bootstrap
+pub const DEFAULT_CONFIDENCE_LEVEL: f64 = 0.95;
+
+#[derive(Clone, Copy, Debug)]
+pub struct BootstrapConfig {
+    pub scheme: BootstrapScheme,
+    pub resamples: usize,
+    pub level: f64,
+    pub seed: u64,
+}
+
+impl BootstrapConfig {
+    pub fn new(n: usize) -> Self {
+        Self {
+            scheme: BootstrapScheme::default_for(n),
+            resamples: 1000,
+            level: DEFAULT_CONFIDENCE_LEVEL,
+            seed: 42,
+        }
+    }
+}
+
+/// สร้าง index ที่สุ่มซ้ำแบบ circular block ยาวเท่ากับข้อมูลเดิม
+pub fn resample_indices(n: usize, scheme: BootstrapScheme, rng: &mut SplitMix64) -> Vec<usize> {
+    let mut out = Vec::with_capacity(n);
+    if n == 0 {
+        return out;
+    }
+    match scheme {
+        BootstrapScheme::Block { len } => {
+            let len = len.clamp(1, n);
+            while out.len() < n {
+                let start = rng.next_below(n);
+                for k in 0..len {
+                    if out.len() == n {
+                        break;
+                    }
+                    out.push((start + k) % n);
+                }
+            }
+        }
+        BootstrapScheme::Stationary { mean_len } => {
+            let p_new = 1.0 / mean_len.max(1.0);
+            let mut idx = rng.next_below(n);
+            out.push(idx);
+            while out.len() < n {
+                idx = if rng.next_f64() < p_new {
+                    rng.next_below(n)
+                } else {
+                    (idx + 1) % n
+                };
+                out.push(idx);
+            }
+        }
+    }
+    out
+}
+
+/// percentile bootstrap interval ของสถิติ `stat` ที่คำนวณจากข้อมูลที่สุ่มซ้ำ
+pub fn bootstrap_interval<T, F>(data: &[T], config: &BootstrapConfig, mut stat: F) -> Interval
+where
+    T: Clone,
+    F: FnMut(&[T]) -> f64,
+{
+    let draws = bootstrap_draws(data, config, |sample| vec![stat(sample)]);
+    percentile_interval(&draws[0], config.level)
+}
+
+/// bootstrap หลายสถิติพร้อมกัน (ใช้ resample ชุดเดียวกัน) คืนค่า draws ของแต่ละสถิติ
+pub fn bootstrap_draws<T, F>(data: &[T], config: &BootstrapConfig, mut stats: F) -> Vec<Vec<f64>>
+where
+    T: Clone,
+    F: FnMut(&[T]) -> Vec<f64>,
+{
+    let n = data.len();
+    let mut rng = SplitMix64::new(config.seed);
+    let mut draws: Vec<Vec<f64>> = Vec::new();
+    let mut sample: Vec<T> = Vec::with_capacity(n);
+    for _ in 0..config.resamples {
+        sample.clear();
+        for i in resample_indices(n, config.scheme, &mut rng) {
+            sample.push(data[i].clone());
+        }
+        let values = stats(&sample);
+        if draws.is_empty() {
+            draws = vec![Vec::with_capacity(config.resamples); values.len()];
+        }
+        for (d, v) in draws.iter_mut().zip(values) {
+            d.push(v);
+        }
+    }
+    if draws.is_empty() {
+        draws.push(Vec::new());
+    }
+    draws
+}
+
+/// ช่วง percentile จาก draws (ตัด NaN ทิ้งก่อน)
+pub fn percentile_interval(draws: &[f64], level: f64) -> Interval {
+    let mut v: Vec<f64> = draws.iter().copied().filter(|x| x.is_finite()).collect();
+    if v.is_empty() {
+        return Interval::nan();
+    }
+    v.sort_by(|a, b| a.total_cmp(b));
+    let alpha = (1.0 - level) / 2.0;
+    Interval {
+        lo: quantile_sorted(&v, alpha),
+        hi: quantile_sorted(&v, 1.0 - alpha),
+    }
+}
+
+fn quantile_sorted(v: &[f64], q: f64) -> f64 {
+    let pos = q.clamp(0.0, 1.0) * (v.len() - 1) as f64;
+    let i = pos.floor() as usize;
+    let j = pos.ceil() as usize;
+    let w = pos - i as f64;
+    v[i] * (1.0 - w) + v[j] * w
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wilson_interval_reference() {
+        // 81 / 263 ที่ 95% -> [0.2553, 0.3662] (Wilson 1927, ตัวอย่างมาตรฐาน)
+        let ci = wilson_interval(81, 263, 0.95);
+        assert!((ci.lo - 0.2553).abs() < 1e-3);
+        assert!((ci.hi - 0.3662).abs() < 1e-3);
+        assert!(wilson_interval(0, 0, 0.95).lo.is_nan());
+    }
+
+    #[test]
+    fn test_resample_indices_length_and_range() {
+        let mut rng = SplitMix64::new(7);
+        for scheme in [
+            BootstrapScheme::Block { len: 5 },
+            BootstrapScheme::Stationary { mean_len: 4.0 },
+        ] {
+            let idx = resample_indices(37, scheme, &mut rng);
+            assert_eq!(idx.len(), 37);
+            assert!(idx.iter().all(|&i| i < 37));
+        }
+    }
+
+    #[test]
+    fn test_bootstrap_mean_interval_covers_sample_mean() {
+        let data: Vec<f64> = (0..200).map(|i| ((i * 37) % 11) as f64).collect();
+        let mean = data.iter().sum::<f64>() / data.len() as f64;
+        let config = BootstrapConfig::new(data.len());
+        let ci = bootstrap_interval(&data, &config, |s| s.iter().sum::<f64>() / s.len() as f64);
+        assert!(ci.contains(mean));
+        assert!(ci.hi - ci.lo > 0.0);
+    }
+}
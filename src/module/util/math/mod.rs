@@ -1 +1,4 @@
+pub mod bootstrap;
+pub mod distribution;
 pub mod percent;
+pub mod rng;
@@ -0,0 +1,40 @@
+// ตัวสุ่มเลขแบบง่าย (SplitMix64) ใช้กับ bootstrap / simulation ให้ผลซ้ำได้ด้วย seed
+
+use crate::module::util::math::distribution::normal_quantile;
+
+#[derive(Clone, Debug)]
+pub struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// uniform [0, 1)
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// N(0, 1) ด้วย inverse cdf (ตัด u ที่ขอบ 0 / 1 ไม่ให้ได้ ±∞)
+    pub fn next_normal(&mut self) -> f64 {
+        normal_quantile(self.next_f64().clamp(1e-12, 1.0 - 1e-12))
+    }
+
+    /// uniform integer in [0, n)
+    pub fn next_below(&mut self, n: usize) -> usize {
+        if n == 0 {
+            return 0;
+        }
+        (self.next_f64() * n as f64) as usize % n
+    }
+}
@@ -10,6 +10,30 @@ pub fn suggest_lag(n: usize) -> usize {
     l.clamp(1, 16)
 }
 
+/// stationary เมื่อ KPSS ไม่ปฏิเสธ H0 และ ADF ปฏิเสธ unit root ที่ระดับ 5%
+pub fn is_stationary(xs: &[f64]) -> bool {
+    if xs.len() < 10 {
+        return true;
+    }
+    let kpss_res = kpss::kpss_level(xs, None);
+    let (_, kpss_cv) = kpss::kpss_interpret_level(kpss_res.stat);
+    let adf_res = adf::adf_test_level(xs, suggest_lag(xs.len()));
+    let (_, adf_cv) = adf::adf_interpret_level(adf_res.t_stat);
+    kpss_res.stat <= kpss_cv[1] && adf_res.t_stat <= adf_cv[1]
+}
+
+/// จำนวนครั้งที่ต้อง difference จนผ่าน is_stationary (ไม่เกิน max_d)
+pub fn ndiffs(xs: &[f64], max_d: usize) -> usize {
+    let mut series = xs.to_vec();
+    for d in 0..max_d {
+        if is_stationary(&series) {
+            return d;
+        }
+        series = series.windows(2).map(|w| w[1] - w[0]).collect();
+    }
+    max_d
+}
+
 pub fn print_stationarity_checks(xs: &[f64]) {
     use crate::module::util::stationarity::{adf, kpss};
 
@@ -1 +1,2 @@
 pub mod hann;
+pub mod window_function;
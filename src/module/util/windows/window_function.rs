@@ -0,0 +1,30 @@
+use crate::module::util::windows::hann::hann_window;
+use std::f64::consts::PI;
+
+/// window สำหรับตัดช่วงก่อนทำ FFT (STFT) ; ทุกแบบเป็น symmetric ความยาว n
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WindowFunction {
+    Rectangular,
+    #[default]
+    Hann,
+    Hamming,
+    Blackman,
+}
+
+impl WindowFunction {
+    pub fn values(self, n: usize) -> Vec<f64> {
+        if n < 2 {
+            return vec![1.0; n];
+        }
+        let m = (n - 1) as f64;
+        let phase = |i: usize| 2.0 * PI * i as f64 / m;
+        match self {
+            WindowFunction::Rectangular => vec![1.0; n],
+            WindowFunction::Hann => hann_window(n),
+            WindowFunction::Hamming => (0..n).map(|i| 0.54 - 0.46 * phase(i).cos()).collect(),
+            WindowFunction::Blackman => (0..n)
+                .map(|i| 0.42 - 0.5 * phase(i).cos() + 0.08 * (2.0 * phase(i)).cos())
+                .collect(),
+        }
+    }
+}
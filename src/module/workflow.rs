@@ -1,8 +1,11 @@
 use crate::module::data::save_data::{save_file, save_file_log};
-use crate::module::model::arma::arma_model;
+use crate::module::model::arima::arima_model;
+use crate::module::model::arma::{arma_model, armax::armax_model};
 use crate::module::model::differencing::{differencing, differencing_with_time};
+use crate::module::model::var::var_model;
 use crate::module::plot::plot_fft::plot_fft;
 use crate::module::plot::plot_graph::{plot_graph, plot_graph_from_points};
+use crate::module::plot::plot_spectrogram::plot_spectrogram;
 use crate::module::util::debug::train::run_search;
 use std::path::PathBuf;
 
@@ -23,6 +26,7 @@ pub async fn workflow() {
     });
     let _ = plot_graph(&real_file_name);
     let _ = plot_fft(&real_file_name);
+    let _ = plot_spectrogram(&real_file_name);
 
     let data_path = PathBuf::from("data/SPX.csv");
     // println!("precent win: {:?}", eval_percent);
@@ -48,4 +52,7 @@ pub async fn workflow() {
 
     // arima model
     arma_model();
+    arima_model();
+    armax_model();
+    var_model();
 }